@@ -36,4 +36,5 @@ cargo test --all-targets --no-default-features --features serde &&
 cargo test --all-targets --no-default-features --features alloc &&
 cargo test --all-targets --features random &&
 cargo test --all-targets --features serde &&
-cargo test --all-targets --features random,profile-gas,profile-coverage,serde
\ No newline at end of file
+cargo test --all-targets --features random,profile-gas,profile-coverage,serde &&
+cargo test -p fuel-vm --all-targets --features calibration,debug-ecal,source-map
\ No newline at end of file
@@ -147,6 +147,8 @@ enum_from! {
         BytecodeAlreadyUploaded = 0x34,
         /// The part of the bytecode is not sequentially connected to the previous parts.
         ThePartIsNotSequentiallyConnected = 0x35,
+        /// The maximum depth of the nested contract calls is reached.
+        CallDepthExceeded = 0x36,
     }
 }
 
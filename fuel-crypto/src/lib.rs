@@ -42,6 +42,7 @@ mod mnemonic;
 mod secp256;
 
 pub mod ed25519;
+pub mod signer;
 
 pub use secp256::backend::r1 as secp256r1;
 
@@ -57,6 +58,10 @@ mod tests;
 pub use error::Error;
 pub use hasher::Hasher;
 pub use message::Message;
+pub use signer::{
+    AsyncSigner,
+    Signer,
+};
 
 #[cfg(all(feature = "std", feature = "random"))]
 pub use mnemonic::generate_mnemonic_phrase;
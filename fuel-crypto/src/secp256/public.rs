@@ -117,7 +117,10 @@ impl TryFrom<Bytes64> for PublicKey {
     type Error = Error;
 
     fn try_from(b: Bytes64) -> Result<Self, Self::Error> {
-        match VerifyingKey::from_sec1_bytes(&*b) {
+        // The SEC1 encoding of the uncompressed key starts with the tag byte
+        let mut sec1 = [0x04; PublicKey::LEN + 1];
+        sec1[1..].copy_from_slice(&*b);
+        match VerifyingKey::from_sec1_bytes(&sec1) {
             Ok(_) => Ok(Self(b)),
             Err(_) => Err(Error::InvalidPublicKey),
         }
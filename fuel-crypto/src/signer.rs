@@ -0,0 +1,295 @@
+//! Signing of the messages with the keys that may live outside of the process.
+//!
+//! The [`Signer`] produces the signatures synchronously, and the [`AsyncSigner`] is
+//! its asynchronous counterpart for the remote custody. Every [`Signer`] that can be
+//! shared between threads is also an [`AsyncSigner`].
+//!
+//! The [`StdioSigner`] talks to the [`serve`] loop of another process over a line-based
+//! protocol, so the secret key never enters the process of the application. Every
+//! request and response is a single line:
+//!
+//! - `public_key` is answered with `public_key <hex>` of the key of the signer;
+//! - `sign <hex>` of the message is answered with `signature <hex>`;
+//! - every request can be answered with `error <reason>` instead.
+
+use core::future::Future;
+
+use crate::{
+    Message,
+    PublicKey,
+    SecretKey,
+    Signature,
+};
+
+#[cfg(feature = "test-helpers")]
+use crate::Hasher;
+
+#[cfg(feature = "std")]
+pub use use_std::*;
+
+/// Produces the secp256k1 signatures of the messages.
+pub trait Signer {
+    /// The error of the signer.
+    type Error;
+
+    /// The public key of the signatures.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs the `message`.
+    fn sign(&self, message: &Message) -> Result<Signature, Self::Error>;
+}
+
+impl<S> Signer for &S
+where
+    S: Signer + ?Sized,
+{
+    type Error = S::Error;
+
+    fn public_key(&self) -> PublicKey {
+        S::public_key(self)
+    }
+
+    fn sign(&self, message: &Message) -> Result<Signature, Self::Error> {
+        S::sign(self, message)
+    }
+}
+
+impl Signer for SecretKey {
+    type Error = core::convert::Infallible;
+
+    fn public_key(&self) -> PublicKey {
+        SecretKey::public_key(self)
+    }
+
+    fn sign(&self, message: &Message) -> Result<Signature, Self::Error> {
+        Ok(Signature::sign(self, message))
+    }
+}
+
+/// The asynchronous [`Signer`], for the keys behind the network or the hardware.
+pub trait AsyncSigner: Send + Sync {
+    /// The error of the signer.
+    type Error: Send;
+
+    /// The public key of the signatures.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs the `message`.
+    fn sign(
+        &self,
+        message: &Message,
+    ) -> impl Future<Output = Result<Signature, Self::Error>> + Send;
+}
+
+impl<S> AsyncSigner for S
+where
+    S: Signer + Send + Sync,
+    S::Error: Send,
+{
+    type Error = S::Error;
+
+    fn public_key(&self) -> PublicKey {
+        Signer::public_key(self)
+    }
+
+    fn sign(
+        &self,
+        message: &Message,
+    ) -> impl Future<Output = Result<Signature, Self::Error>> + Send {
+        core::future::ready(Signer::sign(self, message))
+    }
+}
+
+/// The signer with the secret key derived from the seed, so the tests get the same keys
+/// and signatures on every run.
+#[cfg(feature = "test-helpers")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TestSigner {
+    secret: SecretKey,
+}
+
+#[cfg(feature = "test-helpers")]
+impl TestSigner {
+    /// Derives the signer from the `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut hash = Hasher::hash(seed.to_be_bytes());
+        loop {
+            match SecretKey::try_from(hash) {
+                Ok(secret) => return Self { secret },
+                Err(_) => hash = Hasher::hash(hash),
+            }
+        }
+    }
+
+    /// The secret key of the signer.
+    pub const fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+}
+
+#[cfg(feature = "test-helpers")]
+impl Signer for TestSigner {
+    type Error = core::convert::Infallible;
+
+    fn public_key(&self) -> PublicKey {
+        self.secret.public_key()
+    }
+
+    fn sign(&self, message: &Message) -> Result<Signature, Self::Error> {
+        Ok(Signature::sign(&self.secret, message))
+    }
+}
+
+#[cfg(feature = "std")]
+mod use_std {
+    use super::Signer;
+    use crate::{
+        Message,
+        PublicKey,
+        Signature,
+    };
+    use fuel_types::Bytes32;
+    use std::{
+        fmt,
+        io::{
+            self,
+            BufRead,
+            Write,
+        },
+        str::FromStr,
+        sync::Mutex,
+    };
+
+    /// The client of the signer served by another process with [`serve`].
+    ///
+    /// The `reader` and the `writer` are usually the stdout and the stdin of the child
+    /// process keeping the key.
+    #[derive(Debug)]
+    pub struct StdioSigner<R, W> {
+        public_key: PublicKey,
+        io: Mutex<(R, W)>,
+    }
+
+    impl<R, W> StdioSigner<R, W>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        /// Connects to the signer, requesting its public key.
+        pub fn connect(reader: R, writer: W) -> io::Result<Self> {
+            let mut io = (reader, writer);
+            let public_key = request(&mut io, "public_key", "public_key")?;
+            let public_key = PublicKey::from_str(&public_key).map_err(invalid_data)?;
+
+            Ok(Self {
+                public_key,
+                io: Mutex::new(io),
+            })
+        }
+
+        /// Returns the reader and the writer of the connection.
+        pub fn into_inner(self) -> (R, W) {
+            self.io
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+    }
+
+    impl<R, W> Signer for StdioSigner<R, W>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        type Error = io::Error;
+
+        fn public_key(&self) -> PublicKey {
+            self.public_key
+        }
+
+        fn sign(&self, message: &Message) -> Result<Signature, Self::Error> {
+            let mut io = self
+                .io
+                .lock()
+                .map_err(|_| io::Error::other("The signer panicked"))?;
+            let signature = request(&mut io, &format!("sign {message:x}"), "signature")?;
+            let signature = Signature::from_str(&signature).map_err(invalid_data)?;
+
+            // The remote signer is untrusted, so its signature is checked
+            signature
+                .verify(&self.public_key, message)
+                .map_err(invalid_data)?;
+            Ok(signature)
+        }
+    }
+
+    /// Sends the `request` and returns the value of the response with the `expected`
+    /// keyword.
+    fn request<R, W>(
+        (reader, writer): &mut (R, W),
+        request: &str,
+        expected: &str,
+    ) -> io::Result<String>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        writeln!(writer, "{request}")?;
+        writer.flush()?;
+
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        match line.trim_end().split_once(' ') {
+            Some((keyword, value)) if keyword == expected => Ok(value.to_owned()),
+            Some(("error", reason)) => Err(io::Error::other(reason)),
+            _ => Err(invalid_data(format!("Unexpected response: {line}"))),
+        }
+    }
+
+    fn invalid_data<E>(error: E) -> io::Error
+    where
+        E: fmt::Debug,
+    {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{error:?}"))
+    }
+
+    /// Answers the requests of the [`StdioSigner`] from the `reader` with the `signer`
+    /// until the end of the input.
+    ///
+    /// The errors of the signer and the malformed requests are reported to the client,
+    /// while the I/O errors stop the loop.
+    pub fn serve<S, R, W>(signer: &S, reader: R, mut writer: W) -> io::Result<()>
+    where
+        S: Signer + ?Sized,
+        S::Error: fmt::Display,
+        R: BufRead,
+        W: Write,
+    {
+        for line in reader.lines() {
+            let line = line?;
+            let response = match line.trim_end().split_once(' ') {
+                None if line.trim_end() == "public_key" => {
+                    format!("public_key {:x}", signer.public_key())
+                }
+                Some(("sign", message)) => match Bytes32::from_str(message) {
+                    Ok(message) => {
+                        match signer.sign(&Message::from_bytes(message.into())) {
+                            Ok(signature) => format!("signature {signature:x}"),
+                            // The reason must fit into the single line of the response
+                            Err(error) => {
+                                format!("error {}", error.to_string().replace('\n', " "))
+                            }
+                        }
+                    }
+                    Err(_) => "error Invalid message".to_owned(),
+                },
+                _ => "error Unknown request".to_owned(),
+            };
+            writeln!(writer, "{response}")?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
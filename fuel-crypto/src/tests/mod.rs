@@ -9,6 +9,12 @@ mod mnemonic;
 
 mod signature;
 
+#[cfg(feature = "std")]
+mod public;
+
+#[cfg(feature = "std")]
+mod signer;
+
 #[cfg(feature = "serde")]
 mod serde;
 #[cfg(not(feature = "serde"))]
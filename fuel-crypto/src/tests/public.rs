@@ -0,0 +1,44 @@
+use crate::{
+    Error,
+    PublicKey,
+    SecretKey,
+};
+use fuel_types::Bytes64;
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+
+use core::str::FromStr;
+
+#[test]
+fn try_from_bytes64_accepts_valid_public_keys() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    for _ in 0..100 {
+        let public = SecretKey::random(rng).public_key();
+        let bytes = Bytes64::new(*public);
+
+        assert_eq!(PublicKey::try_from(bytes), Ok(public));
+        assert_eq!(PublicKey::try_from(&bytes[..]), Ok(public));
+    }
+}
+
+#[test]
+fn try_from_bytes64_rejects_points_not_on_the_curve() {
+    for bytes in [Bytes64::zeroed(), Bytes64::new([0xff; Bytes64::LEN])] {
+        assert_eq!(PublicKey::try_from(bytes), Err(Error::InvalidPublicKey));
+    }
+}
+
+#[test]
+fn from_str_parses_the_hex_of_public_keys() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let public = SecretKey::random(rng).public_key();
+
+    assert_eq!(PublicKey::from_str(&format!("{public:x}")), Ok(public));
+    assert_eq!(
+        PublicKey::from_str(&format!("{:x}", Bytes64::zeroed())),
+        Err(Error::InvalidPublicKey)
+    );
+}
@@ -0,0 +1,104 @@
+use crate::{
+    signer::{
+        serve,
+        StdioSigner,
+        TestSigner,
+    },
+    Message,
+    SecretKey,
+    Signature,
+    Signer,
+};
+
+use rand::{
+    rngs::StdRng,
+    SeedableRng,
+};
+use std::io::{
+    self,
+    Cursor,
+};
+
+#[test]
+fn secret_key_signs_like_signature_sign() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let message = Message::new(b"Secret key signer");
+
+    let signature = Signer::sign(&secret, &message).unwrap();
+
+    assert_eq!(signature, Signature::sign(&secret, &message));
+    assert_eq!(Signer::public_key(&secret), secret.public_key());
+}
+
+#[test]
+fn test_signer_derives_the_same_key_from_the_seed() {
+    let message = Message::new(b"Test signer");
+
+    let signer = TestSigner::new(1);
+
+    assert_eq!(signer, TestSigner::new(1));
+    assert_ne!(signer.public_key(), TestSigner::new(2).public_key());
+    assert_eq!(signer.public_key(), signer.secret().public_key());
+    let signature = signer.sign(&message).unwrap();
+    assert_eq!(signature.verify(&signer.public_key(), &message), Ok(()));
+}
+
+#[test]
+fn stdio_signer_signs_through_the_served_signer() {
+    let signer = TestSigner::new(1);
+    let message = Message::new(b"Stdio signer");
+    let requests = format!("public_key\nsign {message:x}\n");
+    let mut responses = Vec::new();
+    serve(&signer, requests.as_bytes(), &mut responses).expect("The requests are served");
+
+    let client = StdioSigner::connect(Cursor::new(responses), Vec::new())
+        .expect("The public key is received");
+    let signature = client.sign(&message).expect("The message is signed");
+
+    assert_eq!(client.public_key(), signer.public_key());
+    assert_eq!(signature, signer.sign(&message).unwrap());
+    let (_, sent) = client.into_inner();
+    assert_eq!(sent, requests.into_bytes());
+}
+
+#[test]
+fn stdio_signer_rejects_errors_and_invalid_signatures() {
+    let signer = TestSigner::new(1);
+    let message = Message::new(b"Stdio signer");
+
+    let error = StdioSigner::connect("error The key is locked\n".as_bytes(), Vec::new())
+        .expect_err("The signer responds with the error");
+    assert_eq!(error.kind(), io::ErrorKind::Other);
+    assert_eq!(error.to_string(), "The key is locked");
+
+    let error = StdioSigner::connect("".as_bytes(), Vec::new())
+        .expect_err("The signer doesn't respond");
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    let other_signature = TestSigner::new(2).sign(&message).unwrap();
+    let responses = format!(
+        "public_key {:x}\nsignature {other_signature:x}\n",
+        signer.public_key()
+    );
+    let client = StdioSigner::connect(responses.as_bytes(), Vec::new())
+        .expect("The public key is received");
+    let error = client
+        .sign(&message)
+        .expect_err("The signature of the other key");
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn serve_answers_malformed_requests_with_errors() {
+    let signer = TestSigner::new(1);
+    let mut responses = Vec::new();
+
+    serve(&signer, "sign 0x12\nsign_all\n".as_bytes(), &mut responses)
+        .expect("The requests are served");
+
+    assert_eq!(
+        String::from_utf8(responses).unwrap(),
+        "error Invalid message\nerror Unknown request\n"
+    );
+}
@@ -5,7 +5,9 @@ use crate::{
         field::{
             self,
             BytecodeWitnessIndex,
+            Expiration,
             Maturity,
+            Owner,
             Tip,
             Witnesses,
         },
@@ -57,7 +59,13 @@ use alloc::{
     collections::BTreeMap,
     vec::Vec,
 };
-use fuel_crypto::SecretKey;
+use fuel_crypto::{
+    AsyncSigner,
+    Message,
+    PublicKey,
+    SecretKey,
+    Signer,
+};
 use fuel_types::{
     AssetId,
     BlockHeight,
@@ -67,6 +75,14 @@ use fuel_types::{
     Word,
 };
 
+mod funding;
+
+pub use funding::{
+    FundingError,
+    ResourceOwner,
+    SpendableResource,
+};
+
 pub trait BuildableAloc
 where
     Self: Default + Clone + Executable + Chargeable + field::Policies + Into<Transaction>,
@@ -355,6 +371,18 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    pub fn expiration(&mut self, expiration: BlockHeight) -> &mut Self {
+        self.tx.set_expiration(expiration);
+
+        self
+    }
+
+    pub fn owner_index(&mut self, input_index: u16) -> &mut Self {
+        self.tx.set_owner_index(input_index);
+
+        self
+    }
+
     pub fn witness_limit(&mut self, witness_limit: Word) -> &mut Self {
         self.tx.set_witness_limit(witness_limit);
 
@@ -432,6 +460,54 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    /// Adds the coin input signed by the external signer with the `public_key` on
+    /// [`TransactionBuilder::finalize_with_signer`].
+    pub fn add_signer_coin_input(
+        &mut self,
+        public_key: &PublicKey,
+        utxo_id: crate::UtxoId,
+        amount: Word,
+        asset_id: AssetId,
+        tx_pointer: TxPointer,
+    ) -> &mut Self {
+        let witness_index = self.upsert_owner(public_key);
+
+        self.tx.add_unsigned_coin_input(
+            utxo_id,
+            public_key,
+            amount,
+            asset_id,
+            tx_pointer,
+            witness_index,
+        );
+
+        self
+    }
+
+    /// Adds the message input signed by the external signer with the `public_key` on
+    /// [`TransactionBuilder::finalize_with_signer`].
+    pub fn add_signer_message_input(
+        &mut self,
+        public_key: &PublicKey,
+        sender: fuel_types::Address,
+        nonce: Nonce,
+        amount: Word,
+        data: Vec<u8>,
+    ) -> &mut Self {
+        let witness_index = self.upsert_owner(public_key);
+
+        self.tx.add_unsigned_message_input(
+            sender,
+            Input::owner(public_key),
+            nonce,
+            amount,
+            data,
+            witness_index,
+        );
+
+        self
+    }
+
     pub fn inputs(&self) -> &[Input] {
         self.tx.inputs()
     }
@@ -474,17 +550,80 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         *witness_index
     }
 
+    /// Returns the witness index of the signed inputs owned by the `public_key`, and adds
+    /// a witness for them if it's a new owner
+    fn upsert_owner(&mut self, public_key: &PublicKey) -> u16 {
+        let owner = Input::owner(public_key);
+        let witness_index = self.tx.inputs().iter().find_map(|input| {
+            let input_owner = input.input_owner().or_else(|| input.recipient())?;
+            (input_owner == &owner)
+                .then_some(input.witness_index())
+                .flatten()
+        });
+        if let Some(witness_index) = witness_index {
+            return witness_index;
+        }
+
+        let witness_len = u16::try_from(self.witnesses().len())
+            .expect("The number of witnesses can't exceed `u16::MAX`");
+
+        if u32::from(witness_len) > self.params.tx_params().max_witnesses() {
+            panic!("Max witnesses exceeded");
+        }
+
+        self.tx.witnesses_mut().push(Witness::default());
+        witness_len
+    }
+
     fn finalize_inner(&self) -> Tx {
+        let mut tx = self.sign_with_keys();
+
+        tx.precompute(&self.get_chain_id())
+            .expect("Should be able to calculate cache");
+
+        tx
+    }
+
+    fn sign_with_keys(&self) -> Tx {
         let mut tx = self.tx.clone();
 
         self.sign_keys
             .iter()
             .for_each(|(k, _)| tx.sign_inputs(k, &self.get_chain_id()));
 
+        tx
+    }
+
+    /// Finalizes the transaction, signing the inputs of the `signer` in addition to the
+    /// inputs of the secret keys of the builder.
+    pub fn finalize_with_signer<S>(&self, signer: &S) -> Result<Tx, S::Error>
+    where
+        S: Signer + ?Sized,
+    {
+        let mut tx = self.sign_with_keys();
+
+        tx.sign_inputs_with(signer, &self.get_chain_id())?;
         tx.precompute(&self.get_chain_id())
             .expect("Should be able to calculate cache");
 
-        tx
+        Ok(tx)
+    }
+
+    /// Finalizes the transaction like [`TransactionBuilder::finalize_with_signer`] with
+    /// the asynchronous `signer`.
+    pub async fn finalize_with_async_signer<S>(&self, signer: &S) -> Result<Tx, S::Error>
+    where
+        S: AsyncSigner + ?Sized,
+    {
+        let mut tx = self.sign_with_keys();
+
+        let id = tx.id(&self.get_chain_id());
+        let signature = signer.sign(&Message::from_bytes(*id)).await?;
+        tx.set_input_signatures(&signer.public_key(), &signature);
+        tx.precompute(&self.get_chain_id())
+            .expect("Should be able to calculate cache");
+
+        Ok(tx)
     }
 
     pub fn finalize_without_signature_inner(&self) -> Tx {
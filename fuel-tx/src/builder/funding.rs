@@ -0,0 +1,329 @@
+use crate::{
+    field::MaxFeeLimit,
+    Input,
+    Output,
+    TxPointer,
+    UtxoId,
+    Witness,
+};
+
+use super::{
+    Buildable,
+    TransactionBuilder,
+};
+
+use alloc::{
+    collections::BTreeMap,
+    vec,
+    vec::Vec,
+};
+use fuel_crypto::{
+    SecretKey,
+    Signature,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    Nonce,
+    Word,
+};
+
+/// The owner of the spendable resource, able to unlock it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceOwner {
+    /// The resource is owned by the public key of the secret and unlocked by its
+    /// signature.
+    Signer(SecretKey),
+    /// The resource is owned by the predicate and unlocked by its execution.
+    Predicate {
+        /// The bytecode of the predicate.
+        predicate: Vec<u8>,
+        /// The data passed to the predicate.
+        predicate_data: Vec<u8>,
+        /// The estimated gas used by the predicate.
+        predicate_gas_used: Word,
+    },
+}
+
+impl ResourceOwner {
+    /// The address owning the resource.
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Signer(secret) => Input::owner(&secret.public_key()),
+            Self::Predicate { predicate, .. } => Input::predicate_owner(predicate),
+        }
+    }
+}
+
+/// The coin or the message without data available to fund the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpendableResource {
+    /// The unspent coin.
+    Coin {
+        /// The id of the coin.
+        utxo_id: UtxoId,
+        /// The amount of the coin.
+        amount: Word,
+        /// The asset of the coin.
+        asset_id: AssetId,
+        /// The position of the transaction that created the coin.
+        tx_pointer: TxPointer,
+        /// The owner of the coin.
+        owner: ResourceOwner,
+    },
+    /// The unspent message of the base asset.
+    Message {
+        /// The sender of the message.
+        sender: Address,
+        /// The nonce of the message.
+        nonce: Nonce,
+        /// The amount of the message.
+        amount: Word,
+        /// The recipient of the message.
+        owner: ResourceOwner,
+    },
+}
+
+impl SpendableResource {
+    /// The amount of the resource.
+    pub const fn amount(&self) -> Word {
+        match self {
+            Self::Coin { amount, .. } | Self::Message { amount, .. } => *amount,
+        }
+    }
+
+    /// The asset of the resource, the messages hold the `base_asset_id`.
+    pub const fn asset_id<'a>(&'a self, base_asset_id: &'a AssetId) -> &'a AssetId {
+        match self {
+            Self::Coin { asset_id, .. } => asset_id,
+            Self::Message { .. } => base_asset_id,
+        }
+    }
+}
+
+/// The error funding the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Display)]
+#[non_exhaustive]
+pub enum FundingError {
+    /// The resources of the asset don't cover the outputs and the fee.
+    #[display(
+        fmt = "Insufficient funds of the asset {asset_id}: required {required}, \
+               available {available}"
+    )]
+    InsufficientFunds {
+        /// The asset missing funds.
+        asset_id: AssetId,
+        /// The amount required by the outputs and the fee.
+        required: u128,
+        /// The amount of the inputs and the spendable resources.
+        available: u128,
+    },
+    /// The maximum fee of the transaction doesn't fit into a `Word`.
+    #[display(fmt = "The maximum fee of the transaction overflows")]
+    FeeOverflow,
+}
+
+impl<Tx: Buildable> TransactionBuilder<Tx> {
+    /// Adds the inputs from the `resources` covering the coin outputs and the maximum
+    /// fee of the transaction at the `gas_price`, and the `Change` outputs to the
+    /// `change_to` for every asset of the inputs.
+    ///
+    /// The largest resources of every asset are selected first. The maximum fee is
+    /// estimated with the signatures of the inputs, and set as the `MaxFee` policy.
+    /// The signers of the selected resources sign the transaction when it's
+    /// finalized, while the predicates are used as is.
+    pub fn fund<I>(
+        &mut self,
+        resources: I,
+        change_to: Address,
+        gas_price: Word,
+    ) -> Result<&mut Self, FundingError>
+    where
+        I: IntoIterator<Item = SpendableResource>,
+    {
+        let base_asset_id = *self.params.base_asset_id();
+
+        let mut required = BTreeMap::<AssetId, u128>::new();
+        for output in self.tx.outputs() {
+            if let Output::Coin {
+                amount, asset_id, ..
+            } = output
+            {
+                let entry = required.entry(*asset_id).or_default();
+                *entry = entry.saturating_add(*amount as u128);
+            }
+        }
+
+        let mut funded = BTreeMap::<AssetId, u128>::new();
+        for input in self.tx.inputs() {
+            if let (
+                Input::CoinSigned(_)
+                | Input::CoinPredicate(_)
+                | Input::MessageCoinSigned(_)
+                | Input::MessageCoinPredicate(_),
+                Some(amount),
+                Some(asset_id),
+            ) = (input, input.amount(), input.asset_id(&base_asset_id))
+            {
+                let entry = funded.entry(*asset_id).or_default();
+                *entry = entry.saturating_add(amount as u128);
+            }
+        }
+
+        // The largest resources are the last ones, so they are popped first
+        let mut available = BTreeMap::<AssetId, Vec<SpendableResource>>::new();
+        for resource in resources {
+            available
+                .entry(*resource.asset_id(&base_asset_id))
+                .or_default()
+                .push(resource);
+        }
+        for resources in available.values_mut() {
+            resources.sort_by_key(SpendableResource::amount);
+        }
+
+        for (asset_id, required) in required.iter() {
+            if *asset_id != base_asset_id {
+                self.select(asset_id, *required, &mut funded, &mut available)?;
+            }
+        }
+
+        // The fee depends on the size of the transaction, so the base asset is selected
+        // until it covers the fee of the transaction with the selected inputs.
+        let required_base = required.get(&base_asset_id).copied().unwrap_or_default();
+        loop {
+            self.add_change_outputs(&funded, change_to);
+            let max_fee = self.estimate_max_fee(gas_price)?;
+            self.tx.set_max_fee_limit(max_fee);
+
+            let required = required_base.saturating_add(max_fee as u128);
+            let funded_base = funded.get(&base_asset_id).copied().unwrap_or_default();
+            if funded_base >= required {
+                break;
+            }
+            self.select(&base_asset_id, required, &mut funded, &mut available)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Adds the largest `available` resources of the asset until the `funded` amount
+    /// covers the `required` one.
+    fn select(
+        &mut self,
+        asset_id: &AssetId,
+        required: u128,
+        funded: &mut BTreeMap<AssetId, u128>,
+        available: &mut BTreeMap<AssetId, Vec<SpendableResource>>,
+    ) -> Result<(), FundingError> {
+        let funded = funded.entry(*asset_id).or_default();
+        let resources = available.entry(*asset_id).or_default();
+
+        while *funded < required {
+            let Some(resource) = resources.pop() else {
+                return Err(FundingError::InsufficientFunds {
+                    asset_id: *asset_id,
+                    required,
+                    available: *funded,
+                });
+            };
+            *funded = funded.saturating_add(resource.amount() as u128);
+            self.add_resource(resource);
+        }
+        Ok(())
+    }
+
+    fn add_resource(&mut self, resource: SpendableResource) {
+        match resource {
+            SpendableResource::Coin {
+                utxo_id,
+                amount,
+                asset_id,
+                tx_pointer,
+                owner: ResourceOwner::Signer(secret),
+            } => {
+                self.add_unsigned_coin_input(
+                    secret, utxo_id, amount, asset_id, tx_pointer,
+                );
+            }
+            SpendableResource::Coin {
+                utxo_id,
+                amount,
+                asset_id,
+                tx_pointer,
+                owner:
+                    ResourceOwner::Predicate {
+                        predicate,
+                        predicate_data,
+                        predicate_gas_used,
+                    },
+            } => {
+                self.add_input(Input::coin_predicate(
+                    utxo_id,
+                    Input::predicate_owner(&predicate),
+                    amount,
+                    asset_id,
+                    tx_pointer,
+                    predicate_gas_used,
+                    predicate,
+                    predicate_data,
+                ));
+            }
+            SpendableResource::Message {
+                sender,
+                nonce,
+                amount,
+                owner: ResourceOwner::Signer(secret),
+            } => {
+                self.add_unsigned_message_input(secret, sender, nonce, amount, vec![]);
+            }
+            SpendableResource::Message {
+                sender,
+                nonce,
+                amount,
+                owner:
+                    ResourceOwner::Predicate {
+                        predicate,
+                        predicate_data,
+                        predicate_gas_used,
+                    },
+            } => {
+                self.add_input(Input::message_coin_predicate(
+                    sender,
+                    Input::predicate_owner(&predicate),
+                    amount,
+                    nonce,
+                    predicate_gas_used,
+                    predicate,
+                    predicate_data,
+                ));
+            }
+        }
+    }
+
+    /// Adds the `Change` outputs of the `funded` assets that don't have one.
+    fn add_change_outputs(&mut self, funded: &BTreeMap<AssetId, u128>, to: Address) {
+        for (asset_id, _) in funded.iter().filter(|(_, amount)| **amount > 0) {
+            let has_change = self.tx.outputs().iter().any(|output| {
+                matches!(output, Output::Change { asset_id: change, .. } if change == asset_id)
+            });
+            if !has_change {
+                self.tx.outputs_mut().push(Output::change(to, 0, *asset_id));
+            }
+        }
+    }
+
+    /// The maximum fee of the transaction once the inputs are signed.
+    fn estimate_max_fee(&self, gas_price: Word) -> Result<Word, FundingError> {
+        let mut tx = self.tx.clone();
+        for witness_index in self.sign_keys.values() {
+            if let Some(witness) = tx.witnesses_mut().get_mut(*witness_index as usize) {
+                *witness = Witness::from(vec![0u8; Signature::LEN]);
+            }
+        }
+
+        let max_fee =
+            tx.max_fee(self.params.gas_costs(), self.params.fee_params(), gas_price);
+        Word::try_from(max_fee).map_err(|_| FundingError::FeeOverflow)
+    }
+}
@@ -63,11 +63,15 @@ pub mod test_helper;
 pub use builder::{
     Buildable,
     Finalizable,
+    FundingError,
+    ResourceOwner,
+    SpendableResource,
     TransactionBuilder,
 };
 
 #[cfg(feature = "alloc")]
 pub use receipt::{
+    PanicContext,
     Receipt,
     ScriptExecutionResult,
 };
@@ -97,7 +101,12 @@ pub use transaction::{
     FormatValidityChecks,
     GasCosts,
     GasCostsValues,
+    InputRequirement,
     Mint,
+    PartiallySignedError,
+    PartiallySignedTransaction,
+    Party,
+    PredicateEstimation,
     PredicateParameters,
     Script,
     ScriptParameters,
@@ -117,6 +126,7 @@ pub use transaction::{
     UploadSubsection,
     UtxoId,
     ValidityError,
+    VmParameters,
     Witness,
 };
 
@@ -18,10 +18,12 @@ use fuel_types::{
     Word,
 };
 
+mod panic_context;
 mod receipt_repr;
 mod script_result;
 
 use crate::input::message::compute_message_id;
+pub use panic_context::PanicContext;
 pub use script_result::ScriptExecutionResult;
 
 #[derive(Clone, Derivative)]
@@ -69,6 +71,10 @@ pub enum Receipt {
         #[derivative(PartialEq = "ignore", Hash = "ignore")]
         #[canonical(skip)]
         contract_id: Option<ContractId>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        #[canonical(skip)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        context: PanicContext,
     },
 
     Revert {
@@ -235,6 +241,7 @@ impl Receipt {
             pc,
             is,
             contract_id: None,
+            context: PanicContext::None,
         }
     }
 
@@ -249,6 +256,23 @@ impl Receipt {
         self
     }
 
+    /// Attaches the `panic_context` to the panic receipt. The contract of the
+    /// [`PanicContext::ContractId`] is also set as the `contract_id` of the receipt.
+    pub fn with_panic_context(mut self, panic_context: PanicContext) -> Self {
+        if let Receipt::Panic {
+            ref mut contract_id,
+            ref mut context,
+            ..
+        } = self
+        {
+            if let Some(id) = panic_context.contract_id() {
+                *contract_id = Some(*id);
+            }
+            *context = panic_context;
+        }
+        self
+    }
+
     pub const fn revert(id: ContractId, ra: Word, pc: Word, is: Word) -> Self {
         Self::Revert { id, ra, pc, is }
     }
@@ -700,6 +724,13 @@ impl Receipt {
             _ => None,
         }
     }
+
+    pub const fn panic_context(&self) -> Option<&PanicContext> {
+        match self {
+            Self::Panic { context, .. } => Some(context),
+            _ => None,
+        }
+    }
 }
 
 fn trim_contract_id(id: Option<&ContractId>) -> Option<&ContractId> {
@@ -0,0 +1,126 @@
+use fuel_types::{
+    AssetId,
+    ContractId,
+    Word,
+};
+
+/// Additional information attached to a [`Receipt::Panic`](crate::Receipt::Panic)
+/// to simplify debugging of the failed transaction without re-execution.
+///
+/// The context is not a part of the canonical encoding of the receipt, so it
+/// doesn't affect the receipts root. It has its own canonical encoding
+/// prefixed with the variant discriminant. New variants are only appended, so
+/// the encoding of the existing variants is stable across versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+pub enum PanicContext {
+    /// No additional information.
+    #[default]
+    None,
+    /// The contract that caused the panic, e.g. the one missing from the
+    /// inputs for `ContractNotInInputs`.
+    ContractId(ContractId),
+    /// The asset that caused the panic, e.g. the one without enough balance
+    /// for `NotEnoughBalance`.
+    AssetId(AssetId),
+    /// The memory range that caused the panic, e.g. the one not owned by the
+    /// context for `MemoryOwnership`.
+    MemoryRange {
+        /// The start of the range.
+        start: Word,
+        /// The length of the range.
+        len: Word,
+    },
+}
+
+impl PanicContext {
+    /// Returns `true` if no additional information is available.
+    pub const fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// The contract that caused the panic, if any.
+    pub const fn contract_id(&self) -> Option<&ContractId> {
+        match self {
+            Self::ContractId(contract_id) => Some(contract_id),
+            _ => None,
+        }
+    }
+
+    /// The asset that caused the panic, if any.
+    pub const fn asset_id(&self) -> Option<&AssetId> {
+        match self {
+            Self::AssetId(asset_id) => Some(asset_id),
+            _ => None,
+        }
+    }
+
+    /// The memory range `(start, len)` that caused the panic, if any.
+    pub const fn memory_range(&self) -> Option<(Word, Word)> {
+        match self {
+            Self::MemoryRange { start, len } => Some((*start, *len)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use fuel_types::canonical::{
+        Deserialize,
+        Serialize,
+    };
+
+    #[rstest::rstest]
+    #[case(PanicContext::None)]
+    #[case(PanicContext::ContractId(ContractId::new([1; 32])))]
+    #[case(PanicContext::AssetId(AssetId::new([2; 32])))]
+    #[case(PanicContext::MemoryRange { start: 3, len: 4 })]
+    fn panic_context__canonical_round_trip(#[case] context: PanicContext) {
+        let bytes = context.to_bytes();
+        let decoded = PanicContext::from_bytes(&bytes).expect("Valid encoding");
+
+        assert_eq!(decoded, context);
+    }
+
+    #[test]
+    fn panic_context__encoding_is_prefixed_with_discriminant() {
+        let bytes = PanicContext::AssetId(AssetId::new([2; 32])).to_bytes();
+
+        assert_eq!(&bytes[..8], &2u64.to_be_bytes());
+        assert_eq!(&bytes[8..], &[2; 32]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn panic_receipt__deserializes_without_context() {
+        use crate::Receipt;
+        use fuel_asm::{
+            PanicInstruction,
+            PanicReason,
+        };
+
+        let contract_id = ContractId::new([3; 32]);
+        let receipt = Receipt::panic(
+            ContractId::default(),
+            PanicInstruction::error(PanicReason::ContractNotInInputs, 0),
+            0,
+            0,
+        )
+        .with_panic_context(PanicContext::ContractId(contract_id));
+
+        let mut json = serde_json::to_value(&receipt).expect("Serializable");
+        json["Panic"]
+            .as_object_mut()
+            .expect("Panic receipt")
+            .remove("context")
+            .expect("Context is serialized");
+        let decoded: Receipt = serde_json::from_value(json).expect("Valid receipt");
+
+        assert_eq!(decoded.contract_id(), Some(&contract_id));
+        assert_eq!(decoded.panic_context(), Some(&PanicContext::None));
+    }
+}
@@ -40,6 +40,7 @@ pub fn test_params() -> ConsensusParameters {
         Default::default(),
         Default::default(),
         Default::default(),
+        Default::default(),
     )
 }
 
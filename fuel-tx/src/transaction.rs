@@ -52,6 +52,7 @@ mod types;
 mod validity;
 
 mod id;
+mod partially_signed;
 
 pub mod consensus_parameters;
 pub mod policies;
@@ -66,6 +67,7 @@ pub use consensus_parameters::{
     PredicateParameters,
     ScriptParameters,
     TxParameters,
+    VmParameters,
 };
 pub use fee::{
     Chargeable,
@@ -86,6 +88,13 @@ pub use id::{
     PrepareSign,
     UniqueIdentifier,
 };
+pub use partially_signed::{
+    InputRequirement,
+    PartiallySignedError,
+    PartiallySignedTransaction,
+    Party,
+    PredicateEstimation,
+};
 
 /// Identification of transaction (also called transaction hash)
 pub type TxId = Bytes32;
@@ -403,6 +412,19 @@ impl Transaction {
             _ => None,
         }
     }
+
+    /// Returns the owner of the transaction set by the `Owner` policy, if any.
+    pub fn owner(&self) -> Option<&Address> {
+        use field::Owner;
+
+        match self {
+            Self::Script(tx) => tx.owner(),
+            Self::Create(tx) => tx.owner(),
+            Self::Mint(_) => None,
+            Self::Upgrade(tx) => tx.owner(),
+            Self::Upload(tx) => tx.owner(),
+        }
+    }
 }
 
 pub trait Executable: field::Inputs + field::Outputs + field::Witnesses {
@@ -668,6 +690,7 @@ pub mod field {
         Witness,
     };
     use fuel_types::{
+        Address,
         AssetId,
         BlockHeight,
         Bytes32,
@@ -747,6 +770,57 @@ pub mod field {
         }
     }
 
+    pub trait Expiration {
+        fn expiration(&self) -> BlockHeight;
+        fn set_expiration(&mut self, value: BlockHeight);
+    }
+
+    impl<T: Policies + ?Sized> Expiration for T {
+        #[inline(always)]
+        fn expiration(&self) -> BlockHeight {
+            self.policies()
+                .get(PolicyType::Expiration)
+                .map(|value| u32::try_from(value).unwrap_or(u32::MAX).into())
+                .unwrap_or(u32::MAX.into())
+        }
+
+        #[inline(always)]
+        fn set_expiration(&mut self, block_height: BlockHeight) {
+            self.policies_mut()
+                .set(PolicyType::Expiration, Some(*block_height.deref() as u64))
+        }
+    }
+
+    pub trait Owner {
+        /// Returns the index of the input owned by the owner of the transaction.
+        fn owner_index(&self) -> Option<u16>;
+        fn set_owner_index(&mut self, input_index: u16);
+
+        /// Returns the owner of the coin or message input at the
+        /// [`Owner::owner_index`].
+        fn owner(&self) -> Option<&Address>;
+    }
+
+    impl<T: Policies + Inputs + ?Sized> Owner for T {
+        #[inline(always)]
+        fn owner_index(&self) -> Option<u16> {
+            self.policies()
+                .get(PolicyType::Owner)
+                .and_then(|value| u16::try_from(value).ok())
+        }
+
+        #[inline(always)]
+        fn set_owner_index(&mut self, input_index: u16) {
+            self.policies_mut()
+                .set(PolicyType::Owner, Some(input_index as u64))
+        }
+
+        fn owner(&self) -> Option<&Address> {
+            let input = self.inputs().get(self.owner_index()? as usize)?;
+            input.input_owner().or_else(|| input.recipient())
+        }
+    }
+
     pub trait MaxFeeLimit {
         fn max_fee_limit(&self) -> Word;
         fn set_max_fee_limit(&mut self, value: Word);
@@ -14,7 +14,10 @@ pub use gas::{
     GasCostsValues,
 };
 
-use crate::consts::BALANCE_ENTRY_SIZE;
+use crate::{
+    consts::BALANCE_ENTRY_SIZE,
+    ValidityError,
+};
 
 #[cfg(feature = "test-helpers")]
 const MAX_GAS: u64 = 100_000_000;
@@ -26,6 +29,8 @@ const MAX_SIZE: u64 = 110 * 1024;
 pub enum ConsensusParameters {
     /// Version 1 of the consensus parameters
     V1(ConsensusParametersV1),
+    /// Version 2 of the consensus parameters, adding the VM parameters
+    V2(ConsensusParametersV2),
 }
 
 #[cfg(feature = "test-helpers")]
@@ -39,13 +44,13 @@ impl ConsensusParameters {
     #[cfg(feature = "test-helpers")]
     /// Constructor for the `ConsensusParameters` with Standard values.
     pub fn standard() -> Self {
-        ConsensusParametersV1::standard().into()
+        ConsensusParametersV2::standard().into()
     }
 
     #[cfg(feature = "test-helpers")]
     /// Constructor for the `ConsensusParameters` with Standard values around `ChainId`.
     pub fn standard_with_id(chain_id: ChainId) -> Self {
-        ConsensusParametersV1::standard_with_id(chain_id).into()
+        ConsensusParametersV2::standard_with_id(chain_id).into()
     }
 
     /// Constructor for the `ConsensusParameters`
@@ -60,8 +65,9 @@ impl ConsensusParameters {
         base_asset_id: AssetId,
         block_gas_limit: u64,
         privileged_address: Address,
+        vm_params: VmParameters,
     ) -> Self {
-        Self::V1(ConsensusParametersV1 {
+        Self::V2(ConsensusParametersV2 {
             tx_params,
             predicate_params,
             script_params,
@@ -72,6 +78,7 @@ impl ConsensusParameters {
             base_asset_id,
             block_gas_limit,
             privileged_address,
+            vm_params,
         })
     }
 
@@ -79,6 +86,7 @@ impl ConsensusParameters {
     pub const fn tx_params(&self) -> &TxParameters {
         match self {
             Self::V1(params) => &params.tx_params,
+            Self::V2(params) => &params.tx_params,
         }
     }
 
@@ -86,6 +94,7 @@ impl ConsensusParameters {
     pub const fn predicate_params(&self) -> &PredicateParameters {
         match self {
             Self::V1(params) => &params.predicate_params,
+            Self::V2(params) => &params.predicate_params,
         }
     }
 
@@ -93,6 +102,7 @@ impl ConsensusParameters {
     pub const fn script_params(&self) -> &ScriptParameters {
         match self {
             Self::V1(params) => &params.script_params,
+            Self::V2(params) => &params.script_params,
         }
     }
 
@@ -100,6 +110,7 @@ impl ConsensusParameters {
     pub const fn contract_params(&self) -> &ContractParameters {
         match self {
             Self::V1(params) => &params.contract_params,
+            Self::V2(params) => &params.contract_params,
         }
     }
 
@@ -107,6 +118,7 @@ impl ConsensusParameters {
     pub const fn fee_params(&self) -> &FeeParameters {
         match self {
             Self::V1(params) => &params.fee_params,
+            Self::V2(params) => &params.fee_params,
         }
     }
 
@@ -114,6 +126,7 @@ impl ConsensusParameters {
     pub const fn chain_id(&self) -> ChainId {
         match self {
             Self::V1(params) => params.chain_id,
+            Self::V2(params) => params.chain_id,
         }
     }
 
@@ -121,6 +134,7 @@ impl ConsensusParameters {
     pub const fn gas_costs(&self) -> &GasCosts {
         match self {
             Self::V1(params) => &params.gas_costs,
+            Self::V2(params) => &params.gas_costs,
         }
     }
 
@@ -128,6 +142,7 @@ impl ConsensusParameters {
     pub const fn base_asset_id(&self) -> &AssetId {
         match self {
             Self::V1(params) => &params.base_asset_id,
+            Self::V2(params) => &params.base_asset_id,
         }
     }
 
@@ -135,6 +150,7 @@ impl ConsensusParameters {
     pub const fn block_gas_limit(&self) -> u64 {
         match self {
             Self::V1(params) => params.block_gas_limit,
+            Self::V2(params) => params.block_gas_limit,
         }
     }
 
@@ -142,6 +158,16 @@ impl ConsensusParameters {
     pub const fn privileged_address(&self) -> &Address {
         match self {
             Self::V1(params) => &params.privileged_address,
+            Self::V2(params) => &params.privileged_address,
+        }
+    }
+
+    /// Get the VM parameters. The first version of the consensus parameters uses
+    /// [`VmParameters::DEFAULT`].
+    pub const fn vm_params(&self) -> &VmParameters {
+        match self {
+            Self::V1(_) => &VmParameters::DEFAULT,
+            Self::V2(params) => &params.vm_params,
         }
     }
 }
@@ -151,6 +177,7 @@ impl ConsensusParameters {
     pub fn set_tx_params(&mut self, tx_params: TxParameters) {
         match self {
             Self::V1(params) => params.tx_params = tx_params,
+            Self::V2(params) => params.tx_params = tx_params,
         }
     }
 
@@ -158,6 +185,7 @@ impl ConsensusParameters {
     pub fn set_predicate_params(&mut self, predicate_params: PredicateParameters) {
         match self {
             Self::V1(params) => params.predicate_params = predicate_params,
+            Self::V2(params) => params.predicate_params = predicate_params,
         }
     }
 
@@ -165,6 +193,7 @@ impl ConsensusParameters {
     pub fn set_script_params(&mut self, script_params: ScriptParameters) {
         match self {
             Self::V1(params) => params.script_params = script_params,
+            Self::V2(params) => params.script_params = script_params,
         }
     }
 
@@ -172,6 +201,7 @@ impl ConsensusParameters {
     pub fn set_contract_params(&mut self, contract_params: ContractParameters) {
         match self {
             Self::V1(params) => params.contract_params = contract_params,
+            Self::V2(params) => params.contract_params = contract_params,
         }
     }
 
@@ -179,6 +209,7 @@ impl ConsensusParameters {
     pub fn set_fee_params(&mut self, fee_params: FeeParameters) {
         match self {
             Self::V1(params) => params.fee_params = fee_params,
+            Self::V2(params) => params.fee_params = fee_params,
         }
     }
 
@@ -186,6 +217,7 @@ impl ConsensusParameters {
     pub fn set_chain_id(&mut self, chain_id: ChainId) {
         match self {
             Self::V1(params) => params.chain_id = chain_id,
+            Self::V2(params) => params.chain_id = chain_id,
         }
     }
 
@@ -193,6 +225,7 @@ impl ConsensusParameters {
     pub fn set_gas_costs(&mut self, gas_costs: GasCosts) {
         match self {
             Self::V1(params) => params.gas_costs = gas_costs,
+            Self::V2(params) => params.gas_costs = gas_costs,
         }
     }
 
@@ -200,6 +233,7 @@ impl ConsensusParameters {
     pub fn set_base_asset_id(&mut self, base_asset_id: AssetId) {
         match self {
             Self::V1(params) => params.base_asset_id = base_asset_id,
+            Self::V2(params) => params.base_asset_id = base_asset_id,
         }
     }
 
@@ -207,6 +241,7 @@ impl ConsensusParameters {
     pub fn set_block_gas_limit(&mut self, block_gas_limit: u64) {
         match self {
             Self::V1(params) => params.block_gas_limit = block_gas_limit,
+            Self::V2(params) => params.block_gas_limit = block_gas_limit,
         }
     }
 
@@ -214,6 +249,20 @@ impl ConsensusParameters {
     pub fn set_privileged_address(&mut self, privileged_address: Address) {
         match self {
             Self::V1(params) => params.privileged_address = privileged_address,
+            Self::V2(params) => params.privileged_address = privileged_address,
+        }
+    }
+
+    /// Set the VM parameters. The first version of the consensus parameters is
+    /// upgraded to the second one.
+    pub fn set_vm_params(&mut self, vm_params: VmParameters) {
+        match self {
+            Self::V1(params) => {
+                let mut params = ConsensusParametersV2::from(params.clone());
+                params.vm_params = vm_params;
+                *self = Self::V2(params);
+            }
+            Self::V2(params) => params.vm_params = vm_params,
         }
     }
 }
@@ -272,6 +321,85 @@ impl From<ConsensusParametersV1> for ConsensusParameters {
     }
 }
 
+/// A collection of parameters for convenience. Extends [`ConsensusParametersV1`] with
+/// the VM parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ConsensusParametersV2 {
+    pub tx_params: TxParameters,
+    pub predicate_params: PredicateParameters,
+    pub script_params: ScriptParameters,
+    pub contract_params: ContractParameters,
+    pub fee_params: FeeParameters,
+    pub chain_id: ChainId,
+    pub gas_costs: GasCosts,
+    pub base_asset_id: AssetId,
+    pub block_gas_limit: u64,
+    /// The privileged address(user or predicate) that can perform permissioned
+    /// operations(like upgrading the network).
+    pub privileged_address: Address,
+    /// The limits of the VM.
+    pub vm_params: VmParameters,
+}
+
+#[cfg(feature = "test-helpers")]
+impl ConsensusParametersV2 {
+    /// Constructor for the `ConsensusParameters` with Standard values.
+    pub fn standard() -> Self {
+        Self::standard_with_id(ChainId::default())
+    }
+
+    /// Constructor for the `ConsensusParameters` with Standard values around `ChainId`.
+    pub fn standard_with_id(chain_id: ChainId) -> Self {
+        ConsensusParametersV1::standard_with_id(chain_id).into()
+    }
+}
+
+#[cfg(feature = "test-helpers")]
+impl Default for ConsensusParametersV2 {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Uses [`VmParameters::DEFAULT`], the limits of the VM before they became
+/// configurable.
+impl From<ConsensusParametersV1> for ConsensusParametersV2 {
+    fn from(params: ConsensusParametersV1) -> Self {
+        let ConsensusParametersV1 {
+            tx_params,
+            predicate_params,
+            script_params,
+            contract_params,
+            fee_params,
+            chain_id,
+            gas_costs,
+            base_asset_id,
+            block_gas_limit,
+            privileged_address,
+        } = params;
+
+        Self {
+            tx_params,
+            predicate_params,
+            script_params,
+            contract_params,
+            fee_params,
+            chain_id,
+            gas_costs,
+            base_asset_id,
+            block_gas_limit,
+            privileged_address,
+            vm_params: VmParameters::DEFAULT,
+        }
+    }
+}
+
+impl From<ConsensusParametersV2> for ConsensusParameters {
+    fn from(params: ConsensusParametersV2) -> Self {
+        Self::V2(params)
+    }
+}
+
 /// The versioned fee parameters.
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
@@ -875,6 +1003,143 @@ impl Default for ContractParametersV1 {
     }
 }
 
+/// Versioned VM parameters.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum VmParameters {
+    V1(VmParametersV1),
+}
+
+impl VmParameters {
+    /// The limits of the VM before they became configurable. Used by the first
+    /// version of the consensus parameters.
+    pub const DEFAULT: Self = Self::V1(VmParametersV1::DEFAULT);
+    /// The size of the VM memory, in bytes. The memory limit can't exceed it.
+    pub const MAX_MEMORY: u64 = 64 * 1024 * 1024;
+    /// The maximum number of receipts, defined by the specification.
+    pub const MAX_RECEIPTS: u64 = u16::MAX as u64;
+    /// The minimum number of receipts: a script must be able to end with a panic
+    /// and the script result.
+    pub const MIN_RECEIPTS: u64 = 2;
+
+    /// Replace the max number of receipts with the given argument
+    pub const fn with_max_receipts(self, max_receipts: u64) -> Self {
+        match self {
+            Self::V1(mut params) => {
+                params.max_receipts = max_receipts;
+                Self::V1(params)
+            }
+        }
+    }
+
+    /// Replace the max depth of the nested calls with the given argument
+    pub const fn with_max_call_depth(self, max_call_depth: u64) -> Self {
+        match self {
+            Self::V1(mut params) => {
+                params.max_call_depth = max_call_depth;
+                Self::V1(params)
+            }
+        }
+    }
+
+    /// Replace the max memory usage with the given argument
+    pub const fn with_max_memory(self, max_memory: u64) -> Self {
+        match self {
+            Self::V1(mut params) => {
+                params.max_memory = max_memory;
+                Self::V1(params)
+            }
+        }
+    }
+
+    /// Checks that the limits are in the safe ranges:
+    /// - the maximum number of receipts is within
+    ///   [`Self::MIN_RECEIPTS`]`..=`[`Self::MAX_RECEIPTS`];
+    /// - the nested calls are allowed, i.e. the maximum call depth isn't zero;
+    /// - the memory limit doesn't exceed [`Self::MAX_MEMORY`] and fits the largest
+    ///   transaction placed at the `tx_offset`.
+    pub fn validate(&self, tx_params: &TxParameters) -> Result<(), ValidityError> {
+        let max_tx_end =
+            (tx_params.tx_offset() as u64).saturating_add(tx_params.max_size());
+
+        if !(Self::MIN_RECEIPTS..=Self::MAX_RECEIPTS).contains(&self.max_receipts())
+            || self.max_call_depth() == 0
+            || self.max_memory() > Self::MAX_MEMORY
+            || self.max_memory() < max_tx_end
+        {
+            return Err(ValidityError::TransactionUpgradeInvalidVmParameters)
+        }
+
+        Ok(())
+    }
+}
+
+impl VmParameters {
+    /// Get the maximum number of receipts
+    pub const fn max_receipts(&self) -> u64 {
+        match self {
+            Self::V1(params) => params.max_receipts,
+        }
+    }
+
+    /// Get the maximum depth of the nested calls
+    pub const fn max_call_depth(&self) -> u64 {
+        match self {
+            Self::V1(params) => params.max_call_depth,
+        }
+    }
+
+    /// Get the maximum memory usage
+    pub const fn max_memory(&self) -> u64 {
+        match self {
+            Self::V1(params) => params.max_memory,
+        }
+    }
+}
+
+impl From<VmParametersV1> for VmParameters {
+    fn from(params: VmParametersV1) -> Self {
+        Self::V1(params)
+    }
+}
+
+impl Default for VmParameters {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct VmParametersV1 {
+    /// Maximum number of receipts of a transaction.
+    pub max_receipts: u64,
+
+    /// Maximum depth of the nested contract calls.
+    pub max_call_depth: u64,
+
+    /// Maximum memory usage, i.e. the total size of the stack and the heap, in bytes.
+    pub max_memory: u64,
+}
+
+impl VmParametersV1 {
+    /// The limits of the VM before they became configurable. The depth of the calls
+    /// was bounded only by the memory.
+    pub const DEFAULT: Self = Self {
+        max_receipts: VmParameters::MAX_RECEIPTS,
+        max_call_depth: u64::MAX,
+        max_memory: VmParameters::MAX_MEMORY,
+    };
+}
+
+impl Default for VmParametersV1 {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[cfg(feature = "typescript")]
 pub mod typescript {
     use wasm_bindgen::prelude::*;
@@ -15,6 +15,7 @@ use fuel_crypto::{
     PublicKey,
     SecretKey,
     Signature,
+    Signer,
 };
 use fuel_types::{
     Bytes32,
@@ -65,6 +66,20 @@ impl UniqueIdentifier for Transaction {
 pub trait Signable: UniqueIdentifier {
     /// Signs inputs of the transaction.
     fn sign_inputs(&mut self, secret: &SecretKey, chain_id: &ChainId);
+
+    /// Signs the inputs owned by the public key of the `signer`.
+    fn sign_inputs_with<S>(
+        &mut self,
+        signer: &S,
+        chain_id: &ChainId,
+    ) -> Result<(), S::Error>
+    where
+        S: Signer + ?Sized;
+
+    /// Sets the `signature` of the transaction id as the witness of the inputs owned by
+    /// the `public_key`. It allows to sign the transaction id by the external signers,
+    /// like the `AsyncSigner`.
+    fn set_input_signatures(&mut self, public_key: &PublicKey, signature: &Signature);
 }
 
 impl<T> Signable for T
@@ -74,15 +89,33 @@ where
     /// For all inputs of type `coin` or `message`, check if its `owner` equals the public
     /// counterpart of the provided key. Sign all matches.
     fn sign_inputs(&mut self, secret: &SecretKey, chain_id: &ChainId) {
-        use itertools::Itertools;
+        match self.sign_inputs_with(secret, chain_id) {
+            Ok(()) => {}
+            Err(infallible) => match infallible {},
+        }
+    }
 
-        let pk = PublicKey::from(secret);
-        let pk = Input::owner(&pk);
+    fn sign_inputs_with<S>(
+        &mut self,
+        signer: &S,
+        chain_id: &ChainId,
+    ) -> Result<(), S::Error>
+    where
+        S: Signer + ?Sized,
+    {
         let id = self.id(chain_id);
-
         let message = Message::from_bytes_ref(&id);
 
-        let signature = Signature::sign(secret, message);
+        let signature = signer.sign(message)?;
+        self.set_input_signatures(&signer.public_key(), &signature);
+
+        Ok(())
+    }
+
+    fn set_input_signatures(&mut self, public_key: &PublicKey, signature: &Signature) {
+        use itertools::Itertools;
+
+        let pk = Input::owner(public_key);
 
         let inputs = self.inputs();
 
@@ -0,0 +1,545 @@
+//! The transaction under construction by several parties.
+//!
+//! Every party adds the signatures of its inputs or estimates the predicates, and passes
+//! the [`PartiallySignedTransaction`] to the next one, or returns it to the coordinator
+//! that merges the contributions of all parties. The container records what every input
+//! requires, so the missing parts are known at every step, and it can be exchanged in
+//! the canonical encoding or in JSON.
+
+use alloc::vec::Vec;
+
+use fuel_crypto::{
+    Message,
+    PublicKey,
+    Signature,
+};
+use fuel_types::{
+    canonical::{
+        self,
+        Deserialize,
+        Error,
+    },
+    Address,
+    BlockHeight,
+    ChainId,
+    Word,
+};
+
+use crate::{
+    field::{
+        Inputs,
+        Witnesses,
+    },
+    input::{
+        coin::{
+            CoinPredicate,
+            CoinSigned,
+        },
+        message::{
+            MessageCoinPredicate,
+            MessageCoinSigned,
+            MessageDataPredicate,
+            MessageDataSigned,
+        },
+    },
+    ConsensusParameters,
+    FormatValidityChecks,
+    Input,
+    Transaction,
+    TxId,
+    UniqueIdentifier,
+    ValidityError,
+    Witness,
+};
+
+/// Applies the expression to the transaction with inputs, or returns the `mint` value.
+macro_rules! map_executable {
+    ($tx:expr, $executable:ident => $e:expr, $mint:expr) => {
+        match $tx {
+            Transaction::Script($executable) => $e,
+            Transaction::Create($executable) => $e,
+            Transaction::Upgrade($executable) => $e,
+            Transaction::Upload($executable) => $e,
+            Transaction::Mint(_) => $mint,
+        }
+    };
+}
+
+/// The error of the operations on the [`PartiallySignedTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PartiallySignedError {
+    /// The `Mint` transaction isn't built by the parties.
+    #[display(fmt = "The `Mint` transaction can't be partially signed")]
+    UnsupportedTransaction,
+    /// The contribution is for another transaction.
+    #[display(fmt = "Expected the transaction {expected}, got {actual}")]
+    TransactionMismatch {
+        /// The id of the transaction.
+        expected: TxId,
+        /// The id of the transaction of the contribution.
+        actual: TxId,
+    },
+    /// The parties provided different witnesses at the same index.
+    #[display(fmt = "The witness {} differs between the parties", _0)]
+    WitnessConflict(u16),
+    /// The parties estimated different gas used by the predicate of the input.
+    #[display(
+        fmt = "The predicate gas used of the input {} differs between the parties",
+        _0
+    )]
+    PredicateConflict(usize),
+    /// The parties provided different metadata for the same party.
+    #[display(fmt = "The metadata of the party {} differs between the parties", _0)]
+    PartyConflict(Address),
+    /// The transaction doesn't have the inputs signed by the owner.
+    #[display(fmt = "The transaction doesn't have the inputs signed by {}", _0)]
+    UnknownOwner(Address),
+    /// The signature isn't made by the owner of the inputs over the transaction id.
+    #[display(fmt = "The signature of {} is invalid", _0)]
+    InvalidSignature(Address),
+    /// The signature of the input is missing.
+    #[display(fmt = "The signature of the input {} is missing", _0)]
+    MissingSignature(usize),
+    /// The gas used by the predicate of the input isn't estimated.
+    #[display(fmt = "The predicate of the input {} isn't estimated", _0)]
+    UnestimatedPredicate(usize),
+    /// The requirements of the inputs don't match the inputs of the transaction.
+    #[display(fmt = "The input requirements don't match the transaction")]
+    InputRequirementsMismatch,
+    /// The complete transaction is invalid.
+    #[display(fmt = "The transaction is invalid: {}", _0)]
+    Validity(ValidityError),
+}
+
+/// Whether the gas used by the predicate is estimated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+pub enum PredicateEstimation {
+    /// The predicate must be estimated before the transaction is finalized.
+    Pending,
+    /// The `predicate_gas_used` of the input is estimated.
+    Estimated,
+}
+
+/// What unlocks the input of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+pub enum InputRequirement {
+    /// The input doesn't require anything, like the contract input.
+    None,
+    /// The input requires the signature of the owner.
+    Signature {
+        /// The address of the owner of the input.
+        owner: Address,
+        /// The index of the witness with the signature.
+        witness_index: u16,
+    },
+    /// The input is unlocked by the predicate.
+    Predicate(PredicateEstimation),
+}
+
+impl InputRequirement {
+    fn of(input: &Input) -> Self {
+        match input {
+            Input::CoinSigned(CoinSigned {
+                owner,
+                witness_index,
+                ..
+            })
+            | Input::MessageCoinSigned(MessageCoinSigned {
+                recipient: owner,
+                witness_index,
+                ..
+            })
+            | Input::MessageDataSigned(MessageDataSigned {
+                recipient: owner,
+                witness_index,
+                ..
+            }) => Self::Signature {
+                owner: *owner,
+                witness_index: *witness_index,
+            },
+            Input::CoinPredicate(_)
+            | Input::MessageCoinPredicate(_)
+            | Input::MessageDataPredicate(_) => {
+                if input.predicate_gas_used().unwrap_or_default() == 0 {
+                    Self::Predicate(PredicateEstimation::Pending)
+                } else {
+                    Self::Predicate(PredicateEstimation::Estimated)
+                }
+            }
+            Input::Contract(_) => Self::None,
+        }
+    }
+}
+
+/// The party constructing the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+pub struct Party {
+    /// The address of the inputs of the party.
+    pub owner: Address,
+    /// The application-defined metadata, like the name or the role in the swap.
+    pub metadata: Vec<u8>,
+}
+
+/// The transaction with the signatures and the predicate estimations collected from
+/// several parties.
+///
+/// The requirements of the inputs are checked against the transaction on
+/// deserialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PartiallySignedTransactionData"))]
+#[derive(fuel_types::canonical::Serialize)]
+pub struct PartiallySignedTransaction {
+    chain_id: ChainId,
+    transaction: Transaction,
+    inputs: Vec<InputRequirement>,
+    parties: Vec<Party>,
+}
+
+/// The unchecked fields of the deserialized [`PartiallySignedTransaction`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PartiallySignedTransactionData {
+    chain_id: ChainId,
+    transaction: Transaction,
+    inputs: Vec<InputRequirement>,
+    parties: Vec<Party>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PartiallySignedTransactionData> for PartiallySignedTransaction {
+    type Error = PartiallySignedError;
+
+    fn try_from(data: PartiallySignedTransactionData) -> Result<Self, Self::Error> {
+        let tx = Self {
+            chain_id: data.chain_id,
+            transaction: data.transaction,
+            inputs: data.inputs,
+            parties: data.parties,
+        };
+        tx.validate_inputs()?;
+        Ok(tx)
+    }
+}
+
+impl Deserialize for PartiallySignedTransaction {
+    fn decode_static<I: canonical::Input + ?Sized>(
+        buffer: &mut I,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            chain_id: ChainId::decode_static(buffer)?,
+            transaction: Transaction::decode_static(buffer)?,
+            inputs: Vec::decode_static(buffer)?,
+            parties: Vec::decode_static(buffer)?,
+        })
+    }
+
+    fn decode_dynamic<I: canonical::Input + ?Sized>(
+        &mut self,
+        buffer: &mut I,
+    ) -> Result<(), Error> {
+        self.chain_id.decode_dynamic(buffer)?;
+        self.transaction.decode_dynamic(buffer)?;
+        self.inputs.decode_dynamic(buffer)?;
+        self.parties.decode_dynamic(buffer)?;
+
+        self.validate_inputs().map_err(|_| {
+            Error::Unknown("The input requirements don't match the transaction")
+        })
+    }
+}
+
+impl PartiallySignedTransaction {
+    /// Wraps the `transaction` for the chain with the `chain_id`. The non-empty
+    /// witnesses and the non-zero `predicate_gas_used` are considered as provided.
+    pub fn new(
+        transaction: Transaction,
+        chain_id: ChainId,
+    ) -> Result<Self, PartiallySignedError> {
+        let inputs = input_requirements(&transaction)?;
+
+        Ok(Self {
+            chain_id,
+            transaction,
+            inputs,
+            parties: Vec::new(),
+        })
+    }
+
+    /// The transaction with the provided witnesses.
+    pub const fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// The id of the chain of the transaction.
+    pub const fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    /// The id of the transaction, which doesn't depend on the witnesses and the
+    /// predicate estimations.
+    pub fn id(&self) -> TxId {
+        self.transaction.id(&self.chain_id)
+    }
+
+    /// The message to sign by the owners of the inputs.
+    pub fn message(&self) -> Message {
+        Message::from_bytes(*self.id())
+    }
+
+    /// The requirements of the inputs, in the order of the inputs.
+    pub fn inputs(&self) -> &[InputRequirement] {
+        &self.inputs
+    }
+
+    /// The parties constructing the transaction.
+    pub fn parties(&self) -> &[Party] {
+        &self.parties
+    }
+
+    /// Adds the party, or replaces the metadata of the existing one.
+    pub fn add_party(&mut self, owner: Address, metadata: Vec<u8>) -> &mut Self {
+        match self.parties.iter_mut().find(|party| party.owner == owner) {
+            Some(party) => party.metadata = metadata,
+            None => self.parties.push(Party { owner, metadata }),
+        }
+        self
+    }
+
+    /// The indexes and the owners of the inputs without the signatures.
+    pub fn missing_signatures(&self) -> impl Iterator<Item = (usize, &Address)> + '_ {
+        self.inputs.iter().enumerate().filter_map(
+            |(index, requirement)| match requirement {
+                InputRequirement::Signature {
+                    owner,
+                    witness_index,
+                } if !self.has_witness(*witness_index) => Some((index, owner)),
+                _ => None,
+            },
+        )
+    }
+
+    /// The indexes of the inputs with the predicates to estimate.
+    pub fn pending_predicates(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, requirement)| {
+                (requirement
+                    == &InputRequirement::Predicate(PredicateEstimation::Pending))
+                    .then_some(index)
+            })
+    }
+
+    /// Returns `true` if every input is signed or estimated.
+    pub fn is_complete(&self) -> bool {
+        self.missing_signatures().next().is_none()
+            && self.pending_predicates().next().is_none()
+    }
+
+    /// Adds the `signature` of the [`PartiallySignedTransaction::message`] to the inputs
+    /// owned by the `public_key`.
+    pub fn add_signature(
+        &mut self,
+        public_key: &PublicKey,
+        signature: Signature,
+    ) -> Result<&mut Self, PartiallySignedError> {
+        let owner = Input::owner(public_key);
+        let witness_indexes: Vec<_> = self
+            .inputs
+            .iter()
+            .filter_map(|requirement| match requirement {
+                InputRequirement::Signature {
+                    owner: input_owner,
+                    witness_index,
+                } if input_owner == &owner => Some(*witness_index),
+                _ => None,
+            })
+            .collect();
+        if witness_indexes.is_empty() {
+            return Err(PartiallySignedError::UnknownOwner(owner));
+        }
+        signature
+            .verify(public_key, &self.message())
+            .map_err(|_| PartiallySignedError::InvalidSignature(owner))?;
+
+        let witnesses = self.witnesses_mut()?;
+        for witness_index in witness_indexes {
+            if let Some(witness) = witnesses.get_mut(witness_index as usize) {
+                *witness = signature.as_ref().into();
+            }
+        }
+        Ok(self)
+    }
+
+    /// Takes the `predicate_gas_used` of the predicates from the `estimated` copy of the
+    /// transaction, marking them as estimated.
+    pub fn apply_predicate_estimation(
+        &mut self,
+        estimated: &Transaction,
+    ) -> Result<&mut Self, PartiallySignedError> {
+        self.ensure_same_transaction(estimated.id(&self.chain_id))?;
+
+        let estimated = map_executable!(estimated, tx => tx.inputs().as_slice(), &[]);
+        for (index, input) in estimated.iter().enumerate() {
+            if let Some(gas_used) = input.predicate_gas_used().filter(|gas| *gas > 0) {
+                self.set_predicate_gas_used(index, gas_used);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Merges the witnesses, the predicate estimations and the parties provided by the
+    /// `other` party. Nothing is merged on error, including when the parties estimated
+    /// different gas used by the same predicate.
+    pub fn merge(&mut self, other: &Self) -> Result<&mut Self, PartiallySignedError> {
+        self.ensure_same_transaction(other.id())?;
+
+        let mut merged = self.clone();
+        let witnesses = merged.witnesses_mut()?;
+        for (index, witness) in other.witnesses().iter().enumerate() {
+            let Some(current) = witnesses.get_mut(index) else {
+                continue
+            };
+            if current.as_vec().is_empty() {
+                *current = witness.clone();
+            } else if !witness.as_vec().is_empty() && current != witness {
+                let index = u16::try_from(index).unwrap_or(u16::MAX);
+                return Err(PartiallySignedError::WitnessConflict(index));
+            }
+        }
+
+        let other_inputs =
+            map_executable!(&other.transaction, tx => tx.inputs().as_slice(), &[]);
+        for (index, requirement) in other.inputs.iter().enumerate() {
+            if requirement == &InputRequirement::Predicate(PredicateEstimation::Estimated)
+            {
+                let gas_used = other_inputs
+                    .get(index)
+                    .and_then(Input::predicate_gas_used)
+                    .unwrap_or_default();
+                if merged.inputs.get(index) == Some(requirement)
+                    && merged.predicate_gas_used(index) != Some(gas_used)
+                {
+                    return Err(PartiallySignedError::PredicateConflict(index));
+                }
+                merged.set_predicate_gas_used(index, gas_used);
+            }
+        }
+
+        for party in other.parties.iter() {
+            match merged.parties.iter().find(|p| p.owner == party.owner) {
+                Some(existing) if existing != party => {
+                    return Err(PartiallySignedError::PartyConflict(party.owner));
+                }
+                Some(_) => {}
+                None => merged.parties.push(party.clone()),
+            }
+        }
+
+        *self = merged;
+        Ok(self)
+    }
+
+    /// Returns the transaction once every input is signed or estimated, and it passes the
+    /// [`FormatValidityChecks`].
+    pub fn finalize(
+        self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<Transaction, PartiallySignedError> {
+        if let Some((index, _)) = self.missing_signatures().next() {
+            return Err(PartiallySignedError::MissingSignature(index));
+        }
+        if let Some(index) = self.pending_predicates().next() {
+            return Err(PartiallySignedError::UnestimatedPredicate(index));
+        }
+
+        self.transaction
+            .check(block_height, consensus_params)
+            .map_err(PartiallySignedError::Validity)?;
+
+        Ok(self.transaction)
+    }
+
+    fn validate_inputs(&self) -> Result<(), PartiallySignedError> {
+        if input_requirements(&self.transaction)? != self.inputs {
+            return Err(PartiallySignedError::InputRequirementsMismatch);
+        }
+        Ok(())
+    }
+
+    fn ensure_same_transaction(&self, actual: TxId) -> Result<(), PartiallySignedError> {
+        let expected = self.id();
+        if expected != actual {
+            return Err(PartiallySignedError::TransactionMismatch { expected, actual });
+        }
+        Ok(())
+    }
+
+    fn has_witness(&self, witness_index: u16) -> bool {
+        self.witnesses()
+            .get(witness_index as usize)
+            .is_some_and(|witness| !witness.as_vec().is_empty())
+    }
+
+    fn witnesses(&self) -> &[Witness] {
+        map_executable!(&self.transaction, tx => tx.witnesses(), &[])
+    }
+
+    fn witnesses_mut(&mut self) -> Result<&mut Vec<Witness>, PartiallySignedError> {
+        map_executable!(
+            &mut self.transaction,
+            tx => Ok(tx.witnesses_mut()),
+            Err(PartiallySignedError::UnsupportedTransaction)
+        )
+    }
+
+    fn predicate_gas_used(&self, index: usize) -> Option<Word> {
+        map_executable!(&self.transaction, tx => tx.inputs(), return None)
+            .get(index)
+            .and_then(Input::predicate_gas_used)
+    }
+
+    fn set_predicate_gas_used(&mut self, index: usize, gas_used: Word) {
+        let inputs =
+            map_executable!(&mut self.transaction, tx => tx.inputs_mut(), return);
+        match inputs.get_mut(index) {
+            Some(
+                Input::CoinPredicate(CoinPredicate {
+                    predicate_gas_used, ..
+                })
+                | Input::MessageCoinPredicate(MessageCoinPredicate {
+                    predicate_gas_used,
+                    ..
+                })
+                | Input::MessageDataPredicate(MessageDataPredicate {
+                    predicate_gas_used,
+                    ..
+                }),
+            ) => *predicate_gas_used = gas_used,
+            _ => return,
+        }
+        if let Some(requirement) = self.inputs.get_mut(index) {
+            *requirement = InputRequirement::Predicate(PredicateEstimation::Estimated);
+        }
+    }
+}
+
+fn input_requirements(
+    transaction: &Transaction,
+) -> Result<Vec<InputRequirement>, PartiallySignedError> {
+    map_executable!(
+        transaction,
+        tx => Ok(tx.inputs().iter().map(InputRequirement::of).collect()),
+        Err(PartiallySignedError::UnsupportedTransaction)
+    )
+}
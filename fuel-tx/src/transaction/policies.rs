@@ -33,6 +33,10 @@ bitflags::bitflags! {
         const Maturity = 1 << 2;
         /// If set, the max fee is present in the policies.
         const MaxFee = 1 << 3;
+        /// If set, the expiration is present in the policies.
+        const Expiration = 1 << 4;
+        /// If set, the index of the owner input is present in the policies.
+        const Owner = 1 << 5;
     }
 }
 
@@ -54,6 +58,8 @@ pub enum PolicyType {
     WitnessLimit,
     Maturity,
     MaxFee,
+    Expiration,
+    Owner,
 }
 
 impl PolicyType {
@@ -63,6 +69,8 @@ impl PolicyType {
             PolicyType::WitnessLimit => 1,
             PolicyType::Maturity => 2,
             PolicyType::MaxFee => 3,
+            PolicyType::Expiration => 4,
+            PolicyType::Owner => 5,
         }
     }
 
@@ -72,6 +80,8 @@ impl PolicyType {
             PolicyType::WitnessLimit => PoliciesBits::WitnessLimit,
             PolicyType::Maturity => PoliciesBits::Maturity,
             PolicyType::MaxFee => PoliciesBits::MaxFee,
+            PolicyType::Expiration => PoliciesBits::Expiration,
+            PolicyType::Owner => PoliciesBits::Owner,
         }
     }
 }
@@ -138,6 +148,18 @@ impl Policies {
         self
     }
 
+    /// Sets the `expiration` policy.
+    pub fn with_expiration(mut self, expiration: BlockHeight) -> Self {
+        self.set(PolicyType::Expiration, Some(*expiration.deref() as u64));
+        self
+    }
+
+    /// Sets the `owner` policy to the index of the input.
+    pub fn with_owner(mut self, input_index: u16) -> Self {
+        self.set(PolicyType::Owner, Some(input_index as u64));
+        self
+    }
+
     /// Returns a policy's value if the corresponding bit is set.
     pub fn get(&self, policy_type: PolicyType) -> Option<Word> {
         if self.bits.contains(policy_type.bit()) {
@@ -186,6 +208,18 @@ impl Policies {
             }
         }
 
+        if let Some(expiration) = self.get(PolicyType::Expiration) {
+            if expiration > u32::MAX as u64 {
+                return false;
+            }
+        }
+
+        if let Some(owner) = self.get(PolicyType::Owner) {
+            if owner > u16::MAX as u64 {
+                return false;
+            }
+        }
+
         true
     }
 
@@ -253,6 +287,18 @@ impl Deserialize for Policies {
             }
         }
 
+        if let Some(expiration) = self.get(PolicyType::Expiration) {
+            if expiration > u32::MAX as u64 {
+                return Err(Error::Unknown("The expiration in more than `u32::MAX`"));
+            }
+        }
+
+        if let Some(owner) = self.get(PolicyType::Owner) {
+            if owner > u16::MAX as u64 {
+                return Err(Error::Unknown("The owner in more than `u16::MAX`"));
+            }
+        }
+
         Ok(())
     }
 }
@@ -274,6 +320,16 @@ impl Distribution<Policies> for Standard {
             policies.set(PolicyType::Maturity, Some(maturity as u64));
         }
 
+        if policies.get(PolicyType::Expiration).is_some() {
+            let expiration: u32 = rng.gen();
+            policies.set(PolicyType::Expiration, Some(expiration as u64));
+        }
+
+        if policies.get(PolicyType::Owner).is_some() {
+            let owner: u16 = rng.gen();
+            policies.set(PolicyType::Owner, Some(owner as u64));
+        }
+
         policies
     }
 }
@@ -322,10 +378,16 @@ pub mod typescript {
     }
 }
 
+/// The owner used by the tests, it must fit into `u16` to be valid.
+#[cfg(test)]
+const TEST_OWNER: Word = 0x6001;
+
 #[test]
 fn values_for_bitmask_produces_expected_values() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] = [
+        0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001, TEST_OWNER,
+    ];
 
     // Given
     let mut set = hashbrown::HashSet::new();
@@ -344,7 +406,9 @@ fn values_for_bitmask_produces_expected_values() {
 #[test]
 fn canonical_serialization_deserialization_for_any_combination_of_values_works() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] = [
+        0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001, TEST_OWNER,
+    ];
 
     for bitmask in 0..MAX_BITMASK {
         let bits =
@@ -383,3 +447,18 @@ fn canonical_serialization_deserialization_for_any_combination_of_values_works()
         );
     }
 }
+
+#[test]
+fn policies_with_owner_above_u16_max_are_invalid() {
+    let mut policies = Policies::new();
+    policies.set(PolicyType::Owner, Some(u16::MAX as u64));
+    assert!(policies.is_valid());
+    assert_eq!(Policies::from_bytes(&policies.to_bytes()), Ok(policies));
+
+    policies.set(PolicyType::Owner, Some(u16::MAX as u64 + 1));
+    assert!(!policies.is_valid());
+    assert_eq!(
+        Policies::from_bytes(&policies.to_bytes()),
+        Err(Error::Unknown("The owner in more than `u16::MAX`"))
+    );
+}
@@ -79,6 +79,10 @@ impl UpgradeMetadata {
                     ValidityError::TransactionUpgradeConsensusParametersDeserialization
                 })?;
 
+                consensus_parameters
+                    .vm_params()
+                    .validate(consensus_parameters.tx_params())?;
+
                 Ok(Self::ConsensusParameters {
                     consensus_parameters: Box::new(consensus_parameters),
                     calculated_checksum: actual_checksum,
@@ -1,5 +1,8 @@
 use crate::{
-    field::Maturity,
+    field::{
+        Expiration,
+        Maturity,
+    },
     input::{
         coin::{
             CoinPredicate,
@@ -355,6 +358,20 @@ where
         Err(ValidityError::TransactionMaturity)?;
     }
 
+    if tx.expiration() < block_height {
+        Err(ValidityError::TransactionExpiration)?;
+    }
+
+    if let Some(index) = tx.policies().get(PolicyType::Owner) {
+        let is_spendable = usize::try_from(index)
+            .ok()
+            .and_then(|index| tx.inputs().get(index))
+            .is_some_and(|input| input.is_coin() || input.is_message());
+        if !is_spendable {
+            Err(ValidityError::TransactionOwnerInputIndex { index })?;
+        }
+    }
+
     if tx.inputs().len() > tx_params.max_inputs() as usize {
         Err(ValidityError::TransactionInputsMax)?
     }
@@ -3,6 +3,7 @@ use fuel_types::{
     AssetId,
     ContractId,
     MessageId,
+    Word,
 };
 
 /// The error returned during the checking of the transaction's validity rules.
@@ -176,4 +177,14 @@ pub enum ValidityError {
     },
     /// The `Create` transaction doesn't contain `Output::ContractCreated`.
     TransactionOutputDoesntContainContractCreated,
+    /// The `Upgrade` transaction's VM parameters are out of the safe ranges, see
+    /// [`crate::VmParameters::validate`].
+    TransactionUpgradeInvalidVmParameters,
+    /// The transaction expired before the current block height, see the `Expiration`
+    /// policy.
+    TransactionExpiration,
+    /// The `Owner` policy doesn't point to a coin or message input.
+    TransactionOwnerInputIndex {
+        index: Word,
+    },
 }
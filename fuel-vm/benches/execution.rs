@@ -17,6 +17,7 @@ use fuel_tx::{
 use fuel_vm::{
     checked_transaction::IntoChecked,
     interpreter::{
+        InstructionCache,
         InterpreterParams,
         MemoryInstance,
     },
@@ -172,6 +173,56 @@ fn execution(c: &mut Criterion) {
         })
     });
 
+    // Loop-heavy script with a longer body, with and without the instruction cache
+    let loop_body = [
+        op::add(0x10, 0x10, RegId::ONE),
+        op::slli(0x11, 0x10, 2),
+        op::xor(0x12, 0x11, 0x10),
+        op::srli(0x13, 0x12, 3),
+        op::add(0x14, 0x13, 0x10),
+        op::and(0x15, 0x14, 0x12),
+        op::or(0x16, 0x15, 0x11),
+        op::jmpb(RegId::ZERO, 6),
+    ];
+
+    for cached in [false, true] {
+        let mut interpreter = Interpreter::<_, _, Script>::with_storage(
+            MemoryInstance::new(),
+            MemoryStorage::default(),
+            InterpreterParams {
+                gas_costs: GasCosts::free(),
+                ..Default::default()
+            },
+        );
+        if cached {
+            interpreter = interpreter.with_instruction_cache(InstructionCache::new());
+        }
+
+        let script =
+            TransactionBuilder::script(loop_body.iter().copied().collect(), vec![])
+                .max_fee_limit(0)
+                .add_random_fee_input()
+                .finalize();
+        let script = script
+            .into_checked_basic(Default::default(), &Default::default())
+            .unwrap();
+        let script = script.test_into_ready();
+        black_box(interpreter.init_script(script)).unwrap();
+
+        let name = if cached {
+            "Infinite arithmetic loop with instruction cache"
+        } else {
+            "Infinite arithmetic loop without instruction cache"
+        };
+        group_execution.bench_function(name, |b| {
+            b.iter(|| {
+                for _ in 0..1000 {
+                    black_box(interpreter.execute()).unwrap();
+                }
+            })
+        });
+    }
+
     group_execution.finish();
 }
 
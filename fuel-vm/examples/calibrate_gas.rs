@@ -0,0 +1,33 @@
+//! This example calibrates the gas costs on the current machine against the in-memory
+//! storage, and prints the explained measurements, the Rust source of the calibrated
+//! gas costs and their JSON.
+//!
+//! Run it in the release mode: `cargo run --release --example calibrate_gas
+//! --features calibration`.
+
+use fuel_tx::consensus_parameters::gas::GasCostsValuesV3;
+use fuel_vm::{
+    calibration::Calibrator,
+    storage::MemoryStorage,
+};
+
+fn main() {
+    let report = Calibrator::new(MemoryStorage::default())
+        .run()
+        .expect("The calibration should succeed");
+
+    // The costs that aren't measured keep their default values
+    let base = match fuel_tx::GasCostsValues::default() {
+        fuel_tx::GasCostsValues::V3(costs) => costs,
+        _ => GasCostsValuesV3::unit(),
+    };
+
+    println!("{report}");
+    println!("{}", report.to_rust_source(&base));
+    println!(
+        "{}",
+        report
+            .to_json(&base)
+            .expect("The gas costs should be serializable")
+    );
+}
@@ -87,7 +87,7 @@ fn main() {
                                 .unwrap_or_else(|| "???".to_owned()),
                         );
                     }
-                    DebugEval::Continue => {}
+                    DebugEval::Preempted | DebugEval::Continue => {}
                 }
                 t = vm.resume().expect("panicked");
             }
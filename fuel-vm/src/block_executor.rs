@@ -0,0 +1,678 @@
+//! Reference execution of a block: a batch of transactions followed by the `Mint`.
+//!
+//! Every transaction runs on top of an [`OverlayStorage`], and its changes are applied
+//! to the storage only if the execution succeeded. Invalid transactions are skipped,
+//! while the reverted scripts are included into the block with their state changes
+//! discarded. The fees of the included transactions are minted to the coinbase
+//! contract by the final [`Mint`] transaction.
+//!
+//! With the `std` feature, [`BlockExecutor::execute_parallel`] executes the scripts
+//! without common inputs concurrently.
+
+#[cfg(feature = "std")]
+mod parallel;
+
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+use fuel_storage::MerkleRootStorage;
+use fuel_tx::{
+    field::{
+        Expiration,
+        Inputs,
+        Maturity,
+        MaxFeeLimit,
+        Outputs,
+    },
+    input,
+    output,
+    ConsensusParameters,
+    Input,
+    Mint,
+    Output,
+    Receipt,
+    ScriptExecutionResult,
+    Transaction,
+    TxId,
+    TxPointer,
+    UniqueIdentifier,
+    UtxoId,
+    ValidityError,
+};
+use fuel_types::{
+    canonical::Serialize,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    checked_transaction::{
+        CheckError,
+        Checked,
+        CheckedTransaction,
+        IntoChecked,
+    },
+    crypto::ephemeral_merkle_root,
+    error::InterpreterError,
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        Interpreter,
+        InterpreterParams,
+        MemoryInstance,
+    },
+    message_outbox::MessageOutbox,
+    state::ProgramState,
+    storage::{
+        ContractsAssets,
+        ContractsState,
+        InterpreterStorage,
+        OverlayChanges,
+        OverlayStorage,
+        StateOverrides,
+    },
+};
+
+/// The fields of the block header used by the execution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockHeader {
+    /// The height of the block.
+    pub height: BlockHeight,
+    /// The timestamp of the block, in TAI64 format.
+    pub timestamp: Word,
+    /// The contract receiving the fees. The fees are burned if it's zeroed.
+    pub coinbase: ContractId,
+    /// The gas price of the block.
+    pub gas_price: Word,
+}
+
+impl BlockHeader {
+    /// Create the header of the block at the `height`.
+    pub const fn new(
+        height: BlockHeight,
+        timestamp: Word,
+        coinbase: ContractId,
+        gas_price: Word,
+    ) -> Self {
+        Self {
+            height,
+            timestamp,
+            coinbase,
+            gas_price,
+        }
+    }
+}
+
+/// The latest UTXO of the contract, spent by the next transaction using the contract.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContractUtxo {
+    /// The id of the UTXO.
+    pub utxo_id: UtxoId,
+    /// The pointer to the transaction that created the UTXO.
+    pub tx_pointer: TxPointer,
+}
+
+/// Error that aborts the execution of the whole block.
+#[derive(Debug, derive_more::Display)]
+pub enum BlockExecutionError<StorageError> {
+    /// Storage I/O error.
+    #[display(fmt = "Storage error: {}", _0)]
+    Storage(StorageError),
+    /// The block can't fit the transactions and the `Mint`.
+    #[display(fmt = "The block has too many transactions")]
+    TooManyTransactions,
+    /// The sum of the fees overflows.
+    #[display(fmt = "The fees of the block overflow")]
+    FeeOverflow,
+    /// The coinbase contract doesn't exist.
+    #[display(fmt = "The coinbase contract {} doesn't exist", _0)]
+    CoinbaseNotFound(ContractId),
+}
+
+/// The reason why the transaction wasn't included into the block.
+#[derive(Debug, derive_more::Display)]
+pub enum TransactionSkipReason<StorageError> {
+    /// The `Mint` transaction is created by the block executor.
+    #[display(fmt = "The `Mint` transaction can't be executed")]
+    Mint,
+    /// The transaction is invalid against the current state.
+    #[display(fmt = "{}", _0)]
+    Execution(InterpreterError<StorageError>),
+}
+
+/// The transaction included into the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedTransaction {
+    /// The id of the transaction.
+    pub id: TxId,
+    /// The position of the transaction in the block.
+    pub tx_pointer: TxPointer,
+    /// The transaction after the execution, with the updated outputs.
+    pub transaction: Transaction,
+    /// The final state of the program.
+    pub state: ProgramState,
+    /// The receipts of the execution.
+    pub receipts: Vec<Receipt>,
+    /// The Merkle root of the receipts.
+    pub receipts_root: Bytes32,
+    /// The gas used by the script, zero for the other transactions.
+    pub gas_used: Word,
+    /// The fee paid by the transaction.
+    pub fee: Word,
+}
+
+impl ExecutedTransaction {
+    /// Returns `true` if the state changes of the transaction were discarded.
+    pub const fn is_reverted(&self) -> bool {
+        matches!(self.state, ProgramState::Revert(_))
+    }
+}
+
+/// The transaction that wasn't included into the block.
+#[derive(Debug)]
+pub struct SkippedTransaction<StorageError> {
+    /// The id of the transaction.
+    pub id: TxId,
+    /// The reason why it was skipped.
+    pub reason: TransactionSkipReason<StorageError>,
+}
+
+/// The outcome of the transaction of the batch.
+#[derive(Debug)]
+pub enum TransactionExecutionResult<StorageError> {
+    /// The transaction was included into the block.
+    Included(Box<ExecutedTransaction>),
+    /// The transaction was skipped.
+    Skipped(SkippedTransaction<StorageError>),
+}
+
+/// Result of [`BlockExecutor::execute`].
+#[derive(Debug)]
+pub struct ExecutedBlock<StorageError> {
+    /// The header of the block.
+    pub header: BlockHeader,
+    /// The outcomes of the transactions, in the order of the batch.
+    pub results: Vec<TransactionExecutionResult<StorageError>>,
+    /// The `Mint` transaction, the last one of the block.
+    pub mint: Mint,
+    /// The sum of the fees of the included transactions.
+    pub fees: Word,
+    /// The Merkle root of the included transactions, including the `Mint`.
+    pub transactions_root: Bytes32,
+    /// The Merkle root of the ids of the messages sent by the block.
+    pub message_outbox_root: Bytes32,
+}
+
+impl<StorageError> ExecutedBlock<StorageError> {
+    /// The transactions included into the block, excluding the `Mint`.
+    pub fn included(&self) -> impl Iterator<Item = &ExecutedTransaction> {
+        self.results.iter().filter_map(|result| match result {
+            TransactionExecutionResult::Included(tx) => Some(tx.as_ref()),
+            TransactionExecutionResult::Skipped(_) => None,
+        })
+    }
+
+    /// The transactions that weren't included into the block.
+    pub fn skipped(&self) -> impl Iterator<Item = &SkippedTransaction<StorageError>> {
+        self.results.iter().filter_map(|result| match result {
+            TransactionExecutionResult::Included(_) => None,
+            TransactionExecutionResult::Skipped(tx) => Some(tx),
+        })
+    }
+
+    /// The receipts roots of the included transactions, in the block order.
+    pub fn receipts_roots(&self) -> Vec<Bytes32> {
+        self.included().map(|tx| tx.receipts_root).collect()
+    }
+
+    /// The Merkle tree of the messages sent by the included transactions, used to
+    /// prove their inclusion into the block. The messages of the scripts that
+    /// didn't succeed are discarded together with their state changes.
+    pub fn message_outbox(&self) -> MessageOutbox {
+        let mut outbox = MessageOutbox::new();
+        self.included()
+            .filter(|tx| {
+                tx.receipts.iter().find_map(Receipt::result)
+                    == Some(&ScriptExecutionResult::Success)
+            })
+            .flat_map(|tx| tx.receipts.iter().filter_map(Receipt::message_id))
+            .for_each(|message_id| outbox.push(message_id));
+        outbox
+    }
+
+    /// The transactions of the block, including the `Mint`.
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.included()
+            .map(|tx| tx.transaction.clone())
+            .chain(core::iter::once(self.mint.clone().into()))
+            .collect()
+    }
+}
+
+/// Executes the blocks against the storage.
+///
+/// The executor tracks the latest UTXOs of the contracts used by the executed blocks,
+/// which are spent by the contract input of the `Mint`.
+#[derive(Debug, Clone)]
+pub struct BlockExecutor<S> {
+    storage: S,
+    consensus_params: ConsensusParameters,
+    contract_utxos: BTreeMap<ContractId, ContractUtxo>,
+}
+
+impl<S> BlockExecutor<S> {
+    /// Create the executor of the blocks with the `consensus_params`.
+    pub fn new(storage: S, consensus_params: ConsensusParameters) -> Self {
+        Self {
+            storage,
+            consensus_params,
+            contract_utxos: BTreeMap::new(),
+        }
+    }
+
+    /// The consensus parameters of the blocks.
+    pub const fn consensus_params(&self) -> &ConsensusParameters {
+        &self.consensus_params
+    }
+
+    /// The latest UTXO of the contract, if it was created or used by the executed
+    /// blocks or set with [`Self::set_contract_utxo`].
+    pub fn contract_utxo(&self, contract_id: &ContractId) -> Option<&ContractUtxo> {
+        self.contract_utxos.get(contract_id)
+    }
+
+    /// Set the latest UTXO of the contract created before the executed blocks, e.g.
+    /// of the coinbase contract. The zeroed UTXO is used for the unknown contracts.
+    pub fn set_contract_utxo(&mut self, contract_id: ContractId, utxo: ContractUtxo) {
+        self.contract_utxos.insert(contract_id, utxo);
+    }
+
+    /// Records the contract UTXOs created by the outputs of the transaction.
+    fn update_contract_utxos(
+        &mut self,
+        id: &TxId,
+        tx_pointer: TxPointer,
+        tx: &Transaction,
+    ) {
+        let (inputs, outputs) = match tx {
+            Transaction::Script(tx) => (tx.inputs(), tx.outputs()),
+            Transaction::Create(tx) => (tx.inputs(), tx.outputs()),
+            Transaction::Upgrade(tx) => (tx.inputs(), tx.outputs()),
+            Transaction::Upload(tx) => (tx.inputs(), tx.outputs()),
+            Transaction::Mint(_) => return,
+        };
+        for (index, output) in outputs.iter().enumerate() {
+            let contract_id = match output {
+                Output::Contract(contract) => {
+                    match inputs.get(usize::from(contract.input_index)) {
+                        Some(Input::Contract(input)) => input.contract_id,
+                        _ => continue,
+                    }
+                }
+                Output::ContractCreated { contract_id, .. } => *contract_id,
+                _ => continue,
+            };
+            // The number of outputs is bounded by `u16::MAX` in the transaction checks
+            let index = u16::try_from(index).unwrap_or(u16::MAX);
+            self.contract_utxos.insert(
+                contract_id,
+                ContractUtxo {
+                    utxo_id: UtxoId::new(*id, index),
+                    tx_pointer,
+                },
+            );
+        }
+    }
+
+    /// Consumes the executor, returning the storage.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
+
+impl<S> BlockExecutor<S>
+where
+    S: InterpreterStorage
+        + MerkleRootStorage<ContractId, ContractsAssets>
+        + MerkleRootStorage<ContractId, ContractsState>,
+{
+    /// Executes the `transactions` in order and appends the `Mint` of their fees.
+    ///
+    /// The transactions that aren't valid at the height of the block are skipped, since
+    /// they may have been checked against another height.
+    ///
+    /// The changes of the included transactions and the minted fees are applied to
+    /// the storage. If an error is returned, the storage may contain the changes of
+    /// the transactions executed before it.
+    pub fn execute<I>(
+        &mut self,
+        header: &BlockHeader,
+        transactions: I,
+    ) -> Result<ExecutedBlock<S::DataError>, BlockExecutionError<S::DataError>>
+    where
+        I: IntoIterator,
+        I::Item: Into<CheckedTransaction>,
+    {
+        let mut results = Vec::new();
+        for checked in transactions {
+            results.push(execute_transaction(
+                &mut self.storage,
+                &self.consensus_params,
+                header,
+                checked.into(),
+            )?);
+        }
+        self.seal(header, results)
+    }
+
+    /// Assigns the `TxPointer`s to the included transactions, mints their fees and
+    /// computes the roots of the block.
+    fn seal(
+        &mut self,
+        header: &BlockHeader,
+        mut results: Vec<TransactionExecutionResult<S::DataError>>,
+    ) -> Result<ExecutedBlock<S::DataError>, BlockExecutionError<S::DataError>> {
+        let mut fees: Word = 0;
+        let mut tx_index: u16 = 0;
+
+        for result in results.iter_mut() {
+            if let TransactionExecutionResult::Included(tx) = result {
+                tx.tx_pointer = TxPointer::new(header.height, tx_index);
+                self.update_contract_utxos(&tx.id, tx.tx_pointer, &tx.transaction);
+                fees = fees
+                    .checked_add(tx.fee)
+                    .ok_or(BlockExecutionError::FeeOverflow)?;
+                // The last index is reserved for the `Mint`
+                tx_index = tx_index
+                    .checked_add(1)
+                    .filter(|index| *index < u16::MAX)
+                    .ok_or(BlockExecutionError::TooManyTransactions)?;
+            }
+        }
+
+        let mint = self.mint(header, TxPointer::new(header.height, tx_index), fees)?;
+
+        let mut block = ExecutedBlock {
+            header: *header,
+            results,
+            mint,
+            fees,
+            transactions_root: Bytes32::zeroed(),
+            message_outbox_root: Bytes32::zeroed(),
+        };
+        block.transactions_root =
+            ephemeral_merkle_root(block.transactions().iter().map(|tx| tx.to_bytes()));
+        block.message_outbox_root = block.message_outbox().root();
+
+        Ok(block)
+    }
+
+    /// Creates the `Mint` of the `fees` and credits them to the coinbase contract.
+    ///
+    /// The contract input and output of the `Mint` contain the state of the coinbase
+    /// contract before and after the credit.
+    fn mint(
+        &mut self,
+        header: &BlockHeader,
+        tx_pointer: TxPointer,
+        fees: Word,
+    ) -> Result<Mint, BlockExecutionError<S::DataError>> {
+        let base_asset_id = *self.consensus_params.base_asset_id();
+        let coinbase = header.coinbase;
+
+        let (mint_amount, input_contract, output_contract) =
+            if coinbase == ContractId::zeroed() {
+                let output_contract = output::contract::Contract {
+                    input_index: 0,
+                    balance_root: Bytes32::zeroed(),
+                    state_root: Bytes32::zeroed(),
+                };
+                (0, input::contract::Contract::default(), output_contract)
+            } else {
+                if !self
+                    .storage
+                    .storage_contract_exists(&coinbase)
+                    .map_err(BlockExecutionError::Storage)?
+                {
+                    return Err(BlockExecutionError::CoinbaseNotFound(coinbase));
+                }
+
+                let utxo = self
+                    .contract_utxos
+                    .get(&coinbase)
+                    .copied()
+                    .unwrap_or_default();
+                let state_root = self.contract_state_root(&coinbase)?;
+                let input_contract = input::contract::Contract {
+                    utxo_id: utxo.utxo_id,
+                    balance_root: self.contract_balance_root(&coinbase)?,
+                    state_root,
+                    tx_pointer: utxo.tx_pointer,
+                    contract_id: coinbase,
+                };
+
+                let balance = self
+                    .storage
+                    .contract_asset_id_balance(&coinbase, &base_asset_id)
+                    .map_err(BlockExecutionError::Storage)?
+                    .unwrap_or_default()
+                    .checked_add(fees)
+                    .ok_or(BlockExecutionError::FeeOverflow)?;
+                self.storage
+                    .contract_asset_id_balance_insert(&coinbase, &base_asset_id, balance)
+                    .map_err(BlockExecutionError::Storage)?;
+
+                let output_contract = output::contract::Contract {
+                    input_index: 0,
+                    balance_root: self.contract_balance_root(&coinbase)?,
+                    state_root,
+                };
+                (fees, input_contract, output_contract)
+            };
+
+        let mut mint = Transaction::mint(
+            tx_pointer,
+            input_contract,
+            output_contract,
+            mint_amount,
+            base_asset_id,
+            header.gas_price,
+        );
+        let chain_id = self.consensus_params.chain_id();
+        // The id is computed for the caller, it can't fail for `Mint`
+        let _ = fuel_tx::Cacheable::precompute(&mut mint, &chain_id);
+
+        if coinbase != ContractId::zeroed() {
+            self.contract_utxos.insert(
+                coinbase,
+                ContractUtxo {
+                    utxo_id: UtxoId::new(mint.id(&chain_id), 0),
+                    tx_pointer,
+                },
+            );
+        }
+
+        Ok(mint)
+    }
+
+    fn contract_balance_root(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<Bytes32, BlockExecutionError<S::DataError>> {
+        MerkleRootStorage::<ContractId, ContractsAssets>::root(&self.storage, contract_id)
+            .map(Bytes32::new)
+            .map_err(BlockExecutionError::Storage)
+    }
+
+    fn contract_state_root(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<Bytes32, BlockExecutionError<S::DataError>> {
+        MerkleRootStorage::<ContractId, ContractsState>::root(&self.storage, contract_id)
+            .map(Bytes32::new)
+            .map_err(BlockExecutionError::Storage)
+    }
+}
+
+/// Executes the transaction on top of the `storage`, applying its changes unless it
+/// reverted.
+fn execute_transaction<S>(
+    storage: &mut S,
+    consensus_params: &ConsensusParameters,
+    header: &BlockHeader,
+    checked: CheckedTransaction,
+) -> Result<TransactionExecutionResult<S::DataError>, BlockExecutionError<S::DataError>>
+where
+    S: InterpreterStorage,
+{
+    match checked {
+        CheckedTransaction::Script(checked) => {
+            execute_checked(storage, consensus_params, header, checked)
+        }
+        CheckedTransaction::Create(checked) => {
+            execute_checked(storage, consensus_params, header, checked)
+        }
+        CheckedTransaction::Upgrade(checked) => {
+            execute_checked(storage, consensus_params, header, checked)
+        }
+        CheckedTransaction::Upload(checked) => {
+            execute_checked(storage, consensus_params, header, checked)
+        }
+        CheckedTransaction::Mint(checked) => {
+            Ok(TransactionExecutionResult::Skipped(SkippedTransaction {
+                id: checked.id(),
+                reason: TransactionSkipReason::Mint,
+            }))
+        }
+    }
+}
+
+/// Executes the checked transaction of the concrete type on top of the `storage`.
+fn execute_checked<S, Tx>(
+    storage: &mut S,
+    consensus_params: &ConsensusParameters,
+    header: &BlockHeader,
+    checked: Checked<Tx>,
+) -> Result<TransactionExecutionResult<S::DataError>, BlockExecutionError<S::DataError>>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+{
+    let id = checked.id();
+    let mut transaction = checked.transaction().clone();
+    let skip = |error| {
+        TransactionExecutionResult::Skipped(SkippedTransaction {
+            id,
+            reason: TransactionSkipReason::Execution(error),
+        })
+    };
+
+    if let Err(error) = validate_height(&transaction, header.height) {
+        return Ok(skip(InterpreterError::CheckError(CheckError::Validity(
+            error,
+        ))))
+    }
+
+    let gas_costs = consensus_params.gas_costs();
+    let fee_params = consensus_params.fee_params();
+    let ready = match checked.into_ready(header.gas_price, gas_costs, fee_params) {
+        Ok(ready) => ready,
+        Err(error) => return Ok(skip(InterpreterError::CheckError(error))),
+    };
+
+    let overrides = StateOverrides::new()
+        .with_block_height(header.height)
+        .with_timestamp(header.timestamp)
+        .with_coinbase(header.coinbase);
+    let mut vm = Interpreter::<_, _, Tx>::with_storage(
+        MemoryInstance::new(),
+        OverlayStorage::with_overrides(&*storage, &overrides),
+        InterpreterParams::new(header.gas_price, consensus_params),
+    );
+
+    let (outputs, state, receipts) = match vm.transact(ready) {
+        Ok(state) => (
+            state.tx().outputs().clone(),
+            *state.state(),
+            state.receipts().to_vec(),
+        ),
+        Err(InterpreterError::Storage(error)) => {
+            return Err(BlockExecutionError::Storage(error))
+        }
+        Err(error) => return Ok(skip(error)),
+    };
+    let changes = if matches!(state, ProgramState::Revert(_)) {
+        OverlayChanges::default()
+    } else {
+        vm.as_mut().take_changes()
+    };
+    drop(vm);
+    changes
+        .apply(storage)
+        .map_err(BlockExecutionError::Storage)?;
+
+    let gas_used = receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap_or_default();
+    // The VM clears the signed fields of the inputs, so only the outputs are taken
+    *transaction.outputs_mut() = outputs;
+    // The refund was already computed by the VM when updating the outputs
+    let refund = transaction
+        .refund_fee(gas_costs, fee_params, gas_used, header.gas_price)
+        .unwrap_or_default();
+    let fee = transaction.max_fee_limit().saturating_sub(refund);
+
+    Ok(TransactionExecutionResult::Included(Box::new(
+        ExecutedTransaction {
+            id,
+            // Assigned when the block is sealed
+            tx_pointer: TxPointer::default(),
+            receipts_root: ephemeral_merkle_root(
+                receipts.iter().map(|receipt| receipt.to_bytes()),
+            ),
+            transaction: transaction.into(),
+            state,
+            receipts,
+            gas_used,
+            fee,
+        },
+    )))
+}
+
+/// Checks the fields of the transaction that depend on the height of the block.
+fn validate_height<Tx>(tx: &Tx, height: BlockHeight) -> Result<(), ValidityError>
+where
+    Tx: Maturity + Expiration,
+{
+    if tx.maturity() > height {
+        return Err(ValidityError::TransactionMaturity)
+    }
+
+    if tx.expiration() < height {
+        return Err(ValidityError::TransactionExpiration)
+    }
+
+    Ok(())
+}
+
+impl<S> AsRef<S> for BlockExecutor<S> {
+    fn as_ref(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<S> AsMut<S> for BlockExecutor<S> {
+    fn as_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+}
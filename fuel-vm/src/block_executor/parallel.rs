@@ -0,0 +1,260 @@
+//! Parallel execution of the independent scripts of the block.
+//!
+//! The scripts declare every contract they can touch with `Input::Contract`, so the
+//! scripts that don't share contract, coin or message inputs can't observe the changes
+//! of each other. Such scripts are executed concurrently on separate overlays of the
+//! storage, and the overlays are merged afterwards. The other transactions can change
+//! any state, so they split the block into batches of scripts and are executed alone.
+
+use alloc::{
+    collections::BTreeMap,
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Reverse,
+    num::NonZeroUsize,
+};
+
+use fuel_storage::MerkleRootStorage;
+use fuel_tx::{
+    field::Inputs,
+    ConsensusParameters,
+    Input,
+    Script,
+    UtxoId,
+};
+use fuel_types::{
+    ContractId,
+    Nonce,
+};
+
+use super::{
+    execute_checked,
+    execute_transaction,
+    BlockExecutionError,
+    BlockExecutor,
+    BlockHeader,
+    ExecutedBlock,
+    TransactionExecutionResult,
+};
+use crate::{
+    checked_transaction::{
+        Checked,
+        CheckedTransaction,
+    },
+    storage::{
+        ContractsAssets,
+        ContractsState,
+        InterpreterStorage,
+        OverlayChanges,
+        OverlayStorage,
+    },
+};
+
+/// The results of the scripts of the bucket with their indexes, and their changes.
+type BucketOutcome<E> = (Vec<(usize, TransactionExecutionResult<E>)>, OverlayChanges);
+
+impl<S> BlockExecutor<S>
+where
+    S: InterpreterStorage
+        + MerkleRootStorage<ContractId, ContractsAssets>
+        + MerkleRootStorage<ContractId, ContractsState>
+        + Sync,
+    S::DataError: Send,
+{
+    /// Executes the `transactions` like [`BlockExecutor::execute`], running the
+    /// independent scripts on up to `threads` threads.
+    ///
+    /// The results, the receipts and the roots of the block are the same as with the
+    /// sequential execution.
+    pub fn execute_parallel<I>(
+        &mut self,
+        header: &BlockHeader,
+        transactions: I,
+        threads: NonZeroUsize,
+    ) -> Result<ExecutedBlock<S::DataError>, BlockExecutionError<S::DataError>>
+    where
+        I: IntoIterator,
+        I::Item: Into<CheckedTransaction>,
+    {
+        let mut results = Vec::new();
+        let mut scripts = Vec::new();
+
+        for checked in transactions {
+            match checked.into() {
+                CheckedTransaction::Script(script) => scripts.push(script),
+                checked => {
+                    let batch = core::mem::take(&mut scripts);
+                    results.extend(self.execute_scripts(header, batch, threads)?);
+                    results.push(execute_transaction(
+                        &mut self.storage,
+                        &self.consensus_params,
+                        header,
+                        checked,
+                    )?);
+                }
+            }
+        }
+        results.extend(self.execute_scripts(header, scripts, threads)?);
+
+        self.seal(header, results)
+    }
+
+    /// Executes the batch of scripts concurrently and applies their changes to the
+    /// storage, returning the results in the batch order.
+    fn execute_scripts(
+        &mut self,
+        header: &BlockHeader,
+        scripts: Vec<Checked<Script>>,
+        threads: NonZeroUsize,
+    ) -> Result<
+        Vec<TransactionExecutionResult<S::DataError>>,
+        BlockExecutionError<S::DataError>,
+    > {
+        let len = scripts.len();
+        let buckets = schedule(partition(&scripts), threads);
+        let mut scripts: Vec<_> = scripts.into_iter().map(Some).collect();
+        let buckets: Vec<Vec<_>> = buckets
+            .into_iter()
+            .map(|bucket| {
+                bucket
+                    .into_iter()
+                    .filter_map(|index| Some((index, scripts.get_mut(index)?.take()?)))
+                    .collect()
+            })
+            .collect();
+
+        let storage = &self.storage;
+        let consensus_params = &self.consensus_params;
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = buckets
+                .into_iter()
+                .map(|bucket| {
+                    scope.spawn(move || {
+                        execute_bucket(storage, consensus_params, header, bucket)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        let mut results: Vec<_> = (0..len).map(|_| None).collect();
+        for outcome in outcomes {
+            let (bucket_results, changes) = outcome?;
+            changes
+                .apply(&mut self.storage)
+                .map_err(BlockExecutionError::Storage)?;
+            for (index, result) in bucket_results {
+                results[index] = Some(result);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| result.expect("Every script is scheduled exactly once"))
+            .collect())
+    }
+}
+
+/// Executes the scripts of the bucket in order on a separate overlay of the `storage`.
+fn execute_bucket<S>(
+    storage: &S,
+    consensus_params: &ConsensusParameters,
+    header: &BlockHeader,
+    bucket: Vec<(usize, Checked<Script>)>,
+) -> Result<BucketOutcome<S::DataError>, BlockExecutionError<S::DataError>>
+where
+    S: InterpreterStorage,
+{
+    let mut overlay = OverlayStorage::new(storage);
+    let results = bucket
+        .into_iter()
+        .map(|(index, script)| {
+            let result = execute_checked(&mut overlay, consensus_params, header, script)?;
+            Ok((index, result))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((results, overlay.take_changes()))
+}
+
+/// The input that makes the transactions spending it conflicting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ConflictKey {
+    Contract(ContractId),
+    Coin(UtxoId),
+    Message(Nonce),
+}
+
+fn conflict_keys(script: &Script) -> impl Iterator<Item = ConflictKey> + '_ {
+    script.inputs().iter().filter_map(|input| match input {
+        Input::Contract(contract) => Some(ConflictKey::Contract(contract.contract_id)),
+        Input::CoinSigned(_) | Input::CoinPredicate(_) => {
+            input.utxo_id().copied().map(ConflictKey::Coin)
+        }
+        Input::MessageCoinSigned(_)
+        | Input::MessageCoinPredicate(_)
+        | Input::MessageDataSigned(_)
+        | Input::MessageDataPredicate(_) => {
+            input.nonce().copied().map(ConflictKey::Message)
+        }
+    })
+}
+
+/// Splits the scripts into the groups without common inputs, returning the indexes of
+/// the scripts of every group. The groups are ordered by their first script.
+fn partition(scripts: &[Checked<Script>]) -> Vec<Vec<usize>> {
+    let mut parents: Vec<usize> = (0..scripts.len()).collect();
+    let mut owners = BTreeMap::new();
+
+    for (index, script) in scripts.iter().enumerate() {
+        for key in conflict_keys(script.transaction()) {
+            let owner = *owners.entry(key).or_insert(index);
+            let (a, b) = (find(&mut parents, owner), find(&mut parents, index));
+            // The root of the group is always its first script
+            parents[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut groups = BTreeMap::<_, Vec<_>>::new();
+    for index in 0..scripts.len() {
+        let root = find(&mut parents, index);
+        groups.entry(root).or_default().push(index);
+    }
+    groups.into_values().collect()
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        let parent = parents[index];
+        parents[index] = parents[parent];
+        index = parent;
+    }
+    index
+}
+
+/// Distributes the groups between at most `threads` buckets, balancing the number of
+/// scripts in them. The scripts of every bucket are in the batch order.
+fn schedule(mut groups: Vec<Vec<usize>>, threads: NonZeroUsize) -> Vec<Vec<usize>> {
+    // The sort is stable, so the result is deterministic
+    groups.sort_by_key(|group| Reverse(group.len()));
+
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); threads.get().min(groups.len())];
+    for group in groups {
+        if let Some(bucket) = buckets.iter_mut().min_by_key(|bucket| bucket.len()) {
+            bucket.extend(group);
+        }
+    }
+    for bucket in buckets.iter_mut() {
+        bucket.sort_unstable();
+    }
+    buckets
+}
@@ -0,0 +1,1154 @@
+//! Benchmark-driven calibration of the gas costs.
+//!
+//! See [`Calibrator`].
+
+use std::{
+    fmt,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use fuel_asm::{
+    op,
+    GMArgs,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_crypto::SecretKey;
+use fuel_tx::{
+    consensus_parameters::gas::GasCostsValuesV3,
+    ConsensusParameters,
+    Contract,
+    DependentCost,
+    Finalizable,
+    GasCostsValues,
+    Input,
+    Output,
+    Receipt,
+    Script,
+    TransactionBuilder,
+    TxPointer,
+    UtxoId,
+};
+use fuel_types::{
+    canonical::Serialize,
+    Bytes32,
+    Salt,
+    Word,
+};
+
+use crate::{
+    call::Call,
+    checked_transaction::{
+        CheckError,
+        IntoChecked,
+        Ready,
+    },
+    error::InterpreterError,
+    interpreter::{
+        Interpreter,
+        InterpreterParams,
+        MemoryInstance,
+    },
+    storage::InterpreterStorage,
+};
+
+/// Pointer to the first buffer of the measured programs.
+const BUFFER_A: u8 = 0x10;
+/// The number of units, or [`FIXED_OPERAND`] for the fixed costs.
+const UNITS: u8 = 0x11;
+/// Pointer to the second buffer of the measured programs.
+const BUFFER_B: u8 = 0x12;
+/// Scratch register of the setup.
+const SCRATCH: u8 = 0x13;
+/// Destination of the results of the measured instructions.
+const RESULT: u8 = 0x20;
+
+/// The operand of the instructions with the fixed cost.
+const FIXED_OPERAND: Word = 7;
+
+/// The maximal size of the dependent cost measurements, limited by `MOVI`.
+pub const MAX_CALIBRATION_SIZE: Word = (1 << 18) - 1;
+
+/// Size of the storage slot in bytes.
+const SLOT_SIZE: Word = Bytes32::LEN as Word;
+
+/// Owner of the coin paying for the measured transactions.
+const FEE_SECRET: [u8; SecretKey::LEN] = [0x11; SecretKey::LEN];
+
+/// Parameters of the [`Calibrator`].
+#[derive(Debug, Clone)]
+pub struct CalibrationConfig {
+    /// Number of the executions of every measured program. The fastest execution is
+    /// used to reduce the noise.
+    pub iterations: usize,
+    /// Number of times the measured instruction is repeated in the program.
+    pub repetitions: usize,
+    /// Sizes in bytes used to measure the dependent costs. The storage operations
+    /// use the size divided by 32 as the number of slots.
+    pub sizes: Vec<Word>,
+    /// Consensus parameters of the measured transactions.
+    pub consensus_params: ConsensusParameters,
+    /// Names of the measured gas costs, e.g. `"mcp"`, or all measurable costs if
+    /// empty. `noop` is always measured, since it defines the price of one gas.
+    pub cases: Vec<String>,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            repetitions: 200,
+            sizes: vec![32, 256, 1024, 4096, 16384],
+            consensus_params: ConsensusParameters::standard(),
+            cases: vec![],
+        }
+    }
+}
+
+/// Error of the calibration.
+#[derive(Debug, derive_more::Display)]
+pub enum CalibrationError<StorageError> {
+    /// The size exceeds [`MAX_CALIBRATION_SIZE`].
+    #[display(fmt = "The calibration size {_0} is too large")]
+    InvalidSize(Word),
+    /// The gas cost isn't measured by the calibration, see
+    /// [`CalibrationReport::uncalibrated`].
+    #[display(fmt = "The gas cost `{_0}` can't be calibrated")]
+    UnknownCase(String),
+    /// Failed to deploy the measured contract.
+    #[display(fmt = "Storage error: {}", _0)]
+    Storage(StorageError),
+    /// The measured transaction is invalid.
+    #[display(fmt = "Failed to check the transaction: {_0:?}")]
+    Check(CheckError),
+    /// The interpreter failed to execute the measured transaction.
+    #[display(fmt = "{}", _0)]
+    Interpreter(InterpreterError<StorageError>),
+    /// The measured program panicked or reverted, e.g. due to the storage backend.
+    #[display(fmt = "The measurement of `{case}` failed with {receipt:?}")]
+    Failed {
+        /// The name of the measured gas cost.
+        case: &'static str,
+        /// The panic or revert receipt.
+        receipt: Box<Receipt>,
+    },
+}
+
+/// How the units of a dependent cost are derived from the measured size.
+#[derive(Debug, Clone, Copy)]
+enum Units {
+    /// The size in bytes, up to the limit of the instruction.
+    Bytes { max: Word },
+    /// The number of storage slots.
+    Slots,
+}
+
+impl Units {
+    fn of_size(self, size: Word) -> Option<Word> {
+        match self {
+            Units::Bytes { max } => (size <= max).then_some(size),
+            Units::Slots => Some(size.saturating_div(SLOT_SIZE).max(1)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Body {
+    Fixed(Instruction),
+    Dependent(Units, fn(Word) -> Instruction),
+}
+
+/// The measured instruction with its setup.
+#[derive(Debug, Clone)]
+struct Case {
+    name: &'static str,
+    setup: Vec<Instruction>,
+    body: Body,
+}
+
+impl Case {
+    fn fixed(name: &'static str, instruction: Instruction) -> Self {
+        Self {
+            name,
+            setup: vec![],
+            body: Body::Fixed(instruction),
+        }
+    }
+
+    fn dependent(
+        name: &'static str,
+        units: Units,
+        instruction: fn(Word) -> Instruction,
+    ) -> Self {
+        Self {
+            name,
+            setup: vec![],
+            body: Body::Dependent(units, instruction),
+        }
+    }
+
+    fn with_setup(mut self, setup: Vec<Instruction>) -> Self {
+        self.setup = setup;
+        self
+    }
+}
+
+/// The measured cases. The instructions must not modify the registers used by the
+/// setup, and must succeed when repeated.
+fn cases() -> Vec<Case> {
+    fn imm12(units: Word) -> u16 {
+        u16::try_from(units).unwrap_or(u16::MAX)
+    }
+    fn imm24(units: Word) -> u32 {
+        u32::try_from(units).unwrap_or(u32::MAX)
+    }
+    let bytes = |max: Word| Units::Bytes { max };
+
+    vec![
+        Case::fixed("add", op::add(RESULT, UNITS, UNITS)),
+        Case::fixed("addi", op::addi(RESULT, UNITS, 1)),
+        Case::fixed("and", op::and(RESULT, UNITS, UNITS)),
+        Case::fixed("andi", op::andi(RESULT, UNITS, 1)),
+        Case::fixed("bal", op::bal(RESULT, BUFFER_A, RegId::FP)),
+        Case::fixed("bhei", op::bhei(RESULT)),
+        Case::fixed("bhsh", op::bhsh(BUFFER_B, RegId::ZERO)),
+        Case::fixed("burn", op::burn(RegId::ONE, BUFFER_A)).with_setup(vec![
+            op::not(SCRATCH, RegId::ZERO),
+            op::mint(SCRATCH, BUFFER_A),
+        ]),
+        Case::fixed("cb", op::cb(BUFFER_B)),
+        Case::fixed("cfsi", op::cfsi(0)),
+        Case::fixed("div", op::div(RESULT, UNITS, UNITS)),
+        Case::fixed("divi", op::divi(RESULT, UNITS, 1)),
+        Case::fixed("eq", op::eq(RESULT, UNITS, UNITS)),
+        Case::fixed("exp", op::exp(RESULT, UNITS, RegId::ONE)),
+        Case::fixed("expi", op::expi(RESULT, UNITS, 1)),
+        Case::fixed("flag", op::flag(RegId::ZERO)),
+        Case::fixed("gm", op::gm_args(RESULT, GMArgs::IsCallerExternal)),
+        Case::fixed("gt", op::gt(RESULT, UNITS, UNITS)),
+        Case::fixed("gtf", op::gtf_args(RESULT, RegId::ZERO, GTFArgs::Type)),
+        Case::fixed("jne", op::jne(RegId::ZERO, RegId::ZERO, RegId::ZERO)),
+        Case::fixed("jnei", op::jnei(RegId::ZERO, RegId::ZERO, 0)),
+        Case::fixed("jnzi", op::jnzi(RegId::ZERO, 0)),
+        Case::fixed("jmpf", op::jmpf(RegId::ZERO, 0)),
+        Case::fixed("jnzf", op::jnzf(RegId::ZERO, RegId::ZERO, 0)),
+        Case::fixed("jnzb", op::jnzb(RegId::ZERO, RegId::ZERO, 0)),
+        Case::fixed("jnef", op::jnef(RegId::ZERO, RegId::ZERO, RegId::ZERO, 0)),
+        Case::fixed("jneb", op::jneb(RegId::ZERO, RegId::ZERO, RegId::ZERO, 0)),
+        Case::fixed("lb", op::lb(RESULT, BUFFER_A, 0)),
+        Case::fixed("log", op::log(BUFFER_A, UNITS, BUFFER_B, SCRATCH)),
+        Case::fixed("lt", op::lt(RESULT, UNITS, UNITS)),
+        Case::fixed("lw", op::lw(RESULT, BUFFER_A, 0)),
+        Case::fixed("mint", op::mint(UNITS, BUFFER_A)),
+        Case::fixed("mlog", op::mlog(RESULT, UNITS, UNITS)),
+        Case::fixed("mod_op", op::mod_(RESULT, UNITS, UNITS)),
+        Case::fixed("modi", op::modi(RESULT, UNITS, 1)),
+        Case::fixed("move_op", op::move_(RESULT, UNITS)),
+        Case::fixed("movi", op::movi(RESULT, 1)),
+        Case::fixed("mroo", op::mroo(RESULT, UNITS, RegId::ONE)),
+        Case::fixed("mul", op::mul(RESULT, UNITS, UNITS)),
+        Case::fixed("muli", op::muli(RESULT, UNITS, 1)),
+        Case::fixed("mldv", op::mldv(RESULT, UNITS, UNITS, UNITS)),
+        Case::fixed("noop", op::noop()),
+        Case::fixed("not", op::not(RESULT, UNITS)),
+        Case::fixed("or", op::or(RESULT, UNITS, UNITS)),
+        Case::fixed("ori", op::ori(RESULT, UNITS, 1)),
+        Case::fixed("sb", op::sb(BUFFER_A, UNITS, 0)),
+        Case::fixed("sll", op::sll(RESULT, UNITS, RegId::ONE)),
+        Case::fixed("slli", op::slli(RESULT, UNITS, 1)),
+        Case::fixed("srl", op::srl(RESULT, UNITS, RegId::ONE)),
+        Case::fixed("srli", op::srli(RESULT, UNITS, 1)),
+        Case::fixed("srw", op::srw(RESULT, SCRATCH, BUFFER_A)),
+        Case::fixed("sub", op::sub(RESULT, UNITS, UNITS)),
+        Case::fixed("subi", op::subi(RESULT, UNITS, 1)),
+        Case::fixed("sw", op::sw(BUFFER_A, UNITS, 0)),
+        Case::fixed("sww", op::sww(BUFFER_A, SCRATCH, UNITS)),
+        Case::fixed("xor", op::xor(RESULT, UNITS, UNITS)),
+        Case::fixed("xori", op::xori(RESULT, UNITS, 1)),
+        Case::dependent("aloc", bytes(MAX_CALIBRATION_SIZE), |_| op::aloc(UNITS)),
+        Case::dependent("cfe", bytes(MAX_CALIBRATION_SIZE), |_| op::cfe(UNITS)),
+        Case::dependent("cfei", bytes(MAX_CALIBRATION_SIZE), |units| {
+            op::cfei(imm24(units))
+        }),
+        Case::dependent("k256", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::k256(BUFFER_B, BUFFER_A, UNITS)
+        }),
+        Case::dependent("logd", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::logd(RegId::ZERO, RegId::ZERO, BUFFER_A, UNITS)
+        }),
+        Case::dependent("mcl", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::mcl(BUFFER_A, UNITS)
+        }),
+        Case::dependent("mcli", bytes(MAX_CALIBRATION_SIZE), |units| {
+            op::mcli(BUFFER_A, imm24(units))
+        }),
+        Case::dependent("mcp", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::mcp(BUFFER_B, BUFFER_A, UNITS)
+        }),
+        Case::dependent("mcpi", bytes(4095), |units| {
+            op::mcpi(BUFFER_B, BUFFER_A, imm12(units))
+        }),
+        Case::dependent("meq", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::meq(RESULT, BUFFER_A, BUFFER_B, UNITS)
+        }),
+        Case::dependent("s256", bytes(MAX_CALIBRATION_SIZE), |_| {
+            op::s256(BUFFER_B, BUFFER_A, UNITS)
+        }),
+        Case::dependent("scwq", Units::Slots, |_| op::scwq(BUFFER_A, RESULT, UNITS)),
+        Case::dependent("srwq", Units::Slots, |_| {
+            op::srwq(BUFFER_B, RESULT, BUFFER_A, UNITS)
+        }),
+        Case::dependent("swwq", Units::Slots, |_| {
+            op::swwq(BUFFER_A, RESULT, BUFFER_B, UNITS)
+        }),
+    ]
+}
+
+/// Measures the execution time of the instructions and derives the gas costs from it.
+///
+/// Every instruction is repeated inside a contract deployed to a clone of the
+/// configured storage, so the storage operations are measured against the storage
+/// backend used by the node. The time of the same program without the repeated
+/// instruction is subtracted, and the dependent costs are measured for each of the
+/// configured sizes. The time of `noop` defines the price of one gas.
+///
+/// The following costs aren't measured and are taken from the base gas costs passed to
+/// the [`CalibrationReport`], which lists them in [`CalibrationReport::uncalibrated`]:
+/// - the contract calls and the bytecode operations: `call`, `ccp`, `croo`, `csiz`,
+///   `ldc`, `retd`, `smo`, `contract_root`, `state_root` and `new_storage_per_byte`;
+/// - the cryptographic operations: `eck1`, `ecr1` and `ed19`;
+/// - the control flow and the stack operations: `ji`, `jmp`, `jmpb`, `ret`, `rvrt`,
+///   `poph`, `popl`, `pshh` and `pshl`;
+/// - the wide integer operations: `wdcm`, `wqcm`, `wdop`, `wqop`, `wdml`, `wqml`, `wddv`,
+///   `wqdv`, `wdmd`, `wqmd`, `wdam`, `wqam`, `wdmm` and `wqmm`;
+/// - `time`, `tr`, `tro` and `vm_initialization`.
+#[derive(Debug, Clone)]
+pub struct Calibrator<S> {
+    storage: S,
+    config: CalibrationConfig,
+}
+
+impl<S> Calibrator<S>
+where
+    S: InterpreterStorage + Clone,
+{
+    /// Create the calibrator measuring the instructions against the `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            config: CalibrationConfig::default(),
+        }
+    }
+
+    /// Use the `config` instead of the default one.
+    pub fn with_config(mut self, config: CalibrationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The parameters of the calibration.
+    pub const fn config(&self) -> &CalibrationConfig {
+        &self.config
+    }
+
+    /// Measures all cases and fits their costs.
+    pub fn run(&self) -> Result<CalibrationReport, CalibrationError<S::DataError>> {
+        if let Some(size) = self
+            .config
+            .sizes
+            .iter()
+            .find(|size| **size > MAX_CALIBRATION_SIZE)
+        {
+            return Err(CalibrationError::InvalidSize(*size))
+        }
+
+        let mut cases = cases();
+        if let Some(name) = self
+            .config
+            .cases
+            .iter()
+            .find(|name| !cases.iter().any(|case| case.name == name.as_str()))
+        {
+            return Err(CalibrationError::UnknownCase(name.clone()))
+        }
+        if !self.config.cases.is_empty() {
+            cases.retain(|case| {
+                case.name == "noop"
+                    || self
+                        .config
+                        .cases
+                        .iter()
+                        .any(|name| name.as_str() == case.name)
+            });
+        }
+
+        let mut measurements = Vec::with_capacity(cases.len());
+        for case in &cases {
+            let samples = match case.body {
+                Body::Fixed(instruction) => {
+                    let nanos = self.measure(case, FIXED_OPERAND, instruction)?;
+                    vec![Sample { units: 0, nanos }]
+                }
+                Body::Dependent(units, instruction) => {
+                    let mut samples = vec![];
+                    for units in self
+                        .config
+                        .sizes
+                        .iter()
+                        .filter_map(|size| units.of_size(*size))
+                    {
+                        let nanos = self.measure(case, units, instruction(units))?;
+                        samples.push(Sample { units, nanos });
+                    }
+                    samples
+                }
+            };
+            measurements.push((case, samples));
+        }
+
+        let ns_per_gas = measurements
+            .iter()
+            .find(|(case, _)| case.name == "noop")
+            .and_then(|(_, samples)| samples.first())
+            .map_or(0.0, |sample| sample.nanos)
+            .max(f64::MIN_POSITIVE);
+
+        let entries = measurements
+            .into_iter()
+            .map(|(case, samples)| {
+                let fit = LinearFit::from_samples(&samples);
+                let cost = match case.body {
+                    Body::Fixed(_) => {
+                        CalibratedCost::Fixed(fixed_cost(fit.base, ns_per_gas))
+                    }
+                    Body::Dependent(..) => {
+                        CalibratedCost::Dependent(dependent_cost(&fit, ns_per_gas))
+                    }
+                };
+                CalibrationEntry {
+                    name: case.name,
+                    samples,
+                    fit,
+                    cost,
+                }
+            })
+            .collect();
+
+        Ok(CalibrationReport {
+            ns_per_gas,
+            entries,
+        })
+    }
+
+    /// Time of one `instruction` in nanoseconds.
+    fn measure(
+        &self,
+        case: &Case,
+        units: Word,
+        instruction: Instruction,
+    ) -> Result<f64, CalibrationError<S::DataError>> {
+        let repetitions = self.config.repetitions.max(1);
+        let baseline = self.time(case, self.program(case, units, instruction, 0))?;
+        let measured =
+            self.time(case, self.program(case, units, instruction, repetitions))?;
+
+        let nanos = measured.saturating_sub(baseline).as_secs_f64() * 1e9;
+        Ok(nanos / repetitions as f64)
+    }
+
+    /// The code of the measured contract.
+    fn program(
+        &self,
+        case: &Case,
+        units: Word,
+        instruction: Instruction,
+        repetitions: usize,
+    ) -> Vec<Instruction> {
+        let units = u32::try_from(units).unwrap_or(u32::MAX);
+        let buffer = self
+            .config
+            .sizes
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(Bytes32::LEN as Word);
+        let buffer = u32::try_from(buffer).unwrap_or(u32::MAX);
+
+        let setup = [
+            op::movi(UNITS, units),
+            op::movi(SCRATCH, buffer),
+            op::aloc(SCRATCH),
+            op::move_(BUFFER_B, RegId::HP),
+            op::aloc(SCRATCH),
+            op::move_(BUFFER_A, RegId::HP),
+        ];
+
+        setup
+            .into_iter()
+            .chain(case.setup.iter().copied())
+            .chain((0..repetitions).map(|_| instruction))
+            .chain([op::ret(RegId::ONE)])
+            .collect()
+    }
+
+    /// The fastest execution of the `code` called as a contract.
+    fn time(
+        &self,
+        case: &Case,
+        code: Vec<Instruction>,
+    ) -> Result<Duration, CalibrationError<S::DataError>> {
+        let params = &self.config.consensus_params;
+
+        let contract = Contract::from(code.into_iter().collect::<Vec<u8>>());
+        let contract_id = contract.id(
+            &Salt::zeroed(),
+            &contract.root(),
+            &Contract::default_state_root(),
+        );
+        let mut storage = self.storage.clone();
+        storage
+            .deploy_contract_with_id(&[], &contract, &contract_id)
+            .map_err(CalibrationError::Storage)?;
+
+        let tx = self.transaction(Call::new(contract_id, 0, 0))?;
+
+        let mut fastest = Duration::MAX;
+        for _ in 0..self.config.iterations.max(1) {
+            let mut vm = Interpreter::<_, _, Script>::with_storage(
+                MemoryInstance::new(),
+                storage.clone(),
+                InterpreterParams::new(0, params),
+            );
+            let tx = tx.clone();
+
+            let start = Instant::now();
+            let result = vm.transact(tx).map_err(CalibrationError::Interpreter)?;
+            let elapsed = start.elapsed();
+
+            if let Some(receipt) = result.receipts().iter().find(|receipt| {
+                matches!(receipt, Receipt::Panic { .. } | Receipt::Revert { .. })
+            }) {
+                return Err(CalibrationError::Failed {
+                    case: case.name,
+                    receipt: Box::new(receipt.clone()),
+                })
+            }
+            fastest = fastest.min(elapsed);
+        }
+
+        Ok(fastest)
+    }
+
+    /// The script calling the measured contract.
+    fn transaction(
+        &self,
+        call: Call,
+    ) -> Result<Ready<Script>, CalibrationError<S::DataError>> {
+        let params = &self.config.consensus_params;
+        let contract_id = *call.to();
+
+        let script = [
+            op::gtf_args(BUFFER_A, RegId::ZERO, GTFArgs::ScriptData),
+            op::call(BUFFER_A, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ];
+        let secret = SecretKey::try_from(Bytes32::new(FEE_SECRET))
+            .expect("The fee secret key is valid");
+
+        let mut builder =
+            TransactionBuilder::script(script.into_iter().collect(), call.to_bytes());
+        builder
+            .with_params(params.clone())
+            .script_gas_limit(params.tx_params().max_gas_per_tx().saturating_div(2))
+            .add_input(Input::contract(
+                UtxoId::default(),
+                Bytes32::zeroed(),
+                Bytes32::zeroed(),
+                TxPointer::default(),
+                contract_id,
+            ))
+            .add_output(Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed()))
+            .add_unsigned_coin_input(
+                secret,
+                UtxoId::default(),
+                Word::MAX,
+                *params.base_asset_id(),
+                TxPointer::default(),
+            );
+        builder
+            .finalize()
+            .into_checked_basic(Default::default(), params)
+            .and_then(|tx| tx.into_ready(0, params.gas_costs(), params.fee_params()))
+            .map_err(CalibrationError::Check)
+    }
+}
+
+/// Time of the instruction measured for the number of units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// The number of units, `0` for the fixed costs.
+    pub units: Word,
+    /// Time of one instruction in nanoseconds.
+    pub nanos: f64,
+}
+
+/// Linear fit `base + per_unit * units` of the samples with the least squares.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    /// Time of the instruction without units in nanoseconds.
+    pub base: f64,
+    /// Time per unit in nanoseconds.
+    pub per_unit: f64,
+    /// The coefficient of determination, `1.0` if the line fits the samples
+    /// exactly.
+    pub r_squared: f64,
+}
+
+impl LinearFit {
+    /// Fits the samples. If all samples have the same units, the fit is the mean time
+    /// without the per-unit time.
+    pub fn from_samples(samples: &[Sample]) -> Self {
+        let count = samples.len().max(1) as f64;
+        let mean_units = samples
+            .iter()
+            .map(|sample| sample.units as f64)
+            .sum::<f64>()
+            / count;
+        let mean_nanos = samples.iter().map(|sample| sample.nanos).sum::<f64>() / count;
+
+        let (covariance, variance) =
+            samples
+                .iter()
+                .fold((0.0, 0.0), |(covariance, variance), sample| {
+                    let units = sample.units as f64 - mean_units;
+                    (
+                        covariance + units * (sample.nanos - mean_nanos),
+                        variance + units * units,
+                    )
+                });
+
+        let per_unit = if variance > 0.0 {
+            covariance / variance
+        } else {
+            0.0
+        };
+        let base = mean_nanos - per_unit * mean_units;
+
+        let (residual, total) =
+            samples
+                .iter()
+                .fold((0.0, 0.0), |(residual, total), sample| {
+                    let predicted = base + per_unit * sample.units as f64;
+                    (
+                        residual + (sample.nanos - predicted).powi(2),
+                        total + (sample.nanos - mean_nanos).powi(2),
+                    )
+                });
+        let r_squared = if total > 0.0 {
+            1.0 - residual / total
+        } else {
+            1.0
+        };
+
+        Self {
+            base,
+            per_unit,
+            r_squared,
+        }
+    }
+}
+
+/// Converts the non-negative gas amount to [`Word`], saturating.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_gas(gas: f64) -> Word {
+    gas.max(0.0) as Word
+}
+
+/// The fixed cost of the instruction taking `nanos`, at least one gas.
+pub fn fixed_cost(nanos: f64, ns_per_gas: f64) -> Word {
+    to_gas((nanos / ns_per_gas).round()).max(1)
+}
+
+/// The dependent cost of the instruction with the `fit` of its time.
+///
+/// The operation is light if a unit takes less time than one gas, and heavy otherwise.
+/// The base is at least one gas.
+pub fn dependent_cost(fit: &LinearFit, ns_per_gas: f64) -> DependentCost {
+    let base = to_gas((fit.base / ns_per_gas).ceil()).max(1);
+    let gas_per_unit = fit.per_unit / ns_per_gas;
+
+    if gas_per_unit >= 1.0 {
+        DependentCost::HeavyOperation {
+            base,
+            gas_per_unit: to_gas(gas_per_unit.round()),
+        }
+    } else if gas_per_unit > 0.0 {
+        DependentCost::LightOperation {
+            base,
+            units_per_gas: to_gas((1.0 / gas_per_unit).round()).max(1),
+        }
+    } else {
+        DependentCost::HeavyOperation {
+            base,
+            gas_per_unit: 0,
+        }
+    }
+}
+
+/// Calibrated cost of the instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibratedCost {
+    /// The cost of the instruction doesn't depend on its operands.
+    Fixed(Word),
+    /// The cost of the instruction depends on the number of units.
+    Dependent(DependentCost),
+}
+
+/// Measurements and the calibrated cost of one gas cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationEntry {
+    /// The name of the field of [`GasCostsValuesV3`].
+    pub name: &'static str,
+    /// The measured samples.
+    pub samples: Vec<Sample>,
+    /// The fit of the samples.
+    pub fit: LinearFit,
+    /// The cost derived from the fit.
+    pub cost: CalibratedCost,
+}
+
+/// Result of the [`Calibrator`].
+///
+/// The `Display` implementation explains every calibrated cost with its measurements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationReport {
+    /// Time of one gas in nanoseconds, i.e. the time of `noop`.
+    pub ns_per_gas: f64,
+    /// The calibrated costs.
+    pub entries: Vec<CalibrationEntry>,
+}
+
+enum Field<'a> {
+    Fixed(&'a mut Word),
+    Dependent(&'a mut DependentCost),
+}
+
+/// The fields of the gas costs with their names, in the order of the declaration.
+fn fields(costs: &mut GasCostsValuesV3) -> Vec<(&'static str, Field<'_>)> {
+    macro_rules! fields {
+        ($($field:ident: $kind:ident),* $(,)?) => {{
+            // Exhaustive, so the new fields have to be added here
+            let GasCostsValuesV3 { $($field),* } = costs;
+            vec![$((stringify!($field), Field::$kind($field))),*]
+        }};
+    }
+
+    fields!(
+        add: Fixed,
+        addi: Fixed,
+        and: Fixed,
+        andi: Fixed,
+        bal: Fixed,
+        bhei: Fixed,
+        bhsh: Fixed,
+        burn: Fixed,
+        cb: Fixed,
+        cfsi: Fixed,
+        div: Fixed,
+        divi: Fixed,
+        eck1: Fixed,
+        ecr1: Fixed,
+        ed19: Fixed,
+        eq: Fixed,
+        exp: Fixed,
+        expi: Fixed,
+        flag: Fixed,
+        gm: Fixed,
+        gt: Fixed,
+        gtf: Fixed,
+        ji: Fixed,
+        jmp: Fixed,
+        jne: Fixed,
+        jnei: Fixed,
+        jnzi: Fixed,
+        jmpf: Fixed,
+        jmpb: Fixed,
+        jnzf: Fixed,
+        jnzb: Fixed,
+        jnef: Fixed,
+        jneb: Fixed,
+        lb: Fixed,
+        log: Fixed,
+        lt: Fixed,
+        lw: Fixed,
+        mint: Fixed,
+        mlog: Fixed,
+        mod_op: Fixed,
+        modi: Fixed,
+        move_op: Fixed,
+        movi: Fixed,
+        mroo: Fixed,
+        mul: Fixed,
+        muli: Fixed,
+        mldv: Fixed,
+        noop: Fixed,
+        not: Fixed,
+        or: Fixed,
+        ori: Fixed,
+        poph: Fixed,
+        popl: Fixed,
+        pshh: Fixed,
+        pshl: Fixed,
+        ret: Fixed,
+        rvrt: Fixed,
+        sb: Fixed,
+        sll: Fixed,
+        slli: Fixed,
+        srl: Fixed,
+        srli: Fixed,
+        srw: Fixed,
+        sub: Fixed,
+        subi: Fixed,
+        sw: Fixed,
+        sww: Fixed,
+        time: Fixed,
+        tr: Fixed,
+        tro: Fixed,
+        wdcm: Fixed,
+        wqcm: Fixed,
+        wdop: Fixed,
+        wqop: Fixed,
+        wdml: Fixed,
+        wqml: Fixed,
+        wddv: Fixed,
+        wqdv: Fixed,
+        wdmd: Fixed,
+        wqmd: Fixed,
+        wdam: Fixed,
+        wqam: Fixed,
+        wdmm: Fixed,
+        wqmm: Fixed,
+        xor: Fixed,
+        xori: Fixed,
+        aloc: Dependent,
+        cfe: Dependent,
+        cfei: Dependent,
+        call: Dependent,
+        ccp: Dependent,
+        croo: Dependent,
+        csiz: Dependent,
+        k256: Dependent,
+        ldc: Dependent,
+        logd: Dependent,
+        mcl: Dependent,
+        mcli: Dependent,
+        mcp: Dependent,
+        mcpi: Dependent,
+        meq: Dependent,
+        retd: Dependent,
+        s256: Dependent,
+        scwq: Dependent,
+        smo: Dependent,
+        srwq: Dependent,
+        swwq: Dependent,
+        contract_root: Dependent,
+        state_root: Dependent,
+        new_storage_per_byte: Fixed,
+        vm_initialization: Dependent,
+    )
+}
+
+impl CalibrationReport {
+    /// The names of the gas costs without the calibrated cost, which are taken from the
+    /// base gas costs. See [`Calibrator`] for the costs that are never measured.
+    pub fn uncalibrated(&self) -> Vec<&'static str> {
+        fields(&mut GasCostsValuesV3::free())
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !self.entries.iter().any(|entry| entry.name == *name))
+            .collect()
+    }
+
+    /// The `base` gas costs with the calibrated costs.
+    pub fn gas_costs(&self, base: &GasCostsValuesV3) -> GasCostsValuesV3 {
+        let mut costs = base.clone();
+        for (name, field) in fields(&mut costs) {
+            let Some(entry) = self.entries.iter().find(|entry| entry.name == name) else {
+                continue
+            };
+            match (field, entry.cost) {
+                (Field::Fixed(field), CalibratedCost::Fixed(cost)) => *field = cost,
+                (Field::Dependent(field), CalibratedCost::Dependent(cost)) => {
+                    *field = cost
+                }
+                _ => {}
+            }
+        }
+        costs
+    }
+
+    /// The calibrated gas costs as the Rust source in the format of the
+    /// `default_gas_costs.rs` of `fuel-tx`.
+    pub fn to_rust_source(&self, base: &GasCostsValuesV3) -> String {
+        use core::fmt::Write;
+
+        let uncalibrated = self.uncalibrated();
+        let mut costs = self.gas_costs(base);
+        let mut source = String::new();
+        // Writing to a `String` is infallible
+        let _ = writeln!(source, "use super::*;");
+        let _ = writeln!(
+            source,
+            "/// File generated by the gas cost calibration of fuel-vm, with 1 gas = {:.3} ns",
+            self.ns_per_gas
+        );
+        let _ = writeln!(source, "pub fn default_gas_costs() -> GasCostsValues {{");
+        let _ = writeln!(source, "    GasCostsValuesV3 {{");
+        for (name, field) in fields(&mut costs) {
+            match name {
+                "aloc" => {
+                    let _ = writeln!(source, "\n        // Dependent");
+                }
+                "contract_root" => {
+                    let _ = writeln!(source, "\n        // Non-opcode costs");
+                }
+                _ => {}
+            }
+            if uncalibrated.contains(&name) {
+                let _ = writeln!(source, "        // Not calibrated");
+            }
+            match field {
+                Field::Fixed(cost) => {
+                    let _ = writeln!(source, "        {name}: {cost},");
+                }
+                Field::Dependent(DependentCost::LightOperation {
+                    base,
+                    units_per_gas,
+                }) => {
+                    let _ = writeln!(
+                        source,
+                        "        {name}: DependentCost::LightOperation {{\n            \
+                         base: {base},\n            units_per_gas: {units_per_gas},\n        \
+                         }},"
+                    );
+                }
+                Field::Dependent(DependentCost::HeavyOperation {
+                    base,
+                    gas_per_unit,
+                }) => {
+                    let _ = writeln!(
+                        source,
+                        "        {name}: DependentCost::HeavyOperation {{\n            \
+                         base: {base},\n            gas_per_unit: {gas_per_unit},\n        \
+                         }},"
+                    );
+                }
+            }
+        }
+        let _ = writeln!(source, "    }}");
+        let _ = writeln!(source, "    .into()");
+        let _ = writeln!(source, "}}");
+        source
+    }
+
+    /// The calibrated gas costs as the JSON of [`GasCostsValues`].
+    pub fn to_json(&self, base: &GasCostsValuesV3) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&GasCostsValues::V3(self.gas_costs(base)))
+    }
+}
+
+impl fmt::Display for CalibrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "1 gas = {:.3} ns (the time of `noop`)", self.ns_per_gas)?;
+
+        for entry in &self.entries {
+            match entry.cost {
+                CalibratedCost::Fixed(cost) => {
+                    writeln!(
+                        f,
+                        "{:<8} {:>12.3} ns => {cost}",
+                        entry.name, entry.fit.base
+                    )?;
+                }
+                CalibratedCost::Dependent(cost) => {
+                    writeln!(
+                        f,
+                        "{:<8} {:>12.3} ns + {:.5} ns/unit (r² = {:.3}) => {cost:?}",
+                        entry.name,
+                        entry.fit.base,
+                        entry.fit.per_unit,
+                        entry.fit.r_squared
+                    )?;
+                    for sample in &entry.samples {
+                        writeln!(
+                            f,
+                            "{:<8} {:>12.3} ns for {} units",
+                            "", sample.nanos, sample.units
+                        )?;
+                    }
+                }
+            }
+        }
+
+        let uncalibrated = self.uncalibrated();
+        if !uncalibrated.is_empty() {
+            writeln!(
+                f,
+                "Not calibrated, taken from the base costs: {}",
+                uncalibrated.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn samples(samples: &[(Word, f64)]) -> Vec<Sample> {
+        samples
+            .iter()
+            .map(|(units, nanos)| Sample {
+                units: *units,
+                nanos: *nanos,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn linear_fit__recovers_exact_line() {
+        let fit = LinearFit::from_samples(&samples(&[(0, 10.0), (10, 15.0), (20, 20.0)]));
+
+        assert!((fit.base - 10.0).abs() < 1e-9);
+        assert!((fit.per_unit - 0.5).abs() < 1e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn linear_fit__single_units_uses_mean() {
+        let fit = LinearFit::from_samples(&samples(&[(0, 4.0), (0, 6.0)]));
+
+        assert_eq!(fit.base, 5.0);
+        assert_eq!(fit.per_unit, 0.0);
+    }
+
+    #[test]
+    fn fixed_cost__is_at_least_one() {
+        assert_eq!(fixed_cost(0.0, 2.0), 1);
+        assert_eq!(fixed_cost(9.0, 2.0), 5);
+    }
+
+    #[test]
+    fn dependent_cost__selects_light_or_heavy_operation() {
+        let fit = |base, per_unit| LinearFit {
+            base,
+            per_unit,
+            r_squared: 1.0,
+        };
+
+        assert_eq!(
+            dependent_cost(&fit(10.0, 0.01), 2.0),
+            DependentCost::LightOperation {
+                base: 5,
+                units_per_gas: 200,
+            }
+        );
+        assert_eq!(
+            dependent_cost(&fit(3.0, 6.0), 2.0),
+            DependentCost::HeavyOperation {
+                base: 2,
+                gas_per_unit: 3,
+            }
+        );
+        assert_eq!(
+            dependent_cost(&fit(-1.0, -0.5), 2.0),
+            DependentCost::HeavyOperation {
+                base: 1,
+                gas_per_unit: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn run__calibrates_selected_cases() {
+        let config = CalibrationConfig {
+            iterations: 1,
+            repetitions: 2,
+            sizes: vec![32, 64],
+            cases: vec!["mcpi".to_owned()],
+            ..Default::default()
+        };
+        let report = Calibrator::new(MemoryStorage::default())
+            .with_config(config)
+            .run()
+            .expect("The calibration should succeed");
+
+        let names: Vec<_> = report.entries.iter().map(|entry| entry.name).collect();
+        assert_eq!(names, vec!["noop", "mcpi"]);
+        assert_eq!(report.entries[1].samples.len(), 2);
+
+        let uncalibrated = report.uncalibrated();
+        assert!(uncalibrated.contains(&"call"));
+        assert!(uncalibrated.contains(&"add"));
+        assert!(!uncalibrated.contains(&"mcpi"));
+        assert!(report.to_string().contains("Not calibrated"));
+
+        let base = GasCostsValuesV3::unit();
+        let costs = report.gas_costs(&base);
+        assert_eq!(costs.noop, 1);
+        assert_eq!(costs.eck1, base.eck1);
+
+        let source = report.to_rust_source(&base);
+        assert!(source.contains("pub fn default_gas_costs() -> GasCostsValues {"));
+        assert!(source.contains("        mcpi: DependentCost::"));
+        assert!(
+            source.contains("        // Not calibrated\n        call: DependentCost::")
+        );
+
+        let json = report.to_json(&base).expect("The costs are serializable");
+        let decoded: GasCostsValues =
+            serde_json::from_str(&json).expect("The JSON is valid");
+        assert_eq!(decoded, GasCostsValues::V3(costs));
+    }
+
+    #[test]
+    fn run__rejects_unknown_cases() {
+        let config = CalibrationConfig {
+            cases: vec!["call".to_owned()],
+            ..Default::default()
+        };
+        let result = Calibrator::new(MemoryStorage::default())
+            .with_config(config)
+            .run();
+
+        assert!(matches!(
+            result,
+            Err(CalibrationError::UnknownCase(name)) if name == "call"
+        ));
+    }
+
+    #[test]
+    fn run__rejects_too_large_sizes() {
+        let config = CalibrationConfig {
+            sizes: vec![MAX_CALIBRATION_SIZE + 1],
+            ..Default::default()
+        };
+        let result = Calibrator::new(MemoryStorage::default())
+            .with_config(config)
+            .run();
+
+        assert!(matches!(
+            result,
+            Err(CalibrationError::InvalidSize(size)) if size == MAX_CALIBRATION_SIZE + 1
+        ));
+    }
+}
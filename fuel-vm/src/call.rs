@@ -155,7 +155,6 @@ impl CallFrame {
         &self.to
     }
 
-    #[cfg(feature = "test-helpers")]
     /// Contract code length in bytes.
     pub fn code_size_padded(&self) -> usize {
         self.code_size_padded
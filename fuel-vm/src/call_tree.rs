@@ -0,0 +1,458 @@
+//! Reconstruction of the nested call tree from the flat list of receipts.
+//!
+//! The VM emits the receipts of a script execution in a single flat list. The
+//! nesting of the contract calls is implicit: every `Call` receipt opens a new
+//! frame, and the following `Return` or `ReturnData` receipt closes it. A
+//! `Revert` or `Panic` aborts the whole transaction from the innermost frame.
+//!
+//! [`CallTree::from_receipts`] rebuilds that nesting, attributing every log,
+//! transfer, mint, burn and outgoing message to the frame that emitted it.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use fuel_asm::PanicInstruction;
+use fuel_tx::{
+    PanicContext,
+    Receipt,
+    ScriptExecutionResult,
+};
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Parameters of a contract call, taken from the `Call` receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallParams {
+    /// Amount of coins forwarded to the callee.
+    pub amount: Word,
+    /// Asset of the forwarded coins.
+    pub asset_id: AssetId,
+    /// Gas forwarded to the callee.
+    pub gas: Word,
+    /// First call argument.
+    pub param1: Word,
+    /// Second call argument.
+    pub param2: Word,
+}
+
+/// The way a call frame was terminated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallOutcome {
+    /// The frame was not terminated by one of its own receipts.
+    ///
+    /// This is the case for the callers of a frame that reverted or panicked,
+    /// since the failure aborts the whole transaction, or when the list of
+    /// receipts is truncated.
+    Unfinished,
+    /// The frame returned a [`Word`] with `RET`.
+    Return(Word),
+    /// The frame returned data with `RETD`.
+    ReturnData {
+        /// Digest of the returned data.
+        digest: Bytes32,
+        /// The returned data, if it was kept in the receipt.
+        data: Option<Vec<u8>>,
+    },
+    /// The frame executed `RVRT`.
+    Revert(Word),
+    /// The frame panicked.
+    Panic {
+        /// The reason of the panic and the instruction that caused it.
+        reason: PanicInstruction,
+        /// Additional information about the panic.
+        context: PanicContext,
+    },
+}
+
+impl CallOutcome {
+    /// Returns `true` if the frame reverted or panicked.
+    pub const fn is_failure(&self) -> bool {
+        matches!(self, Self::Revert(_) | Self::Panic { .. })
+    }
+}
+
+/// An event that happened inside of a call frame, in the order of emission.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallEvent {
+    /// A nested contract call.
+    Call(CallNode),
+    /// A receipt emitted by the frame which doesn't affect the nesting, like
+    /// `Log`, `LogData`, `Transfer`, `TransferOut`, `Mint`, `Burn` or
+    /// `MessageOut`.
+    Receipt(Receipt),
+}
+
+/// A single frame of the call tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallNode {
+    caller: Option<ContractId>,
+    contract: Option<ContractId>,
+    params: Option<CallParams>,
+    events: Vec<CallEvent>,
+    outcome: CallOutcome,
+}
+
+impl CallNode {
+    fn script() -> Self {
+        Self {
+            caller: None,
+            contract: None,
+            params: None,
+            events: Vec::new(),
+            outcome: CallOutcome::Unfinished,
+        }
+    }
+
+    /// The contract that made the call, or `None` if called from the script.
+    pub const fn caller(&self) -> Option<&ContractId> {
+        self.caller.as_ref()
+    }
+
+    /// The called contract, or `None` for the root script frame.
+    pub const fn contract(&self) -> Option<&ContractId> {
+        self.contract.as_ref()
+    }
+
+    /// Forwarded assets, gas and arguments, or `None` for the root script frame.
+    pub const fn params(&self) -> Option<&CallParams> {
+        self.params.as_ref()
+    }
+
+    /// Events of the frame in the order of emission.
+    pub fn events(&self) -> &[CallEvent] {
+        self.events.as_slice()
+    }
+
+    /// Nested calls made by this frame.
+    pub fn calls(&self) -> impl Iterator<Item = &CallNode> {
+        self.events.iter().filter_map(|event| match event {
+            CallEvent::Call(node) => Some(node),
+            CallEvent::Receipt(_) => None,
+        })
+    }
+
+    /// The way the frame was terminated.
+    pub const fn outcome(&self) -> &CallOutcome {
+        &self.outcome
+    }
+
+    /// The depth of the deepest call made from this frame, `0` if it made none.
+    pub fn depth(&self) -> usize {
+        self.calls()
+            .map(|node| node.depth().saturating_add(1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn current_mut(&mut self, depth: usize) -> &mut CallNode {
+        let mut node = self;
+        for _ in 0..depth {
+            node = match node.events.last_mut() {
+                Some(CallEvent::Call(child)) => child,
+                _ => unreachable!("Every open frame is the last event of its parent"),
+            };
+        }
+        node
+    }
+}
+
+/// Errors that can occur while reconstructing a [`CallTree`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum CallTreeError {
+    /// The receipt was emitted by a contract other than the one of the current
+    /// frame.
+    #[display(
+        fmt = "Receipt {index} was emitted by {found:?} while the current frame is {expected:?}"
+    )]
+    UnexpectedContext {
+        /// Index of the receipt.
+        index: usize,
+        /// The contract of the current frame, `None` for the script.
+        expected: Option<ContractId>,
+        /// The contract of the receipt, `None` for the script.
+        found: Option<ContractId>,
+    },
+    /// The receipt follows the termination of the script.
+    #[display(fmt = "Receipt {index} follows the termination of the script")]
+    ReceiptAfterTermination {
+        /// Index of the receipt.
+        index: usize,
+    },
+}
+
+/// Nested call tree of a script execution.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallTree {
+    root: CallNode,
+    result: Option<ScriptExecutionResult>,
+    gas_used: Option<Word>,
+}
+
+impl CallTree {
+    /// Rebuild the call tree out of the receipts of a script execution.
+    pub fn from_receipts(receipts: &[Receipt]) -> Result<Self, CallTreeError> {
+        let mut root = CallNode::script();
+        let mut result = None;
+        let mut gas_used = None;
+        // Number of frames opened above the root script frame.
+        let mut depth = 0usize;
+        let mut terminated = false;
+
+        for (index, receipt) in receipts.iter().enumerate() {
+            if let Receipt::ScriptResult {
+                result: r,
+                gas_used: g,
+            } = receipt
+            {
+                if result.is_some() {
+                    return Err(CallTreeError::ReceiptAfterTermination { index })
+                }
+                result = Some(*r);
+                gas_used = Some(*g);
+                terminated = true;
+                continue
+            }
+
+            if terminated {
+                return Err(CallTreeError::ReceiptAfterTermination { index })
+            }
+
+            let current = root.current_mut(depth);
+
+            if let Some(found) = emitter(receipt) {
+                if found != current.contract {
+                    return Err(CallTreeError::UnexpectedContext {
+                        index,
+                        expected: current.contract,
+                        found,
+                    })
+                }
+            }
+
+            match receipt {
+                Receipt::Call {
+                    to,
+                    amount,
+                    asset_id,
+                    gas,
+                    param1,
+                    param2,
+                    ..
+                } => {
+                    current.events.push(CallEvent::Call(CallNode {
+                        caller: current.contract,
+                        contract: Some(*to),
+                        params: Some(CallParams {
+                            amount: *amount,
+                            asset_id: *asset_id,
+                            gas: *gas,
+                            param1: *param1,
+                            param2: *param2,
+                        }),
+                        events: Vec::new(),
+                        outcome: CallOutcome::Unfinished,
+                    }));
+                    depth = depth.saturating_add(1);
+                }
+                Receipt::Return { val, .. } => {
+                    current.outcome = CallOutcome::Return(*val);
+                    terminated = close_frame(&mut depth);
+                }
+                Receipt::ReturnData { digest, data, .. } => {
+                    current.outcome = CallOutcome::ReturnData {
+                        digest: *digest,
+                        data: data.clone(),
+                    };
+                    terminated = close_frame(&mut depth);
+                }
+                Receipt::Revert { ra, .. } => {
+                    current.outcome = CallOutcome::Revert(*ra);
+                    terminated = true;
+                }
+                Receipt::Panic {
+                    reason, context, ..
+                } => {
+                    current.outcome = CallOutcome::Panic {
+                        reason: *reason,
+                        context: context.clone(),
+                    };
+                    terminated = true;
+                }
+                _ => current.events.push(CallEvent::Receipt(receipt.clone())),
+            }
+        }
+
+        Ok(Self {
+            root,
+            result,
+            gas_used,
+        })
+    }
+
+    /// The root frame of the script.
+    pub const fn root(&self) -> &CallNode {
+        &self.root
+    }
+
+    /// The result of the script, if the receipts contain `ScriptResult`.
+    pub const fn result(&self) -> Option<&ScriptExecutionResult> {
+        self.result.as_ref()
+    }
+
+    /// The gas used by the script, if the receipts contain `ScriptResult`.
+    pub const fn gas_used(&self) -> Option<Word> {
+        self.gas_used
+    }
+
+    /// Convert this instance into its root frame.
+    pub fn into_root(self) -> CallNode {
+        self.root
+    }
+}
+
+/// Closes the current frame, returning `true` if it was the root script frame.
+fn close_frame(depth: &mut usize) -> bool {
+    match depth.checked_sub(1) {
+        Some(parent) => {
+            *depth = parent;
+            false
+        }
+        None => true,
+    }
+}
+
+/// The frame that emitted the receipt: `Some(None)` for the script,
+/// `Some(Some(id))` for a contract and `None` if the receipt doesn't say.
+fn emitter(receipt: &Receipt) -> Option<Option<ContractId>> {
+    match receipt {
+        Receipt::MessageOut { .. } | Receipt::ScriptResult { .. } => None,
+        _ => Some(receipt.id().copied()),
+    }
+}
+
+impl fmt::Display for CallTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_node(&self.root, 0, f)?;
+
+        if let (Some(result), Some(gas_used)) = (self.result, self.gas_used) {
+            writeln!(f, "result: {result:?}, gas used: {gas_used}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CallNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_node(self, 0, f)
+    }
+}
+
+fn fmt_node(node: &CallNode, indent: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let pad = indent.saturating_mul(2);
+
+    match (&node.contract, &node.params) {
+        (Some(contract), Some(params)) => writeln!(
+            f,
+            "{:pad$}call {:#x} (amount: {}, asset: {:#x}, gas: {}, param1: {}, param2: {})",
+            "",
+            contract,
+            params.amount,
+            params.asset_id,
+            params.gas,
+            params.param1,
+            params.param2,
+        )?,
+        _ => writeln!(f, "{:pad$}script", "")?,
+    }
+
+    let inner = indent.saturating_add(1);
+    let inner_pad = inner.saturating_mul(2);
+
+    for event in &node.events {
+        match event {
+            CallEvent::Call(child) => fmt_node(child, inner, f)?,
+            CallEvent::Receipt(receipt) => {
+                write!(f, "{:inner_pad$}", "")?;
+                fmt_receipt(receipt, f)?;
+                writeln!(f)?;
+            }
+        }
+    }
+
+    write!(f, "{:inner_pad$}", "")?;
+    match &node.outcome {
+        CallOutcome::Unfinished => writeln!(f, "unfinished"),
+        CallOutcome::Return(val) => writeln!(f, "return {val}"),
+        CallOutcome::ReturnData { digest, .. } => {
+            writeln!(f, "return data {digest:#x}")
+        }
+        CallOutcome::Revert(ra) => writeln!(f, "revert {ra}"),
+        CallOutcome::Panic { reason, context } => {
+            write!(f, "panic {}", reason.reason())?;
+            match context {
+                PanicContext::None => {}
+                PanicContext::ContractId(contract_id) => {
+                    write!(f, " (contract: {contract_id:#x})")?
+                }
+                PanicContext::AssetId(asset_id) => write!(f, " (asset: {asset_id:#x})")?,
+                PanicContext::MemoryRange { start, len } => {
+                    write!(f, " (memory: {start:#x}+{len})")?
+                }
+            }
+            writeln!(f)
+        }
+    }
+}
+
+fn fmt_receipt(receipt: &Receipt, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match receipt {
+        Receipt::Log { ra, rb, rc, rd, .. } => {
+            write!(f, "log {ra} {rb} {rc} {rd}")
+        }
+        Receipt::LogData {
+            ra,
+            rb,
+            len,
+            digest,
+            ..
+        } => write!(f, "log data {ra} {rb} (len: {len}, digest: {digest:#x})"),
+        Receipt::Transfer {
+            to,
+            amount,
+            asset_id,
+            ..
+        } => write!(f, "transfer {amount} of {asset_id:#x} to contract {to:#x}"),
+        Receipt::TransferOut {
+            to,
+            amount,
+            asset_id,
+            ..
+        } => write!(f, "transfer {amount} of {asset_id:#x} to address {to:#x}"),
+        Receipt::Mint { sub_id, val, .. } => {
+            write!(f, "mint {val} of sub id {sub_id:#x}")
+        }
+        Receipt::Burn { sub_id, val, .. } => {
+            write!(f, "burn {val} of sub id {sub_id:#x}")
+        }
+        Receipt::MessageOut {
+            recipient,
+            amount,
+            nonce,
+            ..
+        } => write!(f, "message {amount} to {recipient:#x} (nonce: {nonce:#x})"),
+        receipt => write!(f, "{receipt:?}"),
+    }
+}
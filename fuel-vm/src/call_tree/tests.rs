@@ -0,0 +1,218 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    PanicReason,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use super::*;
+use crate::{
+    call::Call,
+    prelude::TestBuilder,
+};
+
+const A: ContractId = ContractId::new([0xaa; 32]);
+const B: ContractId = ContractId::new([0xbb; 32]);
+
+fn call(id: ContractId, to: ContractId) -> Receipt {
+    Receipt::call(id, to, 10, AssetId::zeroed(), 1_000, 1, 2, 0, 0)
+}
+
+fn log(id: ContractId, ra: Word) -> Receipt {
+    Receipt::log(id, ra, 0, 0, 0, 0, 0)
+}
+
+#[test]
+fn from_receipts__nested_calls_are_reconstructed() {
+    let receipts = vec![
+        log(ContractId::zeroed(), 1),
+        call(ContractId::zeroed(), A),
+        log(A, 2),
+        call(A, B),
+        Receipt::transfer(B, A, 5, AssetId::zeroed(), 0, 0),
+        Receipt::ret(B, 3, 0, 0),
+        Receipt::ret(A, 4, 0, 0),
+        log(ContractId::zeroed(), 5),
+        Receipt::ret(ContractId::zeroed(), 6, 0, 0),
+        Receipt::script_result(ScriptExecutionResult::Success, 123),
+    ];
+
+    let tree = CallTree::from_receipts(&receipts).expect("Valid receipts");
+
+    assert_eq!(tree.result(), Some(&ScriptExecutionResult::Success));
+    assert_eq!(tree.gas_used(), Some(123));
+
+    let root = tree.root();
+    assert_eq!(root.contract(), None);
+    assert_eq!(root.outcome(), &CallOutcome::Return(6));
+    assert_eq!(root.events().len(), 3);
+    assert_eq!(root.depth(), 2);
+
+    let a = root.calls().next().expect("Call to A");
+    assert_eq!(a.caller(), None);
+    assert_eq!(a.contract(), Some(&A));
+    assert_eq!(a.params().map(|p| p.gas), Some(1_000));
+    assert_eq!(a.outcome(), &CallOutcome::Return(4));
+    assert_eq!(a.events()[0], CallEvent::Receipt(log(A, 2)));
+
+    let b = a.calls().next().expect("Call to B");
+    assert_eq!(b.caller(), Some(&A));
+    assert_eq!(b.contract(), Some(&B));
+    assert_eq!(b.outcome(), &CallOutcome::Return(3));
+    assert!(matches!(
+        b.events(),
+        [CallEvent::Receipt(Receipt::Transfer { amount: 5, .. })]
+    ));
+}
+
+#[test]
+fn from_receipts__panic_leaves_callers_unfinished() {
+    let reason = PanicInstruction::error(PanicReason::ContractNotInInputs, 0);
+    let receipts = vec![
+        call(ContractId::zeroed(), A),
+        Receipt::panic(A, reason, 0, 0).with_panic_context(PanicContext::ContractId(B)),
+        Receipt::script_result(ScriptExecutionResult::Panic, 10),
+    ];
+
+    let tree = CallTree::from_receipts(&receipts).expect("Valid receipts");
+
+    let root = tree.root();
+    assert_eq!(root.outcome(), &CallOutcome::Unfinished);
+    let a = root.calls().next().expect("Call to A");
+    assert_eq!(
+        a.outcome(),
+        &CallOutcome::Panic {
+            reason,
+            context: PanicContext::ContractId(B),
+        }
+    );
+    assert!(a.outcome().is_failure());
+}
+
+#[test]
+fn from_receipts__receipt_from_other_contract_is_rejected() {
+    let receipts = vec![call(ContractId::zeroed(), A), log(B, 0)];
+
+    let err = CallTree::from_receipts(&receipts).expect_err("Invalid receipts");
+
+    assert_eq!(
+        err,
+        CallTreeError::UnexpectedContext {
+            index: 1,
+            expected: Some(A),
+            found: Some(B),
+        }
+    );
+}
+
+#[test]
+fn from_receipts__receipt_after_termination_is_rejected() {
+    let receipts = vec![
+        Receipt::revert(ContractId::zeroed(), 0, 0, 0),
+        log(ContractId::zeroed(), 0),
+    ];
+
+    let err = CallTree::from_receipts(&receipts).expect_err("Invalid receipts");
+
+    assert_eq!(err, CallTreeError::ReceiptAfterTermination { index: 1 });
+}
+
+#[test]
+fn display__renders_indented_frames() {
+    let receipts = vec![
+        call(ContractId::zeroed(), A),
+        log(A, 7),
+        Receipt::revert(A, 1, 0, 0),
+        Receipt::script_result(ScriptExecutionResult::Revert, 10),
+    ];
+    let tree = CallTree::from_receipts(&receipts).expect("Valid receipts");
+
+    let expected = [
+        "script".to_string(),
+        format!(
+            "  call {A:#x} (amount: 10, asset: {:#x}, gas: 1000, param1: 1, param2: 2)",
+            AssetId::zeroed()
+        ),
+        "    log 7 0 0 0".to_string(),
+        "    revert 1".to_string(),
+        "  unfinished".to_string(),
+        "result: Revert, gas used: 10".to_string(),
+    ];
+    assert_eq!(tree.to_string(), expected.join("\n") + "\n");
+}
+
+#[test]
+fn serde__json_round_trip() {
+    let receipts = vec![
+        call(ContractId::zeroed(), A),
+        Receipt::return_data(A, 0, 0, 0, vec![1, 2, 3]),
+        Receipt::ret(ContractId::zeroed(), 1, 0, 0),
+        Receipt::script_result(ScriptExecutionResult::Success, 10),
+    ];
+    let tree = CallTree::from_receipts(&receipts).expect("Valid receipts");
+
+    let json = serde_json::to_string(&tree).expect("Serializable");
+    let decoded: CallTree = serde_json::from_str(&json).expect("Deserializable");
+
+    assert_eq!(decoded, tree);
+}
+
+#[test]
+fn from_receipts__matches_vm_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let gas_limit = 1_000_000;
+
+    let callee = test_context
+        .setup_contract(
+            vec![
+                op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data: Vec<u8> = Call::new(callee, 0, 0).to_bytes();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(gas_limit)
+        .contract_input(callee)
+        .fee_input()
+        .contract_output(&callee)
+        .build();
+
+    let state = test_context.execute_tx(tx).expect("Should execute tx");
+    let tree = CallTree::from_receipts(state.receipts()).expect("Valid receipts");
+
+    assert_eq!(tree.result(), Some(&ScriptExecutionResult::Success));
+    assert_eq!(tree.root().outcome(), &CallOutcome::Return(1));
+
+    let calls: Vec<_> = tree.root().calls().collect();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].contract(), Some(&callee));
+    assert_eq!(calls[0].outcome(), &CallOutcome::Return(1));
+    assert!(matches!(
+        calls[0].events(),
+        [CallEvent::Receipt(Receipt::Log { ra: 1, .. })]
+    ));
+}
@@ -13,6 +13,7 @@ use fuel_tx::{
     Script,
     Transaction,
     ValidityError,
+    VmParameters,
 };
 use fuel_types::{
     BlockHeight,
@@ -94,7 +95,11 @@ pub struct Checked<Tx: IntoChecked> {
 }
 
 impl<Tx: IntoChecked> Checked<Tx> {
-    fn new(transaction: Tx, metadata: Tx::Metadata, checks_bitmask: Checks) -> Self {
+    pub(crate) fn new(
+        transaction: Tx,
+        metadata: Tx::Metadata,
+        checks_bitmask: Checks,
+    ) -> Self {
         Checked {
             transaction,
             metadata,
@@ -356,6 +361,8 @@ pub struct CheckPredicateParams {
     pub fee_params: FeeParameters,
     /// Base Asset ID
     pub base_asset_id: AssetId,
+    /// VM parameters
+    pub vm_params: VmParameters,
 }
 
 #[cfg(feature = "test-helpers")]
@@ -384,6 +391,7 @@ impl From<&ConsensusParameters> for CheckPredicateParams {
             tx_offset: value.tx_params().tx_offset(),
             fee_params: *(value.fee_params()),
             base_asset_id: *value.base_asset_id(),
+            vm_params: *value.vm_params(),
         }
     }
 }
@@ -882,6 +890,7 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
         )
     }
 
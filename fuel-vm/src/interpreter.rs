@@ -6,7 +6,11 @@ use crate::{
     constraints::reg_key::*,
     consts::*,
     context::Context,
-    error::SimpleResult,
+    error::{
+        IoResult,
+        RuntimeError,
+        SimpleResult,
+    },
     state::Debugger,
 };
 use alloc::vec::Vec;
@@ -27,6 +31,7 @@ use fuel_tx::{
     FeeParameters,
     GasCosts,
     Output,
+    PanicContext,
     PrepareSign,
     Receipt,
     Script,
@@ -36,6 +41,7 @@ use fuel_tx::{
     Upgrade,
     Upload,
     ValidityError,
+    VmParameters,
 };
 use fuel_types::{
     AssetId,
@@ -55,13 +61,16 @@ pub mod diff;
 mod executors;
 mod flow;
 mod gas;
+mod hook;
 mod initialization;
+mod instruction_cache;
 mod internal;
 mod log;
 mod memory;
 mod metadata;
 mod post_execution;
 mod receipts;
+mod statistics;
 
 mod debug;
 mod ecal;
@@ -72,15 +81,44 @@ use crate::profiler::Profiler;
 use crate::profiler::InstructionLocation;
 
 pub use balances::RuntimeBalances;
+#[cfg(all(feature = "debug-ecal", feature = "std"))]
+pub use ecal::StderrSink;
+#[cfg(feature = "debug-ecal")]
 pub use ecal::{
+    DebugEcal,
+    DebugMessage,
+    DebugOp,
+    DebugSink,
+    DebugValue,
+};
+pub use ecal::{
+    EcalArg,
+    EcalArgs,
     EcalHandler,
+    EcalRegistry,
+    EcalVm,
+    HostError,
     PredicateErrorEcal,
+    ECAL_ARGS_REGISTERS,
 };
+pub use hook::{
+    ExecutionHook,
+    NoopHook,
+    StorageAccess,
+    StorageAccessKind,
+};
+pub use instruction_cache::InstructionCache;
 pub use memory::{
     Memory,
     MemoryInstance,
     MemoryRange,
 };
+#[cfg(feature = "std")]
+pub use memory::{
+    MemoryDiagnostic,
+    MemoryDiagnosticKind,
+};
+pub use statistics::ExecutionStatistics;
 
 use crate::checked_transaction::{
     CreateCheckedMetadata,
@@ -112,7 +150,7 @@ pub struct NotSupportedEcal;
 /// These can be obtained with the help of a [`crate::transactor::Transactor`]
 /// or a client implementation.
 #[derive(Debug, Clone)]
-pub struct Interpreter<M, S, Tx = (), Ecal = NotSupportedEcal> {
+pub struct Interpreter<M, S, Tx = (), Ecal = NotSupportedEcal, Hook = NoopHook> {
     registers: [Word; VM_REGISTER_COUNT],
     memory: M,
     frames: Vec<CallFrame>,
@@ -131,6 +169,8 @@ pub struct Interpreter<M, S, Tx = (), Ecal = NotSupportedEcal> {
     /// `append_panic_receipt` and is `PanicContext::None` after consumption.
     panic_context: PanicContext,
     ecal_state: Ecal,
+    hook: Hook,
+    instruction_cache: Option<InstructionCache>,
 }
 
 /// Interpreter parameters
@@ -154,6 +194,8 @@ pub struct InterpreterParams {
     pub fee_params: FeeParameters,
     /// Base Asset ID
     pub base_asset_id: AssetId,
+    /// VM parameters
+    pub vm_params: VmParameters,
 }
 
 #[cfg(feature = "test-helpers")]
@@ -170,6 +212,7 @@ impl Default for InterpreterParams {
             chain_id: ChainId::default(),
             fee_params: FeeParameters::default(),
             base_asset_id: Default::default(),
+            vm_params: VmParameters::DEFAULT,
         }
     }
 }
@@ -188,37 +231,32 @@ impl InterpreterParams {
             chain_id: params.chain_id,
             fee_params: params.fee_params,
             base_asset_id: params.base_asset_id,
+            vm_params: params.vm_params,
         }
     }
 }
 
-/// Sometimes it is possible to add some additional context information
-/// regarding panic reasons to simplify debugging.
-// TODO: Move this enum into `fuel-tx` and use it inside of the `Receipt::Panic` as meta
-//  information. Maybe better to have `Vec<PanicContext>` to provide more information.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum PanicContext {
-    /// No additional information.
-    None,
-    /// `ContractId` retrieved during instruction execution.
-    ContractId(ContractId),
-}
-
-impl<M: Memory, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M: Memory, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     /// Returns the current state of the VM memory
     pub fn memory(&self) -> &MemoryInstance {
         self.memory.as_ref()
     }
 }
 
-impl<M: AsMut<MemoryInstance>, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M: AsMut<MemoryInstance>, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     /// Returns mutable access to the vm memory
+    ///
+    /// The instruction cache, if enabled, is bypassed until the next transaction,
+    /// since the code could be modified.
     pub fn memory_mut(&mut self) -> &mut MemoryInstance {
+        if let Some(cache) = &mut self.instruction_cache {
+            cache.suspend();
+        }
         self.memory.as_mut()
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     /// Returns the current state of the registers
     pub const fn registers(&self) -> &[Word] {
         &self.registers
@@ -299,6 +337,11 @@ impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
         self.interpreter_params.chain_id
     }
 
+    /// Get the parameters of the interpreter
+    pub(crate) fn interpreter_params(&self) -> &InterpreterParams {
+        &self.interpreter_params
+    }
+
     /// Receipts generated by a transaction execution.
     pub fn receipts(&self) -> &[Receipt] {
         self.receipts.as_ref().as_slice()
@@ -350,13 +393,13 @@ fn current_location(
     InstructionLocation::new(current_contract, offset)
 }
 
-impl<M, S, Tx, Ecal> AsRef<S> for Interpreter<M, S, Tx, Ecal> {
+impl<M, S, Tx, Ecal, Hook> AsRef<S> for Interpreter<M, S, Tx, Ecal, Hook> {
     fn as_ref(&self) -> &S {
         &self.storage
     }
 }
 
-impl<M, S, Tx, Ecal> AsMut<S> for Interpreter<M, S, Tx, Ecal> {
+impl<M, S, Tx, Ecal, Hook> AsMut<S> for Interpreter<M, S, Tx, Ecal, Hook> {
     fn as_mut(&mut self) -> &mut S {
         &mut self.storage
     }
@@ -654,4 +697,17 @@ impl<'vm> InputContracts<'vm> {
             Ok(())
         }
     }
+
+    /// Attaches the asset to the panic context if the `result` failed because of
+    /// insufficient balance.
+    pub fn check_balance<T, E>(
+        &mut self,
+        asset_id: &AssetId,
+        result: IoResult<T, E>,
+    ) -> IoResult<T, E> {
+        if let Err(RuntimeError::Recoverable(PanicReason::NotEnoughBalance)) = &result {
+            *self.panic_context = PanicContext::AssetId(*asset_id);
+        }
+        result
+    }
 }
@@ -15,7 +15,7 @@ use fuel_types::{
     Word,
 };
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
 {
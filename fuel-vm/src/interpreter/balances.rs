@@ -166,7 +166,7 @@ impl RuntimeBalances {
 
     /// Write all assets into the start of VM stack, i.e. at $ssp.
     /// Panics if the assets cannot fit.
-    pub fn to_vm<M, S, Tx, Ecal>(self, vm: &mut Interpreter<M, S, Tx, Ecal>)
+    pub fn to_vm<M, S, Tx, Ecal, Hook>(self, vm: &mut Interpreter<M, S, Tx, Ecal, Hook>)
     where
         M: Memory,
         Tx: ExecutableTransaction,
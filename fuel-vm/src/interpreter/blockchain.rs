@@ -33,11 +33,13 @@ use crate::{
         },
         receipts::ReceiptsCtx,
         ExecutableTransaction,
+        ExecutionHook,
         InputContracts,
         Interpreter,
         Memory,
         MemoryInstance,
         RuntimeBalances,
+        StorageAccessKind,
     },
     prelude::Profiler,
     storage::{
@@ -78,11 +80,12 @@ mod smo_tests;
 #[cfg(test)]
 mod test;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
+    Hook: ExecutionHook,
 {
     /// Loads contract ID pointed by `contract_id_addr`, and then for that contract,
     /// copies `length_unpadded` bytes from it starting from offset `contract_offset` into
@@ -327,6 +330,7 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
+        let access = self.storage_access(StorageAccessKind::Clear, a, c);
         let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
@@ -338,7 +342,9 @@ where
             ..
         } = self;
 
-        state_clear_qword(&contract_id?, storage, memory.as_ref(), pc, result, input)
+        state_clear_qword(&contract_id?, storage, memory.as_ref(), pc, result, input)?;
+        self.hook_storage_access(access);
+        Ok(())
     }
 
     pub(crate) fn state_read_word(
@@ -347,6 +353,7 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
+        let access = self.storage_access(StorageAccessKind::Read, c, 1);
         let (SystemRegisters { fp, pc, .. }, mut w) =
             split_registers(&mut self.registers);
         let (result, got_result) = w
@@ -371,7 +378,9 @@ where
             result,
             got_result,
             c,
-        )
+        )?;
+        self.hook_storage_access(access);
+        Ok(())
     }
 
     pub(crate) fn state_read_qword(
@@ -381,6 +390,7 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
+        let access = self.storage_access(StorageAccessKind::Read, c, d);
         let owner = self.ownership_registers();
         let (SystemRegisters { pc, fp, .. }, mut w) =
             split_registers(&mut self.registers);
@@ -406,7 +416,9 @@ where
                 origin_key_pointer: c,
                 num_slots: d,
             },
-        )
+        )?;
+        self.hook_storage_access(access);
+        Ok(())
     }
 
     pub(crate) fn state_write_word(
@@ -415,6 +427,7 @@ where
         rb: RegisterId,
         c: Word,
     ) -> IoResult<(), S::DataError> {
+        let access = self.storage_access(StorageAccessKind::Write, a, 1);
         let new_storage_gas_per_byte = self.gas_costs().new_storage_per_byte();
         let (
             SystemRegisters {
@@ -451,7 +464,9 @@ where
             a,
             exists,
             c,
-        )
+        )?;
+        self.hook_storage_access(access);
+        Ok(())
     }
 
     pub(crate) fn state_write_qword(
@@ -461,6 +476,7 @@ where
         c: Word,
         d: Word,
     ) -> IoResult<(), S::DataError> {
+        let access = self.storage_access(StorageAccessKind::Write, a, d);
         let new_storage_per_byte = self.gas_costs().new_storage_per_byte();
         let contract_id = self.internal_contract();
         let (
@@ -496,7 +512,9 @@ where
             pc,
             result,
             input,
-        )
+        )?;
+        self.hook_storage_access(access);
+        Ok(())
     }
 
     pub(crate) fn timestamp(
@@ -15,6 +15,7 @@ use crate::{
     context::Context,
     interpreter::{
         InterpreterParams,
+        NoopHook,
         PanicContext,
     },
     state::Debugger,
@@ -84,11 +85,13 @@ where
             interpreter_params,
             panic_context: PanicContext::None,
             ecal_state,
+            hook: NoopHook,
+            instruction_cache: None,
         }
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     /// Sets a profiler for the VM
     #[cfg(feature = "profile-any")]
     pub fn with_profiler<P>(&mut self, receiver: P) -> &mut Self
@@ -57,7 +57,7 @@ use fuel_types::{
 
 use alloc::borrow::Cow;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
@@ -276,13 +276,16 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
             Err(e) => return Err(e.into()),
         };
 
-        if let Some(source_contract) = internal_context {
+        let debit = if let Some(source_contract) = internal_context {
             // debit funding source (source contract balance)
-            balance_decrease(self.storage, &source_contract, &asset_id, amount)?;
+            balance_decrease(self.storage, &source_contract, &asset_id, amount)
+                .map(|_| ())
         } else {
             // debit external funding source (i.e. free balance)
-            external_asset_id_balance_sub(self.balances, self.memory, &asset_id, amount)?;
-        }
+            external_asset_id_balance_sub(self.balances, self.memory, &asset_id, amount)
+                .map_err(RuntimeError::from)
+        };
+        self.input_contracts.check_balance(&asset_id, debit)?;
         // credit destination contract
         let (_, created_new_entry) =
             balance_increase(self.storage, &destination, &asset_id, amount)?;
@@ -324,7 +327,7 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
     /// $rC -> transfer_amount
     /// $rD -> asset_id_offset
     pub(crate) fn transfer_output(
-        self,
+        mut self,
         recipient_offset: Word,
         output_index: Word,
         transfer_amount: Word,
@@ -354,13 +357,16 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
             Err(e) => return Err(e.into()),
         };
 
-        if let Some(source_contract) = internal_context {
+        let debit = if let Some(source_contract) = internal_context {
             // debit funding source (source contract balance)
-            balance_decrease(self.storage, &source_contract, &asset_id, amount)?;
+            balance_decrease(self.storage, &source_contract, &asset_id, amount)
+                .map(|_| ())
         } else {
             // debit external funding source (i.e. UTXOs)
-            external_asset_id_balance_sub(self.balances, self.memory, &asset_id, amount)?;
-        }
+            external_asset_id_balance_sub(self.balances, self.memory, &asset_id, amount)
+                .map_err(RuntimeError::from)
+        };
+        self.input_contracts.check_balance(&asset_id, debit)?;
 
         // credit variable output
         let variable = Output::variable(to, amount, asset_id);
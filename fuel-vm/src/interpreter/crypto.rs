@@ -30,7 +30,7 @@ use fuel_types::{
 #[cfg(test)]
 mod tests;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
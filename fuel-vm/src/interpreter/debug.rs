@@ -2,7 +2,7 @@ use super::Interpreter;
 use crate::prelude::*;
 use fuel_asm::RegId;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
 {
@@ -39,13 +39,32 @@ where
         self.debugger.remove_breakpoint(breakpoint)
     }
 
+    /// Get the execution budget of a slice.
+    pub const fn execution_budget(&self) -> Option<ExecutionBudget> {
+        self.debugger.execution_budget()
+    }
+
+    /// Set the execution budget of a slice, or remove it with `None`.
+    ///
+    /// Once the budget is exhausted, the execution yields
+    /// [`ProgramState::RunProgram`] with [`DebugEval::Preempted`], and
+    /// [`Interpreter::resume`] continues it with a new slice of the same budget.
+    pub fn set_execution_budget(&mut self, budget: Option<ExecutionBudget>) {
+        self.debugger.set_execution_budget(budget)
+    }
+
     pub(crate) fn eval_debugger_state(&mut self) -> DebugEval {
         let debugger = &mut self.debugger;
 
         let contract = self.frames.last().map(CallFrame::to);
         let pc = self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]);
 
-        debugger.eval_state(contract, pc)
+        let debug = debugger.eval_state(contract, pc);
+        if !debug.should_continue() {
+            return debug
+        }
+
+        debugger.eval_budget(self.registers[RegId::GGAS])
     }
 
     pub(crate) fn debugger_set_last_state(&mut self, state: ProgramState) {
@@ -58,6 +77,7 @@ where
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod tests {
     use alloc::{
         vec,
@@ -65,7 +85,11 @@ mod tests {
     };
 
     use super::Interpreter;
-    use crate::prelude::*;
+    use crate::{
+        checked_transaction::Checked,
+        interpreter::InterpreterParams,
+        prelude::*,
+    };
     use fuel_asm::RegId;
 
     #[test]
@@ -245,4 +269,174 @@ mod tests {
         vm.resume()
             .expect_err("Expected error when resuming without debug");
     }
+
+    fn run_to_completion(
+        vm: &mut Interpreter<MemoryInstance, MemoryStorage, Script>,
+        tx: Checked<Script>,
+    ) -> (ProgramState, usize) {
+        let mut state = vm
+            .transact(tx.test_into_ready())
+            .map(ProgramState::from)
+            .expect("Failed to execute script!");
+        let mut preemptions = 0usize;
+        while state.is_preempted() {
+            preemptions = preemptions.saturating_add(1);
+            state = vm.resume().expect("Failed to resume");
+        }
+
+        (state, preemptions)
+    }
+
+    fn budget_interpreter(
+        test_context: &TestBuilder,
+        budget: Option<ExecutionBudget>,
+    ) -> Interpreter<MemoryInstance, MemoryStorage, Script> {
+        let mut vm = Interpreter::with_storage(
+            MemoryInstance::new(),
+            test_context.get_storage().clone(),
+            InterpreterParams::new(0, ConsensusParameters::standard()),
+        );
+        vm.set_execution_budget(budget);
+        vm
+    }
+
+    #[test]
+    fn instruction_budget__preempts_and_resumes_script() {
+        use fuel_asm::op;
+
+        let mut test_context = TestBuilder::new(2322u64);
+        // 2 + 2 * 10 instructions
+        let script = vec![
+            op::movi(0x10, 10),
+            op::subi(0x10, 0x10, 1),
+            op::jnzb(0x10, RegId::ZERO, 0),
+            op::ret(RegId::ONE),
+        ];
+        let tx = test_context
+            .start_script(script, vec![])
+            .script_gas_limit(1_000_000)
+            .fee_input()
+            .build();
+
+        let mut vm = budget_interpreter(&test_context, None);
+        let (expected, _) = run_to_completion(&mut vm, tx.clone());
+        let expected_receipts = vm.receipts().to_vec();
+        let expected_tx = vm.transaction().clone();
+
+        let mut vm =
+            budget_interpreter(&test_context, Some(ExecutionBudget::Instructions(5)));
+        let (state, preemptions) = run_to_completion(&mut vm, tx);
+
+        assert_eq!(preemptions, 4);
+        assert_eq!(state, expected);
+        assert_eq!(vm.receipts(), expected_receipts.as_slice());
+        assert_eq!(vm.transaction(), &expected_tx);
+    }
+
+    #[test]
+    fn gas_budget__preempts_and_resumes_contract_call() {
+        use fuel_asm::op;
+        use fuel_types::canonical::Serialize;
+
+        let mut test_context = TestBuilder::new(2322u64);
+        let contract_id = test_context
+            .setup_contract(
+                vec![
+                    op::movi(0x10, 50),
+                    op::subi(0x10, 0x10, 1),
+                    op::jnzb(0x10, RegId::ZERO, 0),
+                    op::ret(RegId::ONE),
+                ],
+                None,
+                None,
+            )
+            .contract_id;
+
+        let (script, _) = crate::script_with_data_offset!(
+            data_offset,
+            vec![
+                op::movi(0x10, data_offset),
+                op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            test_context.get_tx_params().tx_offset()
+        );
+        let tx = test_context
+            .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+            .script_gas_limit(1_000_000)
+            .contract_input(contract_id)
+            .fee_input()
+            .contract_output(&contract_id)
+            .build();
+
+        let mut vm = budget_interpreter(&test_context, None);
+        let (expected, _) = run_to_completion(&mut vm, tx.clone());
+        let expected_receipts = vm.receipts().to_vec();
+
+        let mut vm = budget_interpreter(&test_context, Some(ExecutionBudget::Gas(20)));
+        let (state, preemptions) = run_to_completion(&mut vm, tx);
+
+        assert!(preemptions > 1);
+        assert_eq!(state, expected);
+        assert_eq!(vm.receipts(), expected_receipts.as_slice());
+        assert!(matches!(
+            vm.receipts().last(),
+            Some(Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn breakpoint__is_not_preemption_and_finalizes_script() {
+        use fuel_asm::op;
+
+        let mut test_context = TestBuilder::new(2322u64);
+        let script = vec![
+            op::movi(0x10, 1),
+            op::movi(0x11, 2),
+            op::movi(0x12, 3),
+            op::ret(RegId::ONE),
+        ];
+        let tx = test_context
+            .start_script(script, vec![])
+            .script_gas_limit(1_000_000)
+            .fee_input()
+            .build();
+
+        let mut vm =
+            budget_interpreter(&test_context, Some(ExecutionBudget::Instructions(100)));
+        vm.set_breakpoint(Breakpoint::script(2));
+        let state = vm
+            .transact(tx.test_into_ready())
+            .map(ProgramState::from)
+            .expect("Failed to execute script!");
+
+        assert!(!state.is_preempted());
+        assert_eq!(
+            state.debug_ref().and_then(DebugEval::breakpoint),
+            Some(&Breakpoint::script(2))
+        );
+        assert!(matches!(
+            vm.receipts().last(),
+            Some(Receipt::ScriptResult { .. })
+        ));
+    }
+
+    #[test]
+    fn execution_budget__clearing_deactivates_debugger() {
+        let mut vm = Interpreter::<_, _, Script>::with_memory_storage();
+
+        vm.set_execution_budget(Some(ExecutionBudget::Instructions(5)));
+        assert!(vm.debugger.is_active());
+
+        vm.set_execution_budget(None);
+        assert!(!vm.debugger.is_active());
+
+        vm.set_breakpoint(Breakpoint::script(0));
+        vm.set_execution_budget(None);
+        assert!(vm.debugger.is_active());
+    }
 }
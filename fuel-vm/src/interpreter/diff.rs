@@ -317,7 +317,7 @@ where
         .map(|((index, a), b)| (index, a.cloned(), b.cloned()))
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
 {
@@ -399,7 +399,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
 {
@@ -482,7 +482,7 @@ fn invert_receipts_ctx(ctx: &mut ReceiptsCtx, value: &VecState<Option<Receipt>>)
     invert_vec(ctx_mut.receipts_mut(), value);
 }
 
-impl<M, S, Tx, Ecal> PartialEq for Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> PartialEq for Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: PartialEq,
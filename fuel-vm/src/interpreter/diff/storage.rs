@@ -79,7 +79,7 @@ pub struct Record<S>(pub(super) S, pub(super) Vec<StorageDelta>)
 where
     S: InterpreterStorage;
 
-impl<M, S, Tx, Ecal> Interpreter<M, Record<S>, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, Record<S>, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
@@ -87,7 +87,7 @@ where
     /// Remove the [`Recording`] wrapper from the storage.
     /// Recording storage changes has an overhead so it's
     /// useful to be able to remove it once the diff is generated.
-    pub fn remove_recording(self) -> Interpreter<M, S, Tx, Ecal> {
+    pub fn remove_recording(self) -> Interpreter<M, S, Tx, Ecal, Hook> {
         Interpreter {
             registers: self.registers,
             memory: self.memory,
@@ -106,6 +106,8 @@ where
             profiler: self.profiler,
             interpreter_params: self.interpreter_params,
             ecal_state: self.ecal_state,
+            hook: self.hook,
+            instruction_cache: self.instruction_cache,
         }
     }
 
@@ -159,7 +161,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
@@ -169,7 +171,7 @@ where
     /// record any changes this VM makes to it's storage.
     /// Recording storage changes has an overhead so should
     /// be used in production.
-    pub fn add_recording(self) -> Interpreter<M, Record<S>, Tx, Ecal> {
+    pub fn add_recording(self) -> Interpreter<M, Record<S>, Tx, Ecal, Hook> {
         Interpreter {
             registers: self.registers,
             memory: self.memory,
@@ -188,6 +190,8 @@ where
             profiler: self.profiler,
             interpreter_params: self.interpreter_params,
             ecal_state: self.ecal_state,
+            hook: self.hook,
+            instruction_cache: self.instruction_cache,
         }
     }
 
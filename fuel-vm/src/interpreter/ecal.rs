@@ -14,6 +14,30 @@ use crate::{
     interpreter::NotSupportedEcal,
 };
 
+#[cfg(feature = "debug-ecal")]
+mod debug;
+mod registry;
+
+#[cfg(all(feature = "debug-ecal", feature = "std"))]
+pub use debug::StderrSink;
+#[cfg(feature = "debug-ecal")]
+pub use debug::{
+    DebugEcal,
+    DebugMessage,
+    DebugOp,
+    DebugSink,
+    DebugValue,
+};
+
+pub use registry::{
+    EcalArg,
+    EcalArgs,
+    EcalRegistry,
+    EcalVm,
+    HostError,
+    ECAL_ARGS_REGISTERS,
+};
+
 use super::{
     internal::inc_pc,
     Interpreter,
@@ -30,8 +54,8 @@ where
     const INC_PC: bool = true;
 
     /// ECAL opcode handler
-    fn ecal<M, S, Tx>(
-        vm: &mut Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        vm: &mut Interpreter<M, S, Tx, Self, Hook>,
         a: RegId,
         b: RegId,
         c: RegId,
@@ -43,8 +67,8 @@ where
 
 /// Default ECAL opcode handler function, which just errors immediately.
 impl EcalHandler for NotSupportedEcal {
-    fn ecal<M, S, Tx>(
-        _: &mut Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        _: &mut Interpreter<M, S, Tx, Self, Hook>,
         _: RegId,
         _: RegId,
         _: RegId,
@@ -60,8 +84,8 @@ pub struct PredicateErrorEcal;
 
 /// ECAL is not allowed in predicates
 impl EcalHandler for PredicateErrorEcal {
-    fn ecal<M, S, Tx>(
-        _vm: &mut Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        _vm: &mut Interpreter<M, S, Tx, Self, Hook>,
         _: RegId,
         _: RegId,
         _: RegId,
@@ -71,7 +95,7 @@ impl EcalHandler for PredicateErrorEcal {
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Ecal: EcalHandler,
@@ -94,7 +118,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     Ecal: EcalHandler,
 {
@@ -0,0 +1,208 @@
+//! ECAL handler printing debug messages for local development.
+//!
+//! See [`DebugEcal`].
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_types::Word;
+
+use crate::{
+    constraints::InstructionLocation,
+    error::SimpleResult,
+    interpreter::{
+        EcalHandler,
+        Interpreter,
+        Memory,
+    },
+};
+
+/// Operation of the [`DebugEcal`], selected by the value of `$rA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u64)]
+pub enum DebugOp {
+    /// Print the value of the register `$rB`.
+    Register = 0,
+    /// Print `$rC` bytes of the memory at `$rB` as hex.
+    Hex = 1,
+    /// Print `$rC` bytes of the memory at `$rB` as an UTF-8 string. Invalid sequences
+    /// are replaced with `U+FFFD`.
+    Utf8 = 2,
+    /// Print only the location of the instruction.
+    Location = 3,
+}
+
+impl TryFrom<Word> for DebugOp {
+    type Error = PanicReason;
+
+    fn try_from(value: Word) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Register),
+            1 => Ok(Self::Hex),
+            2 => Ok(Self::Utf8),
+            3 => Ok(Self::Location),
+            _ => Err(PanicReason::EcalError),
+        }
+    }
+}
+
+/// Printed value of the [`DebugMessage`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DebugValue {
+    /// Value of the register.
+    Register {
+        /// The printed register.
+        register: RegId,
+        /// The value of the register.
+        value: Word,
+    },
+    /// Memory range printed as hex.
+    Hex(Vec<u8>),
+    /// Memory range printed as a string.
+    Utf8(String),
+    /// Only the location is printed.
+    Location,
+}
+
+/// Message printed by the [`DebugEcal`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DebugMessage {
+    /// Location of the `ECAL` instruction.
+    pub location: InstructionLocation,
+    /// The printed value.
+    pub value: DebugValue,
+}
+
+impl fmt::Display for DebugMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location.context {
+            Some(contract_id) => write!(f, "[contract {contract_id}")?,
+            None => write!(f, "[script")?,
+        }
+        write!(f, " +{:#x}]", self.location.offset)?;
+
+        match &self.value {
+            DebugValue::Register { register, value } => {
+                write!(f, " $r{} = {value} ({value:#x})", register.to_u8())
+            }
+            DebugValue::Hex(bytes) => {
+                write!(f, " 0x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+            DebugValue::Utf8(string) => write!(f, " {string}"),
+            DebugValue::Location => Ok(()),
+        }
+    }
+}
+
+/// Receiver of the messages printed by the [`DebugEcal`].
+pub trait DebugSink: Clone {
+    /// Handles the printed message.
+    fn print(&mut self, message: DebugMessage);
+}
+
+/// Collects the messages.
+impl DebugSink for Vec<DebugMessage> {
+    fn print(&mut self, message: DebugMessage) {
+        self.push(message);
+    }
+}
+
+/// Prints the messages to the standard error.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrSink;
+
+#[cfg(feature = "std")]
+impl DebugSink for StderrSink {
+    fn print(&mut self, message: DebugMessage) {
+        std::eprintln!("{message}");
+    }
+}
+
+/// ECAL handler for local development that prints register values, memory ranges and
+/// the current location to a [`DebugSink`], see [`DebugOp`] for the operations.
+///
+/// The messages are handled by the host and don't produce receipts, so debug prints
+/// don't affect the receipts root. Only the `noop` gas is charged for the `ECAL`.
+///
+/// The handler is available only with the `debug-ecal` feature and must never be used
+/// by the nodes: transactions relying on it can't be executed on-chain, where `ECAL`
+/// panics. Predicates don't have access to `ECAL` at all.
+#[derive(Debug, Default, Clone)]
+pub struct DebugEcal<S = Vec<DebugMessage>> {
+    sink: S,
+}
+
+impl<S> DebugEcal<S> {
+    /// Create the handler printing to the `sink`.
+    pub const fn new(sink: S) -> Self {
+        Self { sink }
+    }
+
+    /// The sink of the printed messages.
+    pub const fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable access to the sink of the printed messages.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes the handler returning the sink.
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+}
+
+impl<S> EcalHandler for DebugEcal<S>
+where
+    S: DebugSink,
+{
+    fn ecal<M, St, Tx, Hook>(
+        vm: &mut Interpreter<M, St, Tx, Self, Hook>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        _: RegId,
+    ) -> SimpleResult<()>
+    where
+        M: Memory,
+    {
+        vm.gas_charge(vm.gas_costs().noop())?;
+
+        let registers = vm.registers();
+        let operation = DebugOp::try_from(registers[a])?;
+        let (pointer, len) = (registers[b], registers[c]);
+        let location = InstructionLocation {
+            context: vm.contract_id(),
+            offset: registers[RegId::PC].saturating_sub(registers[RegId::IS]),
+        };
+
+        let value = match operation {
+            DebugOp::Register => DebugValue::Register {
+                register: b,
+                value: registers[b],
+            },
+            DebugOp::Hex => DebugValue::Hex(vm.memory().read(pointer, len)?.to_vec()),
+            DebugOp::Utf8 => DebugValue::Utf8(
+                String::from_utf8_lossy(vm.memory().read(pointer, len)?).into_owned(),
+            ),
+            DebugOp::Location => DebugValue::Location,
+        };
+
+        vm.ecal_state_mut()
+            .sink
+            .print(DebugMessage { location, value });
+
+        Ok(())
+    }
+}
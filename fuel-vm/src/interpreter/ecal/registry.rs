@@ -0,0 +1,379 @@
+//! ECAL handler dispatching to the registered host functions.
+//!
+//! See [`EcalRegistry`].
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt;
+
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    error::{
+        PanicOrBug,
+        SimpleResult,
+    },
+    interpreter::{
+        EcalHandler,
+        Interpreter,
+        Memory,
+        MemoryInstance,
+    },
+};
+
+/// Number of the argument registers of the host function: `$rB`, `$rC` and `$rD`.
+pub const ECAL_ARGS_REGISTERS: usize = 3;
+
+/// The view of the VM available to the host functions of the [`EcalRegistry`].
+pub trait EcalVm {
+    /// The registers of the VM.
+    fn registers(&self) -> &[Word];
+
+    /// The memory of the VM.
+    fn memory(&self) -> &MemoryInstance;
+
+    /// Mutable access to the memory of the VM.
+    fn memory_mut(&mut self) -> &mut MemoryInstance;
+
+    /// Expands the heap by `amount` bytes, updating `$hp`.
+    fn allocate(&mut self, amount: Word) -> SimpleResult<()>;
+
+    /// Charges `gas` from the remaining gas of the current context.
+    fn gas_charge(&mut self, gas: Word) -> SimpleResult<()>;
+}
+
+impl<M, S, Tx, Ecal, Hook> EcalVm for Interpreter<M, S, Tx, Ecal, Hook>
+where
+    M: Memory,
+{
+    fn registers(&self) -> &[Word] {
+        Interpreter::registers(self)
+    }
+
+    fn memory(&self) -> &MemoryInstance {
+        Interpreter::memory(self)
+    }
+
+    fn memory_mut(&mut self) -> &mut MemoryInstance {
+        Interpreter::memory_mut(self)
+    }
+
+    fn allocate(&mut self, amount: Word) -> SimpleResult<()> {
+        Interpreter::allocate(self, amount)
+    }
+
+    fn gas_charge(&mut self, gas: Word) -> SimpleResult<()> {
+        Interpreter::gas_charge(self, gas)
+    }
+}
+
+/// Argument of the host function decoded from the registers and the memory.
+pub trait EcalArg: Sized {
+    /// Number of registers used by the argument.
+    const REGISTERS: usize;
+
+    /// Decodes the argument from its `registers`.
+    fn decode(registers: &[Word], memory: &MemoryInstance) -> Result<Self, PanicReason>;
+}
+
+impl EcalArg for Word {
+    const REGISTERS: usize = 1;
+
+    fn decode(registers: &[Word], _: &MemoryInstance) -> Result<Self, PanicReason> {
+        registers.first().copied().ok_or(PanicReason::EcalError)
+    }
+}
+
+/// Fixed-size bytes at the pointer in the register.
+impl<const N: usize> EcalArg for [u8; N] {
+    const REGISTERS: usize = 1;
+
+    fn decode(registers: &[Word], memory: &MemoryInstance) -> Result<Self, PanicReason> {
+        let pointer = Word::decode(registers, memory)?;
+        memory.read_bytes(pointer)
+    }
+}
+
+macro_rules! impl_ecal_arg_for_bytes {
+    ($($ty:ty),*) => {
+        $(
+            /// The bytes at the pointer in the register.
+            impl EcalArg for $ty {
+                const REGISTERS: usize = 1;
+
+                fn decode(
+                    registers: &[Word],
+                    memory: &MemoryInstance,
+                ) -> Result<Self, PanicReason> {
+                    Ok(<[u8; <$ty>::LEN]>::decode(registers, memory)?.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_ecal_arg_for_bytes!(Address, AssetId, Bytes32, ContractId);
+
+/// Bytes at the pointer in the first register with the length in the second one.
+impl EcalArg for Vec<u8> {
+    const REGISTERS: usize = 2;
+
+    fn decode(registers: &[Word], memory: &MemoryInstance) -> Result<Self, PanicReason> {
+        let [pointer, len] = registers else {
+            return Err(PanicReason::EcalError)
+        };
+        Ok(memory.read(*pointer, *len)?.to_vec())
+    }
+}
+
+/// UTF-8 string at the pointer in the first register with the length in the second
+/// one.
+impl EcalArg for String {
+    const REGISTERS: usize = 2;
+
+    fn decode(registers: &[Word], memory: &MemoryInstance) -> Result<Self, PanicReason> {
+        let bytes = Vec::<u8>::decode(registers, memory)?;
+        String::from_utf8(bytes).map_err(|_| PanicReason::EcalError)
+    }
+}
+
+/// Arguments of the host function decoded from `$rB`, `$rC` and `$rD` in order.
+pub trait EcalArgs: Sized {
+    /// Number of registers used by the arguments.
+    const REGISTERS: usize;
+
+    /// Decodes the arguments from the values of the argument registers.
+    fn decode(
+        registers: &[Word; ECAL_ARGS_REGISTERS],
+        memory: &MemoryInstance,
+    ) -> Result<Self, PanicReason>;
+}
+
+impl EcalArgs for () {
+    const REGISTERS: usize = 0;
+
+    fn decode(
+        _: &[Word; ECAL_ARGS_REGISTERS],
+        _: &MemoryInstance,
+    ) -> Result<Self, PanicReason> {
+        Ok(())
+    }
+}
+
+/// Splits off the first `count` registers of the argument.
+fn take_registers<'a>(
+    registers: &mut &'a [Word],
+    count: usize,
+) -> Result<&'a [Word], PanicReason> {
+    if count > registers.len() {
+        return Err(PanicReason::EcalError)
+    }
+    let (current, rest) = registers.split_at(count);
+    *registers = rest;
+    Ok(current)
+}
+
+macro_rules! impl_ecal_args_for_tuple {
+    ($($arg:ident),+) => {
+        impl<$($arg),+> EcalArgs for ($($arg,)+)
+        where
+            $($arg: EcalArg,)+
+        {
+            const REGISTERS: usize = 0 $(+ $arg::REGISTERS)+;
+
+            fn decode(
+                registers: &[Word; ECAL_ARGS_REGISTERS],
+                memory: &MemoryInstance,
+            ) -> Result<Self, PanicReason> {
+                let mut registers = registers.as_slice();
+                Ok(($(
+                    $arg::decode(take_registers(&mut registers, $arg::REGISTERS)?, memory)?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_ecal_args_for_tuple!(A);
+impl_ecal_args_for_tuple!(A, B);
+impl_ecal_args_for_tuple!(A, B, C);
+
+/// Error of the host function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostError {
+    /// The VM operation failed, e.g. out of gas or invalid memory access. The panic
+    /// is propagated as is.
+    Vm(PanicOrBug),
+    /// The host function failed. Reported as [`PanicReason::EcalError`].
+    Failed,
+}
+
+impl HostError {
+    /// Maps any error of the host function to [`HostError::Failed`], e.g.
+    /// `.map_err(HostError::failed)?`.
+    pub fn failed<E>(_: E) -> Self {
+        Self::Failed
+    }
+}
+
+impl From<PanicOrBug> for HostError {
+    fn from(error: PanicOrBug) -> Self {
+        Self::Vm(error)
+    }
+}
+
+impl From<PanicReason> for HostError {
+    fn from(reason: PanicReason) -> Self {
+        Self::Vm(reason.into())
+    }
+}
+
+impl From<HostError> for PanicOrBug {
+    fn from(error: HostError) -> Self {
+        match error {
+            HostError::Vm(error) => error,
+            HostError::Failed => PanicReason::EcalError.into(),
+        }
+    }
+}
+
+type HostFunction = dyn Fn(&mut dyn EcalVm, &[Word; ECAL_ARGS_REGISTERS]) -> Result<Word, HostError>
+    + Send
+    + Sync;
+
+#[derive(Clone)]
+struct RegisteredFunction {
+    gas: Word,
+    function: Arc<HostFunction>,
+}
+
+/// ECAL handler that dispatches to the host function registered for the selector in
+/// `$rA`.
+///
+/// The host function receives its arguments decoded from `$rB`, `$rC` and `$rD`, see
+/// [`EcalArgs`], and its result is written to `$rA`. The gas registered with the
+/// function is charged before the call, and the function may charge more with
+/// [`EcalVm::gas_charge`]. Unknown selectors and [`HostError::Failed`] cause
+/// [`PanicReason::EcalError`].
+///
+/// The functions are shared between the clones of the registry, so the state of the
+/// functions should be shared explicitly, e.g. with `Arc<Mutex<_>>`.
+#[derive(Clone, Default)]
+pub struct EcalRegistry {
+    functions: BTreeMap<Word, RegisteredFunction>,
+}
+
+impl fmt::Debug for EcalRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcalRegistry")
+            .field("selectors", &self.functions.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl EcalRegistry {
+    /// Create a registry without functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the host `function` for the `selector` charging `gas` per call,
+    /// replacing the previous function with the same selector.
+    ///
+    /// # Panics
+    ///
+    /// If the arguments of the function require more than [`ECAL_ARGS_REGISTERS`]
+    /// registers.
+    pub fn register<A, F>(&mut self, selector: Word, gas: Word, function: F) -> &mut Self
+    where
+        A: EcalArgs,
+        F: Fn(&mut dyn EcalVm, A) -> Result<Word, HostError> + Send + Sync + 'static,
+    {
+        assert!(
+            A::REGISTERS <= ECAL_ARGS_REGISTERS,
+            "The arguments of the host function don't fit into the registers"
+        );
+
+        let function =
+            move |vm: &mut dyn EcalVm, registers: &[Word; ECAL_ARGS_REGISTERS]| {
+                let args = A::decode(registers, vm.memory())?;
+                function(vm, args)
+            };
+        self.functions.insert(
+            selector,
+            RegisteredFunction {
+                gas,
+                function: Arc::new(function),
+            },
+        );
+        self
+    }
+
+    /// Builder version of [`Self::register`].
+    pub fn with_function<A, F>(mut self, selector: Word, gas: Word, function: F) -> Self
+    where
+        A: EcalArgs,
+        F: Fn(&mut dyn EcalVm, A) -> Result<Word, HostError> + Send + Sync + 'static,
+    {
+        self.register(selector, gas, function);
+        self
+    }
+
+    /// Removes the function registered for the `selector`.
+    pub fn unregister(&mut self, selector: Word) -> bool {
+        self.functions.remove(&selector).is_some()
+    }
+
+    /// Returns `true` if a function is registered for the `selector`.
+    pub fn contains(&self, selector: Word) -> bool {
+        self.functions.contains_key(&selector)
+    }
+}
+
+impl EcalHandler for EcalRegistry {
+    fn ecal<M, S, Tx, Hook>(
+        vm: &mut Interpreter<M, S, Tx, Self, Hook>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> SimpleResult<()>
+    where
+        M: Memory,
+    {
+        if a < RegId::WRITABLE {
+            return Err(PanicReason::ReservedRegisterNotWritable.into())
+        }
+
+        let registers = vm.registers();
+        let selector = registers[a];
+        let args = [registers[b], registers[c], registers[d]];
+
+        let RegisteredFunction { gas, function } = vm
+            .ecal_state()
+            .functions
+            .get(&selector)
+            .cloned()
+            .ok_or(PanicReason::EcalError)?;
+
+        vm.gas_charge(gas)?;
+        let result = function(vm, &args)?;
+        vm.registers_mut()[a] = result;
+
+        Ok(())
+    }
+}
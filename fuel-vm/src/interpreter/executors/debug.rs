@@ -3,21 +3,29 @@ use crate::{
     interpreter::{
         EcalHandler,
         ExecutableTransaction,
+        ExecutionHook,
         Interpreter,
         Memory,
     },
-    state::ProgramState,
+    state::{
+        DebugEval,
+        ProgramState,
+    },
     storage::InterpreterStorage,
 };
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     /// Continue the execution from a previously interrupted program flow.
+    ///
+    /// The script execution preempted by the execution budget is finalized once the
+    /// program completes, the same way as if it was never preempted.
     pub fn resume(&mut self) -> Result<ProgramState, InterpreterError<S::DataError>> {
         let state = self
             .debugger_last_state()
@@ -30,6 +38,8 @@ where
 
             ProgramState::Revert(w) => Ok(ProgramState::Revert(w)),
 
+            ProgramState::RunProgram(DebugEval::Preempted) => self.resume_script(),
+
             ProgramState::RunProgram(_) => self.run_program(),
 
             ProgramState::VerifyPredicate(_) => unimplemented!(),
@@ -13,6 +13,7 @@ use crate::{
         },
         EcalHandler,
         ExecutableTransaction,
+        ExecutionHook,
         Interpreter,
         Memory,
     },
@@ -32,15 +33,23 @@ use fuel_types::Word;
 
 use core::ops::Div;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     /// Execute the current instruction located in `$m[$pc]`.
+    ///
+    /// Uses the decoded instruction from the [`crate::interpreter::InstructionCache`] if
+    /// it is enabled.
     pub fn execute(&mut self) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        if let Some((raw, instruction)) = self.cached_instruction() {
+            return self.run_instruction(raw, Some(instruction))
+        }
+
         let raw_instruction = self.fetch_instruction()?;
         self.instruction(raw_instruction)
     }
@@ -72,6 +81,15 @@ where
     pub fn instruction<R: Into<RawInstruction> + Copy>(
         &mut self,
         raw: R,
+    ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
+        self.run_instruction(raw.into(), None)
+    }
+
+    /// Execute the `raw` instruction, skipping the decoding if it is `decoded` already.
+    fn run_instruction(
+        &mut self,
+        raw: RawInstruction,
+        decoded: Option<Instruction>,
     ) -> Result<ExecuteState, InterpreterError<S::DataError>> {
         if self.debugger.is_active() {
             let debug = self.eval_debugger_state();
@@ -80,8 +98,18 @@ where
             }
         }
 
-        self.instruction_inner(raw.into())
-            .map_err(|e| InterpreterError::from_runtime(e, raw.into()))
+        #[cfg(feature = "std")]
+        self.sanitizer_enter_instruction();
+
+        let checkpoint = self.hook_before_instruction(raw);
+        let result = match decoded {
+            Some(instruction) => self.decoded_instruction(instruction),
+            None => self.instruction_inner(raw),
+        }
+        .map_err(|e| InterpreterError::from_runtime(e, raw));
+        self.hook_after_instruction(raw, checkpoint);
+
+        result
     }
 
     fn instruction_inner(
@@ -90,7 +118,13 @@ where
     ) -> IoResult<ExecuteState, S::DataError> {
         let instruction = Instruction::try_from(raw)
             .map_err(|_| RuntimeError::from(PanicReason::InvalidInstruction))?;
+        self.decoded_instruction(instruction)
+    }
 
+    fn decoded_instruction(
+        &mut self,
+        instruction: Instruction,
+    ) -> IoResult<ExecuteState, S::DataError> {
         // TODO additional branch that might be optimized after
         // https://github.com/FuelLabs/fuel-asm/issues/68
         if self.is_predicate() && !instruction.opcode().is_predicate_allowed() {
@@ -22,6 +22,7 @@ use crate::{
         CheckedMetadata,
         EcalHandler,
         ExecutableTransaction,
+        ExecutionHook,
         InitialBalances,
         Interpreter,
         Memory,
@@ -34,6 +35,7 @@ use crate::{
         RuntimeError,
     },
     state::{
+        DebugEval,
         ExecuteState,
         ProgramState,
         StateTransitionRef,
@@ -452,7 +454,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -519,7 +521,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -622,7 +624,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -731,13 +733,14 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
 
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     fn update_transaction_outputs(
         &mut self,
@@ -800,14 +803,11 @@ where
                 return Err(InterpreterError::Panic(PanicReason::ContractNotInInputs));
             }
 
-            let gas_limit;
-            let is_empty_script;
-            if let Some(script) = self.transaction().as_script() {
-                gas_limit = *script.script_gas_limit();
-                is_empty_script = script.script().is_empty();
+            let is_empty_script = if let Some(script) = self.transaction().as_script() {
+                script.script().is_empty()
             } else {
                 unreachable!("Only `Create` and `Script` transactions can be executed inside of the VM")
-            }
+            };
 
             // TODO set tree balance
 
@@ -818,64 +818,113 @@ where
             } else {
                 // Return `1` as successful execution.
                 let return_val = 1;
+                let receipts = self.receipts.len();
                 self.ret(return_val)?;
+                self.hook_receipts(receipts);
                 Ok(ProgramState::Return(return_val))
             };
 
-            let gas_used = gas_limit
-                .checked_sub(self.remaining_gas())
-                .ok_or_else(|| Bug::new(BugVariant::GlobalGasUnderflow))?;
-
-            // Catch VM panic and don't propagate, generating a receipt
-            let (status, program) = match program {
-                Ok(s) => {
-                    // either a revert or success
-                    let res = if let ProgramState::Revert(_) = &s {
-                        ScriptExecutionResult::Revert
-                    } else {
-                        ScriptExecutionResult::Success
-                    };
-                    (res, s)
+            // The preempted execution is finalized after it is resumed
+            if let Ok(state) = &program {
+                if state.is_preempted() {
+                    self.debugger_set_last_state(*state);
+                    return Ok(*state)
                 }
+            }
 
-                Err(e) => match e.instruction_result() {
-                    Some(result) => {
-                        self.append_panic_receipt(result);
+            self.finalize_script(program)?
+        };
+        self.update_transaction_outputs()?;
 
-                        (ScriptExecutionResult::Panic, ProgramState::Revert(0))
-                    }
+        Ok(state)
+    }
 
-                    // This isn't a specified case of an erroneous program and should be
-                    // propagated. If applicable, OS errors will fall into this category.
-                    None => return Err(e),
-                },
-            };
+    /// Finalizes the script execution with the `program` result: generates the
+    /// panic and script result receipts and updates the outputs.
+    fn finalize_script(
+        &mut self,
+        program: Result<ProgramState, InterpreterError<S::DataError>>,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let gas_costs = self.gas_costs().clone();
+        let fee_params = *self.fee_params();
+        let base_asset_id = *self.base_asset_id();
+        let gas_limit = self
+            .transaction()
+            .as_script()
+            .map(|script| *script.script_gas_limit())
+            .unwrap_or_default();
+
+        let gas_used = gas_limit
+            .checked_sub(self.remaining_gas())
+            .ok_or_else(|| Bug::new(BugVariant::GlobalGasUnderflow))?;
+
+        // Catch VM panic and don't propagate, generating a receipt
+        let receipts = self.receipts.len();
+        let (status, program) = match program {
+            Ok(s) => {
+                // either a revert or success
+                let res = if let ProgramState::Revert(_) = &s {
+                    ScriptExecutionResult::Revert
+                } else {
+                    ScriptExecutionResult::Success
+                };
+                (res, s)
+            }
 
-            let receipt = Receipt::script_result(status, gas_used);
+            Err(e) => match e.instruction_result() {
+                Some(result) => {
+                    self.append_panic_receipt(result);
 
-            self.receipts.push(receipt)?;
+                    (ScriptExecutionResult::Panic, ProgramState::Revert(0))
+                }
 
-            if program.is_debug() {
-                self.debugger_set_last_state(program);
-            }
+                // This isn't a specified case of an erroneous program and should be
+                // propagated. If applicable, OS errors will fall into this category.
+                None => return Err(e),
+            },
+        };
 
-            let revert = matches!(program, ProgramState::Revert(_));
-            let gas_price = self.gas_price();
-            Self::finalize_outputs(
-                &mut self.tx,
-                &gas_costs,
-                &fee_params,
-                &base_asset_id,
-                revert,
-                gas_used,
-                &self.initial_balances,
-                &self.balances,
-                gas_price,
-            )?;
+        let receipt = Receipt::script_result(status, gas_used);
 
-            program
-        };
+        self.receipts.push(receipt)?;
+        self.hook_receipts(receipts);
+
+        if program.is_debug() {
+            self.debugger_set_last_state(program);
+        }
+
+        let revert = matches!(program, ProgramState::Revert(_));
+        let gas_price = self.gas_price();
+        Self::finalize_outputs(
+            &mut self.tx,
+            &gas_costs,
+            &fee_params,
+            &base_asset_id,
+            revert,
+            gas_used,
+            &self.initial_balances,
+            &self.balances,
+            gas_price,
+        )?;
+
+        Ok(program)
+    }
+
+    /// Continues the preempted script execution, finalizing it unless the program is
+    /// preempted again.
+    pub(crate) fn resume_script(
+        &mut self,
+    ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        let program = self.run_program();
+        if let Ok(state) = &program {
+            if state.is_preempted() {
+                return Ok(*state)
+            }
+        }
+
+        let state = self.finalize_script(program)?;
         self.update_transaction_outputs()?;
+        self.post_execute();
 
         Ok(state)
     }
@@ -883,6 +932,8 @@ where
     pub(crate) fn run_program(
         &mut self,
     ) -> Result<ProgramState, InterpreterError<S::DataError>> {
+        self.debugger.start_budget_slice();
+
         loop {
             // Check whether the instruction will be executed in a call context
             let in_call = !self.frames.is_empty();
@@ -890,9 +941,14 @@ where
             let state = self.execute()?;
 
             if in_call {
-                // Only reverts should terminate execution from a call context
-                if let ExecuteState::Revert(r) = state {
-                    return Ok(ProgramState::Revert(r));
+                // Only reverts and preemptions should terminate execution from a call
+                // context
+                match state {
+                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+                    ExecuteState::DebugEvent(DebugEval::Preempted) => {
+                        return Ok(ProgramState::RunProgram(DebugEval::Preempted))
+                    }
+                    _ => (),
                 }
             } else {
                 match state {
@@ -918,13 +974,14 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
     <Tx as IntoChecked>::Metadata: CheckedMetadata,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     /// Initialize a pre-allocated instance of [`Interpreter`] with the provided
     /// transaction and execute it. The result will be bound to the lifetime
@@ -957,7 +1014,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -989,7 +1046,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -1021,7 +1078,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -1053,7 +1110,7 @@ where
     }
 }
 
-impl<M, S: InterpreterStorage, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M, S: InterpreterStorage, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     fn verify_ready_tx<Tx2: IntoChecked>(
         &self,
         tx: &Ready<Tx2>,
@@ -2,6 +2,7 @@ use crate::{
     error::PredicateVerificationFailed,
     interpreter::{
         EcalHandler,
+        ExecutionHook,
         Memory,
     },
     prelude::{
@@ -20,11 +21,12 @@ use fuel_asm::{
     RegId,
 };
 
-impl<M, Tx, Ecal> Interpreter<M, PredicateStorage, Tx, Ecal>
+impl<M, Tx, Ecal, Hook> Interpreter<M, PredicateStorage, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     /// Verify a predicate that has been initialized already
     pub(crate) fn verify_predicate(
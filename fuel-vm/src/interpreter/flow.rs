@@ -35,7 +35,6 @@ use crate::{
         Interpreter,
         Memory,
         MemoryInstance,
-        PanicContext,
         RuntimeBalances,
     },
     prelude::{
@@ -50,7 +49,10 @@ use crate::{
     },
 };
 use alloc::vec::Vec;
-use core::cmp;
+use core::{
+    cmp,
+    mem,
+};
 use fuel_asm::{
     Instruction,
     PanicInstruction,
@@ -82,7 +84,7 @@ mod ret_tests;
 #[cfg(test)]
 mod tests;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
@@ -137,16 +139,9 @@ where
         let pc = self.registers[RegId::PC];
         let is = self.registers[RegId::IS];
 
-        let mut receipt =
-            Receipt::panic(self.internal_contract().unwrap_or_default(), result, pc, is);
-
-        match self.panic_context {
-            PanicContext::None => {}
-            PanicContext::ContractId(contract_id) => {
-                receipt = receipt.with_panic_contract_id(Some(contract_id));
-            }
-        };
-        self.panic_context = PanicContext::None;
+        let receipt =
+            Receipt::panic(self.internal_contract().unwrap_or_default(), result, pc, is)
+                .with_panic_context(mem::take(&mut self.panic_context));
 
         self.receipts
             .push(receipt)
@@ -331,7 +326,7 @@ impl JumpArgs {
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
@@ -390,6 +385,7 @@ where
             new_storage_gas_per_byte,
             receipts: &mut self.receipts,
             frames: &mut self.frames,
+            max_call_depth: self.interpreter_params.vm_params.max_call_depth(),
             current_contract,
             profiler: &mut self.profiler,
         }
@@ -455,6 +451,7 @@ struct PrepareCallCtx<'vm, S> {
     input_contracts: InputContracts<'vm>,
     receipts: &'vm mut ReceiptsCtx,
     frames: &'vm mut Vec<CallFrame>,
+    max_call_depth: Word,
     current_contract: Option<ContractId>,
     profiler: &'vm mut Profiler,
 }
@@ -470,6 +467,10 @@ where
             + StorageRead<ContractsRawCode>
             + StorageAsRef,
     {
+        if self.frames.len() as Word >= self.max_call_depth {
+            return Err(PanicReason::CallDepthExceeded.into())
+        }
+
         let call_bytes = self
             .memory
             .read(self.params.call_params_pointer, Call::LEN)?;
@@ -499,13 +500,14 @@ where
             code_size_padded as Word,
         )?;
 
-        if let Some(source_contract) = self.current_contract {
+        let debit = if let Some(source_contract) = self.current_contract {
             balance_decrease(
                 self.storage,
                 &source_contract,
                 &asset_id,
                 self.params.amount_of_coins_to_forward,
-            )?;
+            )
+            .map(|_| ())
         } else {
             let amount = self.params.amount_of_coins_to_forward;
             external_asset_id_balance_sub(
@@ -513,8 +515,10 @@ where
                 self.memory,
                 &asset_id,
                 amount,
-            )?;
-        }
+            )
+            .map_err(RuntimeError::from)
+        };
+        self.input_contracts.check_balance(&asset_id, debit)?;
 
         self.input_contracts.check(call.to())?;
 
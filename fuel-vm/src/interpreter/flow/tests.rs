@@ -14,6 +14,7 @@ use crate::crypto;
 use fuel_storage::StorageAsMut;
 use fuel_tx::{
     field::ReceiptsRoot,
+    PanicContext,
     Script,
 };
 use fuel_types::{
@@ -381,6 +382,7 @@ fn test_prepare_call(input: Input) -> Result<Output, RuntimeError<Infallible>> {
         new_storage_gas_per_byte: 0,
         receipts: &mut receipts,
         frames: &mut frames,
+        max_call_depth: Word::MAX,
         current_contract,
         profiler: &mut Profiler::default(),
     };
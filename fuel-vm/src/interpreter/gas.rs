@@ -22,7 +22,7 @@ use fuel_types::{
 #[cfg(test)]
 mod tests;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal> {
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
     /// Global remaining gas amount
     pub fn remaining_gas(&self) -> Word {
         self.registers[RegId::GGAS]
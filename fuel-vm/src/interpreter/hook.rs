@@ -0,0 +1,279 @@
+//! Instrumentation hooks called by the interpreter during the execution.
+//!
+//! See [`ExecutionHook`].
+
+use fuel_asm::{
+    Instruction,
+    RawInstruction,
+};
+use fuel_tx::Receipt;
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    call::CallFrame,
+    consts::VM_REGISTER_COUNT,
+};
+
+use super::{
+    Interpreter,
+    Memory,
+    MemoryInstance,
+};
+
+/// Instrumentation of the execution, e.g. tracers, gas meters or taint analysis.
+///
+/// The hook is a type parameter of the [`Interpreter`], so the calls are resolved
+/// statically, and [`NoopHook`] is optimized out completely. All methods are no-op
+/// by default, so the implementation only overrides the events it is interested in.
+///
+/// The hook receives a read-only view of the registers and the memory of the VM.
+pub trait ExecutionHook {
+    /// Whether the interpreter should call the hook at all. When `false`, the
+    /// interpreter also skips the bookkeeping required to detect the events.
+    const ENABLED: bool = true;
+
+    /// Called when the interpreter is initialized with a new transaction.
+    fn on_transaction_start(&mut self) {}
+
+    /// Called before the instruction is executed.
+    fn before_instruction(
+        &mut self,
+        _registers: &[Word; VM_REGISTER_COUNT],
+        _memory: &MemoryInstance,
+        _instruction: RawInstruction,
+    ) {
+    }
+
+    /// Called after the instruction is executed, even if the execution failed.
+    fn after_instruction(
+        &mut self,
+        _registers: &[Word; VM_REGISTER_COUNT],
+        _memory: &MemoryInstance,
+        _instruction: RawInstruction,
+    ) {
+    }
+
+    /// Called after `CALL` entered the context of the contract described by the
+    /// `frame`.
+    fn on_call(
+        &mut self,
+        _registers: &[Word; VM_REGISTER_COUNT],
+        _memory: &MemoryInstance,
+        _frame: &CallFrame,
+    ) {
+    }
+
+    /// Called after `RET` or `RETD` returned from the context of the contract
+    /// described by the `frame` to its caller.
+    fn on_return(
+        &mut self,
+        _registers: &[Word; VM_REGISTER_COUNT],
+        _memory: &MemoryInstance,
+        _frame: &CallFrame,
+    ) {
+    }
+
+    /// Called after the contract storage was accessed successfully.
+    fn on_storage_access(&mut self, _access: &StorageAccess) {}
+
+    /// Called after the receipt is emitted.
+    fn on_receipt(&mut self, _receipt: &Receipt) {}
+}
+
+/// Hook that does nothing. Used by default.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoopHook;
+
+impl ExecutionHook for NoopHook {
+    const ENABLED: bool = false;
+}
+
+/// The kind of the contract storage access.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StorageAccessKind {
+    /// `SRW` or `SRWQ`.
+    Read,
+    /// `SWW` or `SWWQ`.
+    Write,
+    /// `SCWQ`.
+    Clear,
+}
+
+/// Access to the slots of the contract storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StorageAccess {
+    /// The kind of the access.
+    pub kind: StorageAccessKind,
+    /// The contract that owns the storage.
+    pub contract_id: ContractId,
+    /// The key of the first slot.
+    pub key: Bytes32,
+    /// The number of consecutive slots starting from the `key`.
+    pub slots: Word,
+}
+
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
+    /// Replace the execution hook of the interpreter.
+    pub fn with_execution_hook<H>(self, hook: H) -> Interpreter<M, S, Tx, Ecal, H> {
+        let Interpreter {
+            registers,
+            memory,
+            frames,
+            receipts,
+            tx,
+            initial_balances,
+            input_contracts,
+            input_contracts_index_to_output_index,
+            storage,
+            debugger,
+            context,
+            balances,
+            profiler,
+            interpreter_params,
+            panic_context,
+            ecal_state,
+            hook: _,
+            instruction_cache,
+        } = self;
+
+        Interpreter {
+            registers,
+            memory,
+            frames,
+            receipts,
+            tx,
+            initial_balances,
+            input_contracts,
+            input_contracts_index_to_output_index,
+            storage,
+            debugger,
+            context,
+            balances,
+            profiler,
+            interpreter_params,
+            panic_context,
+            ecal_state,
+            hook,
+            instruction_cache,
+        }
+    }
+
+    /// Read access to the execution hook
+    pub fn execution_hook(&self) -> &Hook {
+        &self.hook
+    }
+
+    /// Write access to the execution hook
+    pub fn execution_hook_mut(&mut self) -> &mut Hook {
+        &mut self.hook
+    }
+}
+
+/// The state of the interpreter before the instruction, used to detect the events
+/// caused by it.
+pub(crate) struct HookCheckpoint {
+    receipts: usize,
+    frames: usize,
+    returning_frame: Option<CallFrame>,
+}
+
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
+where
+    M: Memory,
+    Hook: ExecutionHook,
+{
+    pub(crate) fn hook_before_instruction(
+        &mut self,
+        raw: RawInstruction,
+    ) -> Option<HookCheckpoint> {
+        if !Hook::ENABLED {
+            return None
+        }
+
+        self.hook
+            .before_instruction(&self.registers, self.memory.as_ref(), raw);
+
+        let returning_frame = match Instruction::try_from(raw) {
+            Ok(Instruction::RET(_) | Instruction::RETD(_)) => self.frames.last().cloned(),
+            _ => None,
+        };
+
+        Some(HookCheckpoint {
+            receipts: self.receipts.len(),
+            frames: self.frames.len(),
+            returning_frame,
+        })
+    }
+
+    pub(crate) fn hook_after_instruction(
+        &mut self,
+        raw: RawInstruction,
+        checkpoint: Option<HookCheckpoint>,
+    ) {
+        let Some(checkpoint) = checkpoint else { return };
+
+        let frames = self.frames.len();
+        if frames > checkpoint.frames {
+            if let Some(frame) = self.frames.last() {
+                self.hook
+                    .on_call(&self.registers, self.memory.as_ref(), frame);
+            }
+        } else if frames < checkpoint.frames {
+            if let Some(frame) = &checkpoint.returning_frame {
+                self.hook
+                    .on_return(&self.registers, self.memory.as_ref(), frame);
+            }
+        }
+
+        self.hook_receipts(checkpoint.receipts);
+        self.hook
+            .after_instruction(&self.registers, self.memory.as_ref(), raw);
+    }
+
+    /// Reports the receipts emitted since the `checkpoint` to the hook.
+    pub(crate) fn hook_receipts(&mut self, checkpoint: usize) {
+        if !Hook::ENABLED {
+            return;
+        }
+
+        for receipt in self.receipts.as_ref().iter().skip(checkpoint) {
+            self.hook.on_receipt(receipt);
+        }
+    }
+
+    /// Captures the storage access of the current contract. The key is read from the
+    /// memory at `key_pointer` before the instruction runs, since the instruction may
+    /// overwrite it. Invalid accesses are not captured, since they panic anyway.
+    pub(crate) fn storage_access(
+        &self,
+        kind: StorageAccessKind,
+        key_pointer: Word,
+        slots: Word,
+    ) -> Option<StorageAccess> {
+        if !Hook::ENABLED {
+            return None
+        }
+
+        let contract_id = self.internal_contract().ok()?;
+        let key = self.memory.as_ref().read_bytes(key_pointer).ok()?;
+
+        Some(StorageAccess {
+            kind,
+            contract_id,
+            key: Bytes32::new(key),
+            slots,
+        })
+    }
+
+    /// Reports the storage access captured by [`Self::storage_access`] to the hook.
+    /// Must only be called once the access succeeded.
+    pub(crate) fn hook_storage_access(&mut self, access: Option<StorageAccess>) {
+        if let Some(access) = access {
+            self.hook.on_storage_access(&access);
+        }
+    }
+}
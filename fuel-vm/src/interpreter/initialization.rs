@@ -1,5 +1,6 @@
 use super::{
     ExecutableTransaction,
+    ExecutionHook,
     InitialBalances,
     Interpreter,
     Memory,
@@ -29,11 +30,12 @@ use fuel_types::Word;
 
 use crate::interpreter::CheckedMetadata;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
+    Hook: ExecutionHook,
 {
     /// Initialize the VM with a given transaction
     fn init_inner(
@@ -77,6 +79,8 @@ where
 
         self.frames.clear();
         self.receipts.clear();
+        self.receipts
+            .set_max_receipts(self.interpreter_params.vm_params.max_receipts());
         self.memory_mut().reset();
 
         // Optimized for memset
@@ -122,15 +126,29 @@ where
 
         self.registers[RegId::SP] = self.registers[RegId::SSP];
 
+        // Applied after the initialization data is placed, so a transaction that
+        // doesn't fit the limit fails during the execution instead
+        let max_memory = self.interpreter_params.vm_params.max_memory();
+        self.memory_mut().set_memory_limit(max_memory);
+
+        if let Some(cache) = &mut self.instruction_cache {
+            cache.reset_transaction();
+        }
+
+        if Hook::ENABLED {
+            self.hook.on_transaction_start();
+        }
+
         Ok(())
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
+    Hook: ExecutionHook,
 {
     /// Initialize the VM for a predicate context
     pub fn init_predicate(
@@ -158,13 +176,14 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
     <S as InterpreterStorage>::DataError: From<S::DataError>,
     Tx: ExecutableTransaction,
     <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    Hook: ExecutionHook,
 {
     /// Initialize the VM with a given transaction, backed by a storage provider that
     /// allows execution of contract opcodes.
@@ -0,0 +1,226 @@
+//! Cache of the decoded instructions of the executed code.
+//!
+//! See [`InstructionCache`].
+
+use alloc::{
+    collections::BTreeMap,
+    sync::Arc,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    Instruction,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::field::Script;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use crate::{
+    call::CallFrame,
+    context::Context,
+};
+
+use super::{
+    ExecutableTransaction,
+    Interpreter,
+    Memory,
+    MemoryInstance,
+};
+
+/// The decoded instructions of the code region, indexed by the instruction offset.
+/// `None` marks the words that are not valid instructions, e.g. data sections.
+type DecodedCode = Arc<[Option<(RawInstruction, Instruction)>]>;
+
+/// Cache of the decoded instructions, used by [`Interpreter::execute`] instead of
+/// reading and decoding the instruction at `$pc` on every step.
+///
+/// The bytecode of the contracts is cached by the contract id, which commits to the
+/// code root, so the cache survives across transactions and can be moved between
+/// interpreters with [`Interpreter::take_instruction_cache`]. The script is cached
+/// only for the current transaction.
+///
+/// The code region of the memory is not writable by the instructions. If the memory is
+/// accessed with [`Interpreter::memory_mut`], e.g. by an ECAL handler or a debugger,
+/// the cache is bypassed until the next transaction, since the code could be modified.
+///
+/// The cache must not be shared between interpreters with different bytecode for the
+/// same contract id, e.g. with [`crate::storage::OverlayStorage`] replacing the code.
+#[derive(Debug, Clone, Default)]
+pub struct InstructionCache {
+    contracts: BTreeMap<ContractId, DecodedCode>,
+    script: Option<(Word, DecodedCode)>,
+    current: Option<LoadedCode>,
+    suspended: bool,
+}
+
+/// The code region of the current context.
+#[derive(Debug, Clone)]
+struct LoadedCode {
+    is: Word,
+    contract: Option<ContractId>,
+    code: DecodedCode,
+}
+
+impl InstructionCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of the contracts with the cached bytecode.
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    /// Returns `true` if no contracts are cached.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Returns `true` if the bytecode of the contract is cached.
+    pub fn contains(&self, contract_id: &ContractId) -> bool {
+        self.contracts.contains_key(contract_id)
+    }
+
+    /// Removes the bytecode of the contract from the cache.
+    pub fn remove(&mut self, contract_id: &ContractId) {
+        self.contracts.remove(contract_id);
+        let current = self
+            .current
+            .as_ref()
+            .and_then(|loaded| loaded.contract.as_ref());
+        if current == Some(contract_id) {
+            self.current = None;
+        }
+    }
+
+    /// Removes everything from the cache.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Drops the state of the previous transaction.
+    pub(crate) fn reset_transaction(&mut self) {
+        self.script = None;
+        self.current = None;
+        self.suspended = false;
+    }
+
+    /// Bypasses the cache until the next transaction.
+    pub(crate) fn suspend(&mut self) {
+        self.script = None;
+        self.current = None;
+        self.suspended = true;
+    }
+
+    fn decode(memory: &MemoryInstance, is: Word, len: usize) -> Option<DecodedCode> {
+        let bytes = memory.read(is, len).ok()?;
+        let code: Vec<_> = bytes
+            .chunks_exact(Instruction::SIZE)
+            .map(|chunk| {
+                let raw = RawInstruction::from_be_bytes(chunk.try_into().ok()?);
+                let instruction = Instruction::try_from(raw).ok()?;
+                Some((raw, instruction))
+            })
+            .collect();
+        Some(code.into())
+    }
+}
+
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook> {
+    /// Use the cache of the decoded instructions during the execution.
+    pub fn with_instruction_cache(mut self, cache: InstructionCache) -> Self {
+        self.instruction_cache = Some(cache);
+        self
+    }
+
+    /// The cache of the decoded instructions, if enabled.
+    pub fn instruction_cache(&self) -> Option<&InstructionCache> {
+        self.instruction_cache.as_ref()
+    }
+
+    /// Disables the cache of the decoded instructions and returns it, so it can be
+    /// reused by another interpreter.
+    pub fn take_instruction_cache(&mut self) -> Option<InstructionCache> {
+        self.instruction_cache.take()
+    }
+}
+
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
+where
+    M: Memory,
+    Tx: ExecutableTransaction,
+{
+    /// Returns the decoded instruction at `$pc` if it is cached.
+    pub(crate) fn cached_instruction(&mut self) -> Option<(RawInstruction, Instruction)> {
+        let cache = self.instruction_cache.as_mut()?;
+        if cache.suspended {
+            return None
+        }
+
+        let is = self.registers[RegId::IS];
+        let contract = self.frames.last().map(|frame| *frame.to());
+        let is_loaded = matches!(
+            &cache.current,
+            Some(loaded) if loaded.is == is && loaded.contract == contract
+        );
+
+        if !is_loaded {
+            let code = match (contract, self.frames.last()) {
+                (Some(contract), Some(frame)) => {
+                    // The code of the contract is located right after its call frame
+                    let code_start = self.registers[RegId::FP]
+                        .saturating_add(CallFrame::serialized_size() as Word);
+                    if is != code_start {
+                        return None
+                    }
+
+                    match cache.contracts.get(&contract) {
+                        Some(code) => code.clone(),
+                        None => {
+                            let code = InstructionCache::decode(
+                                self.memory.as_ref(),
+                                is,
+                                frame.code_size_padded(),
+                            )?;
+                            cache.contracts.insert(contract, code.clone());
+                            code
+                        }
+                    }
+                }
+                _ => {
+                    if !matches!(self.context, Context::Script { .. }) {
+                        return None
+                    }
+
+                    match &cache.script {
+                        Some((script_is, code)) if *script_is == is => code.clone(),
+                        _ => {
+                            let len = self.tx.as_script()?.script().len();
+                            let code =
+                                InstructionCache::decode(self.memory.as_ref(), is, len)?;
+                            cache.script = Some((is, code.clone()));
+                            code
+                        }
+                    }
+                }
+            };
+
+            cache.current = Some(LoadedCode { is, contract, code });
+        }
+
+        let loaded = cache.current.as_ref()?;
+        let offset = self.registers[RegId::PC].checked_sub(loaded.is)?;
+        if offset.checked_rem(Instruction::SIZE as Word)? != 0 {
+            return None
+        }
+        let index =
+            usize::try_from(offset.checked_div(Instruction::SIZE as Word)?).ok()?;
+
+        loaded.code.get(index).copied().flatten()
+    }
+}
@@ -7,7 +7,10 @@ use super::{
 use crate::{
     constraints::reg_key::*,
     consts::*,
-    error::SimpleResult,
+    error::{
+        PanicOrBug,
+        SimpleResult,
+    },
 };
 
 use fuel_asm::{
@@ -16,6 +19,7 @@ use fuel_asm::{
     PanicReason,
     RegId,
 };
+use fuel_tx::PanicContext;
 use fuel_types::{
     fmt_truncated_hex,
     RegisterId,
@@ -40,6 +44,21 @@ use alloc::{
     vec::Vec,
 };
 
+#[cfg(feature = "std")]
+use crate::constraints::InstructionLocation;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use sanitizer::MemorySanitizer;
+#[cfg(feature = "std")]
+pub use sanitizer::{
+    MemoryDiagnostic,
+    MemoryDiagnosticKind,
+};
+
+#[cfg(feature = "std")]
+mod sanitizer;
+
 #[cfg(test)]
 mod tests;
 
@@ -58,7 +77,7 @@ pub trait Memory: AsRef<MemoryInstance> + AsMut<MemoryInstance> {}
 impl<M> Memory for M where M: AsRef<MemoryInstance> + AsMut<MemoryInstance> {}
 
 /// The memory of the VM, represented as stack and heap.
-#[derive(Clone, Eq)]
+#[derive(Clone)]
 pub struct MemoryInstance {
     /// Stack. Grows upwards.
     stack: Vec<u8>,
@@ -67,6 +86,11 @@ pub struct MemoryInstance {
     /// Lowest allowed heap address, i.e. hp register value.
     /// This is needed since we can allocate extra heap for performance reasons.
     hp: usize,
+    /// Maximum total size of the stack and the heap.
+    memory_limit: usize,
+    /// Tracks the suspicious memory accesses, if enabled.
+    #[cfg(feature = "std")]
+    sanitizer: Option<Box<MemorySanitizer>>,
 }
 
 impl Default for MemoryInstance {
@@ -87,7 +111,8 @@ impl fmt::Debug for MemoryInstance {
 }
 
 impl PartialEq for MemoryInstance {
-    /// Equality comparison of the accessible memory.
+    /// Equality comparison of the accessible memory. The state of the sanitizer is
+    /// ignored.
     #[allow(clippy::arithmetic_side_effects)] // Safety: hp is kept valid everywhere
     fn eq(&self, other: &Self) -> bool {
         self.stack == other.stack && self.hp == other.hp && {
@@ -98,6 +123,8 @@ impl PartialEq for MemoryInstance {
     }
 }
 
+impl Eq for MemoryInstance {}
+
 impl AsRef<MemoryInstance> for MemoryInstance {
     fn as_ref(&self) -> &MemoryInstance {
         self
@@ -116,13 +143,50 @@ impl MemoryInstance {
             stack: Vec::new(),
             heap: Vec::new(),
             hp: MEM_SIZE,
+            memory_limit: MEM_SIZE,
+            #[cfg(feature = "std")]
+            sanitizer: None,
         }
     }
 
     /// Resets memory to initial state, keeping the original allocations.
+    ///
+    /// The memory limit is removed. The sanitizer stays enabled, but its state and
+    /// diagnostics are cleared.
     pub fn reset(&mut self) {
         self.stack.truncate(0);
         self.hp = MEM_SIZE;
+        self.memory_limit = MEM_SIZE;
+        #[cfg(feature = "std")]
+        if let Some(sanitizer) = &mut self.sanitizer {
+            sanitizer.reset();
+        }
+    }
+
+    /// Maximum total size of the stack and the heap, in bytes.
+    pub fn memory_limit(&self) -> Word {
+        self.memory_limit as Word
+    }
+
+    /// Limits the total size of the stack and the heap to `limit` bytes, at most
+    /// [`VM_MAX_RAM`]. The memory layout doesn't change: the heap still starts at the
+    /// end of the memory, but the stack and the heap can't grow past the limit.
+    ///
+    /// The memory used before setting the limit is kept.
+    pub fn set_memory_limit(&mut self, limit: Word) {
+        #[allow(clippy::cast_possible_truncation)] // Safety: MEM_SIZE is usize
+        let limit = limit.min(VM_MAX_RAM) as usize;
+        self.memory_limit = limit;
+    }
+
+    /// The highest address the stack can grow to with the heap starting at `hp`.
+    fn stack_limit(&self, hp: usize) -> usize {
+        hp.saturating_sub(MEM_SIZE.saturating_sub(self.memory_limit))
+    }
+
+    /// Number of bytes allocated for the stack and the heap.
+    pub fn allocated_size(&self) -> usize {
+        self.stack.capacity().saturating_add(self.heap.capacity())
     }
 
     /// Offset of the heap section
@@ -153,12 +217,18 @@ impl MemoryInstance {
         let new_sp = new_sp as usize;
 
         if new_sp > self.stack.len() {
-            if new_sp > self.hp {
+            if new_sp > self.stack_limit(self.hp) {
                 return Err(PanicReason::MemoryGrowthOverlap)
             }
 
             self.stack.resize(new_sp, 0);
         }
+
+        #[cfg(feature = "std")]
+        if let Some(sanitizer) = &mut self.sanitizer {
+            sanitizer.on_stack_growth(new_sp);
+        }
+
         Ok(())
     }
 
@@ -180,7 +250,7 @@ impl MemoryInstance {
             .checked_sub(amount)
             .ok_or(PanicReason::MemoryOverflow)?;
 
-        if (new_hp as Word) < *sp_reg {
+        if (self.stack_limit(new_hp) as Word) < *sp_reg {
             return Err(PanicReason::MemoryGrowthOverlap)
         }
 
@@ -206,11 +276,16 @@ impl MemoryInstance {
             self.heap[..prefix_zeroes].fill(0);
         }
 
+        #[cfg(feature = "std")]
+        if let Some(sanitizer) = &mut self.sanitizer {
+            sanitizer.on_allocation(new_hp..self.hp);
+        }
+
         self.hp = new_hp;
         *hp_reg = new_hp as Word;
 
         // If heap enters region where stack has been, truncate the stack
-        self.stack.truncate(new_hp);
+        self.stack.truncate(self.stack_limit(new_hp));
 
         Ok(())
     }
@@ -252,6 +327,11 @@ impl MemoryInstance {
     ) -> Result<&[u8], PanicReason> {
         let range = self.verify(addr, count)?;
 
+        #[cfg(feature = "std")]
+        if let Some(sanitizer) = &self.sanitizer {
+            sanitizer.on_read(self.hp, &range.usizes());
+        }
+
         if range.end() <= self.stack.len() {
             Ok(&self.stack[range.usizes()])
         } else if range.start() >= self.heap_offset() {
@@ -282,6 +362,12 @@ impl MemoryInstance {
         len: B,
     ) -> Result<&mut [u8], PanicReason> {
         let range = self.verify(addr, len)?;
+
+        #[cfg(feature = "std")]
+        if let Some(sanitizer) = &mut self.sanitizer {
+            sanitizer.on_write(self.hp, &range.usizes());
+        }
+
         if range.end() <= self.stack.len() {
             Ok(&mut self.stack[range.usizes()])
         } else if range.start() >= self.heap_offset() {
@@ -347,6 +433,64 @@ impl MemoryInstance {
         Ok(())
     }
 
+    /// Enables the sanitizer, which reports the suspicious memory accesses without
+    /// changing the behavior of the VM:
+    /// - reads of the heap bytes that were allocated but never written,
+    /// - reads past the end of the heap allocation or above `$sp`, which succeed only
+    ///   because the neighbouring memory is accessible,
+    /// - writes bypassing the ownership checks into the stack of a caller.
+    ///
+    /// The tracking makes the execution slower, so it is meant for the development
+    /// tooling. See [`Self::memory_diagnostics`] for the results.
+    #[cfg(feature = "std")]
+    pub fn enable_sanitizer(&mut self) {
+        self.sanitizer.get_or_insert_with(Default::default);
+    }
+
+    /// Disables the sanitizer, dropping its diagnostics.
+    #[cfg(feature = "std")]
+    pub fn disable_sanitizer(&mut self) {
+        self.sanitizer = None;
+    }
+
+    /// Returns `true` if the sanitizer is enabled.
+    #[cfg(feature = "std")]
+    pub fn is_sanitizer_enabled(&self) -> bool {
+        self.sanitizer.is_some()
+    }
+
+    /// The suspicious memory accesses since the last reset, in the order they were
+    /// found. Repeated accesses of the same kind to the same range by the same
+    /// instruction are reported once.
+    #[cfg(feature = "std")]
+    pub fn memory_diagnostics(&self) -> Vec<MemoryDiagnostic> {
+        self.sanitizer
+            .as_ref()
+            .map(|sanitizer| sanitizer.diagnostics())
+            .unwrap_or_default()
+    }
+
+    /// Returns and clears the suspicious memory accesses found so far.
+    #[cfg(feature = "std")]
+    pub fn take_memory_diagnostics(&mut self) -> Vec<MemoryDiagnostic> {
+        self.sanitizer
+            .as_mut()
+            .map(|sanitizer| sanitizer.take_diagnostics())
+            .unwrap_or_default()
+    }
+
+    /// Attributes the following memory accesses to the instruction at `location`.
+    #[cfg(feature = "std")]
+    pub(crate) fn sanitizer_enter_instruction(
+        &mut self,
+        location: InstructionLocation,
+        registers: &[Word; VM_REGISTER_COUNT],
+    ) {
+        if let Some(sanitizer) = &mut self.sanitizer {
+            sanitizer.enter_instruction(location, registers);
+        }
+    }
+
     /// Memory access to the raw stack buffer.
     /// Note that for efficiency reasons this might not match sp value.
     #[cfg(any(test, feature = "test-helpers"))]
@@ -494,7 +638,7 @@ impl MemoryRange {
     }
 }
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
 {
@@ -503,6 +647,36 @@ where
         OwnershipRegisters::new(self)
     }
 
+    /// Enables the memory sanitizer, see [`MemoryInstance::enable_sanitizer`].
+    #[cfg(feature = "std")]
+    pub fn with_memory_sanitizer(mut self) -> Self {
+        self.memory.as_mut().enable_sanitizer();
+        self
+    }
+
+    /// The suspicious memory accesses of the current transaction found by the
+    /// memory sanitizer. Empty if the sanitizer is disabled.
+    #[cfg(feature = "std")]
+    pub fn memory_diagnostics(&self) -> Vec<MemoryDiagnostic> {
+        self.memory.as_ref().memory_diagnostics()
+    }
+
+    /// Attributes the memory accesses to the current instruction.
+    #[cfg(feature = "std")]
+    pub(crate) fn sanitizer_enter_instruction(&mut self) {
+        if !self.memory.as_ref().is_sanitizer_enabled() {
+            return
+        }
+
+        let location = InstructionLocation {
+            context: self.frames.last().map(|frame| *frame.to()),
+            offset: self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]),
+        };
+        self.memory
+            .as_mut()
+            .sanitizer_enter_instruction(location, &self.registers);
+    }
+
     pub(crate) fn stack_pointer_overflow<F>(&mut self, f: F, v: Word) -> SimpleResult<()>
     where
         F: FnOnce(Word, Word) -> (Word, bool),
@@ -578,7 +752,9 @@ where
     ) -> SimpleResult<()> {
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(ra)?];
-        load_byte(self.memory.as_ref(), pc, result, b, c)
+        let outcome = load_byte(self.memory.as_ref(), pc, result, b, c);
+        self.set_memory_panic_context(&outcome, &[(b.saturating_add(c), 1, false)]);
+        outcome
     }
 
     pub(crate) fn load_word(
@@ -589,31 +765,39 @@ where
     ) -> SimpleResult<()> {
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(ra)?];
-        load_word(self.memory.as_ref(), pc, result, b, c)
+        let outcome = load_word(self.memory.as_ref(), pc, result, b, c);
+        let addr = b.saturating_add(word_offset(c));
+        self.set_memory_panic_context(&outcome, &[(addr, WORD_SIZE as Word, false)]);
+        outcome
     }
 
     pub(crate) fn store_byte(&mut self, a: Word, b: Word, c: Word) -> SimpleResult<()> {
         let owner = self.ownership_registers();
-        store_byte(
+        let outcome = store_byte(
             self.memory.as_mut(),
             owner,
             self.registers.pc_mut(),
             a,
             b,
             c,
-        )
+        );
+        self.set_memory_panic_context(&outcome, &[(a.saturating_add(c), 1, true)]);
+        outcome
     }
 
     pub(crate) fn store_word(&mut self, a: Word, b: Word, c: Imm12) -> SimpleResult<()> {
         let owner = self.ownership_registers();
-        store_word(
+        let outcome = store_word(
             self.memory.as_mut(),
             owner,
             self.registers.pc_mut(),
             a,
             b,
             c,
-        )
+        );
+        let addr = a.saturating_add(word_offset(c));
+        self.set_memory_panic_context(&outcome, &[(addr, WORD_SIZE as Word, true)]);
+        outcome
     }
 
     /// Expand heap by `amount` bytes.
@@ -631,19 +815,24 @@ where
 
     pub(crate) fn memclear(&mut self, a: Word, b: Word) -> SimpleResult<()> {
         let owner = self.ownership_registers();
-        memclear(self.memory.as_mut(), owner, self.registers.pc_mut(), a, b)
+        let outcome =
+            memclear(self.memory.as_mut(), owner, self.registers.pc_mut(), a, b);
+        self.set_memory_panic_context(&outcome, &[(a, b, true)]);
+        outcome
     }
 
     pub(crate) fn memcopy(&mut self, a: Word, b: Word, c: Word) -> SimpleResult<()> {
         let owner = self.ownership_registers();
-        memcopy(
+        let outcome = memcopy(
             self.memory.as_mut(),
             owner,
             self.registers.pc_mut(),
             a,
             b,
             c,
-        )
+        );
+        self.set_memory_panic_context(&outcome, &[(a, c, true), (b, c, false)]);
+        outcome
     }
 
     pub(crate) fn memeq(
@@ -655,8 +844,51 @@ where
     ) -> SimpleResult<()> {
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(ra)?];
-        memeq(self.memory.as_mut(), result, pc, b, c, d)
+        let outcome = memeq(self.memory.as_mut(), result, pc, b, c, d);
+        self.set_memory_panic_context(&outcome, &[(b, d, false), (c, d, false)]);
+        outcome
     }
+
+    /// Sets the panic context to the first of the accessed `(start, len, is_write)`
+    /// ranges that fails the memory checks, if the instruction panicked with
+    /// `MemoryOverflow` or `MemoryOwnership`.
+    fn set_memory_panic_context(
+        &mut self,
+        outcome: &SimpleResult<()>,
+        accesses: &[(Word, Word, bool)],
+    ) {
+        let reason = match outcome {
+            Err(PanicOrBug::Panic(
+                reason @ (PanicReason::MemoryOverflow | PanicReason::MemoryOwnership),
+            )) => *reason,
+            _ => return,
+        };
+        let owner = self.ownership_registers();
+        let memory = self.memory.as_ref();
+        let faulty = accesses.iter().find(|(start, len, is_write)| {
+            match memory.verify(*start, *len) {
+                Ok(range) => {
+                    reason == PanicReason::MemoryOwnership
+                        && *is_write
+                        && owner.verify_ownership(&range).is_err()
+                }
+                Err(err) => err == reason,
+            }
+        });
+        if let Some((start, len, _)) = faulty {
+            self.panic_context = PanicContext::MemoryRange {
+                start: *start,
+                len: *len,
+            };
+        }
+    }
+}
+
+/// The byte offset of the word-sized immediate of `LW` and `SW`.
+fn word_offset(imm: Imm12) -> Word {
+    u64::from(imm)
+        .checked_mul(WORD_SIZE as u64)
+        .expect("u12 * 8 cannot overflow a Word")
 }
 
 /// Update stack pointer, checking for validity first.
@@ -902,7 +1134,9 @@ pub struct OwnershipRegisters {
 }
 
 impl OwnershipRegisters {
-    pub(crate) fn new<M, S, Tx, Ecal>(vm: &Interpreter<M, S, Tx, Ecal>) -> Self {
+    pub(crate) fn new<M, S, Tx, Ecal, Hook>(
+        vm: &Interpreter<M, S, Tx, Ecal, Hook>,
+    ) -> Self {
         let prev_hp = vm
             .frames
             .last()
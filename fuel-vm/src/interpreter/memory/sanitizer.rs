@@ -0,0 +1,215 @@
+//! Opt-in tracking of the suspicious memory accesses.
+//!
+//! See [`MemoryInstance::enable_sanitizer`].
+
+use core::ops::Range;
+use std::{
+    collections::HashSet,
+    sync::Mutex,
+};
+
+use fuel_asm::RegId;
+use fuel_types::Word;
+
+use crate::{
+    constraints::InstructionLocation,
+    consts::{
+        MEM_SIZE,
+        VM_REGISTER_COUNT,
+    },
+};
+
+#[cfg(doc)]
+use super::MemoryInstance;
+
+/// The kind of the suspicious memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryDiagnosticKind {
+    /// Read of the heap bytes that were allocated with `ALOC`, but never written.
+    /// The VM zeroes the allocations, so the read succeeds.
+    UninitializedRead,
+    /// Read beyond the region it starts in: past the end of the heap allocation
+    /// into the next allocation, or above `$sp` into the stack that was already
+    /// freed. The read succeeds only because the neighbouring memory is accessible.
+    ReadPastAllocation,
+    /// Write bypassing the ownership checks into the stack of a caller, e.g. by an
+    /// ECAL handler using [`MemoryInstance::write_noownerchecks`].
+    CallerStackWrite,
+}
+
+/// Suspicious memory access found by the sanitizer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryDiagnostic {
+    /// The kind of the access.
+    pub kind: MemoryDiagnosticKind,
+    /// Location of the instruction doing the access. `None` if the memory was
+    /// accessed before the first instruction.
+    pub location: Option<InstructionLocation>,
+    /// The accessed memory range.
+    pub range: Range<Word>,
+}
+
+/// The registers of the current instruction.
+#[derive(Debug, Clone, Copy)]
+struct InstructionState {
+    location: InstructionLocation,
+    /// Top of the stack, including the growth during the instruction.
+    sp: usize,
+    fp: usize,
+}
+
+#[derive(Debug, Default)]
+struct Diagnostics {
+    list: Vec<MemoryDiagnostic>,
+    seen: HashSet<MemoryDiagnostic>,
+}
+
+/// Tracks the initialized heap bytes, the heap allocations and the stack of the
+/// current instruction to find the suspicious memory accesses.
+#[derive(Debug, Default)]
+pub(super) struct MemorySanitizer {
+    /// The written heap bytes, indexed by the distance from the end of the memory.
+    initialized: Vec<bool>,
+    /// The heap allocations, from the oldest to the newest one.
+    allocations: Vec<Range<usize>>,
+    /// Start of the stack of the script, i.e. `$ssp` of the first instruction.
+    stack_base: Option<usize>,
+    current: Option<InstructionState>,
+    /// Reads are done through the shared reference, so the diagnostics are behind
+    /// the lock.
+    diagnostics: Mutex<Diagnostics>,
+}
+
+impl Clone for MemorySanitizer {
+    fn clone(&self) -> Self {
+        let diagnostics = self.lock();
+        Self {
+            initialized: self.initialized.clone(),
+            allocations: self.allocations.clone(),
+            stack_base: self.stack_base,
+            current: self.current,
+            diagnostics: Mutex::new(Diagnostics {
+                list: diagnostics.list.clone(),
+                seen: diagnostics.seen.clone(),
+            }),
+        }
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)] // Safety: the ranges are verified memory ranges
+impl MemorySanitizer {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Diagnostics> {
+        // The diagnostics are always consistent, so the poisoning can be ignored
+        self.diagnostics
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn report(&self, kind: MemoryDiagnosticKind, range: &Range<usize>) {
+        let diagnostic = MemoryDiagnostic {
+            kind,
+            location: self.current.map(|current| current.location),
+            range: range.start as Word..range.end as Word,
+        };
+        let mut diagnostics = self.lock();
+        if diagnostics.seen.insert(diagnostic.clone()) {
+            diagnostics.list.push(diagnostic);
+        }
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.initialized.clear();
+        self.allocations.clear();
+        self.stack_base = None;
+        self.current = None;
+        *self.lock() = Diagnostics::default();
+    }
+
+    pub(super) fn diagnostics(&self) -> Vec<MemoryDiagnostic> {
+        self.lock().list.clone()
+    }
+
+    pub(super) fn take_diagnostics(&mut self) -> Vec<MemoryDiagnostic> {
+        core::mem::take(&mut *self.lock()).list
+    }
+
+    pub(super) fn enter_instruction(
+        &mut self,
+        location: InstructionLocation,
+        registers: &[Word; VM_REGISTER_COUNT],
+    ) {
+        let register = |id: RegId| usize::try_from(registers[id]).unwrap_or(usize::MAX);
+        self.stack_base.get_or_insert(register(RegId::SSP));
+        self.current = Some(InstructionState {
+            location,
+            sp: register(RegId::SP),
+            fp: register(RegId::FP),
+        });
+    }
+
+    pub(super) fn on_stack_growth(&mut self, new_sp: usize) {
+        if let Some(current) = &mut self.current {
+            current.sp = current.sp.max(new_sp);
+        }
+    }
+
+    pub(super) fn on_allocation(&mut self, allocation: Range<usize>) {
+        if !allocation.is_empty() {
+            self.allocations.push(allocation);
+        }
+    }
+
+    /// Index range of the heap `range` in `initialized`.
+    fn heap_indices(range: &Range<usize>) -> Range<usize> {
+        MEM_SIZE - range.end..MEM_SIZE - range.start
+    }
+
+    pub(super) fn on_read(&self, hp: usize, range: &Range<usize>) {
+        if range.is_empty() {
+            return
+        }
+
+        if range.start >= hp {
+            let uninitialized = Self::heap_indices(range)
+                .any(|index| !self.initialized.get(index).copied().unwrap_or(false));
+            if uninitialized {
+                self.report(MemoryDiagnosticKind::UninitializedRead, range);
+            }
+
+            // The allocations grow downwards, so their starts are decreasing
+            let index = self
+                .allocations
+                .partition_point(|allocation| allocation.start > range.start);
+            if let Some(allocation) = self.allocations.get(index) {
+                if range.end > allocation.end {
+                    self.report(MemoryDiagnosticKind::ReadPastAllocation, range);
+                }
+            }
+        } else if let Some(current) = &self.current {
+            if range.end > current.sp {
+                self.report(MemoryDiagnosticKind::ReadPastAllocation, range);
+            }
+        }
+    }
+
+    pub(super) fn on_write(&mut self, hp: usize, range: &Range<usize>) {
+        if range.is_empty() {
+            return
+        }
+
+        if range.start >= hp {
+            let indices = Self::heap_indices(range);
+            if self.initialized.len() < indices.end {
+                self.initialized.resize(indices.end, false);
+            }
+            self.initialized[indices].fill(true);
+        } else if let (Some(current), Some(stack_base)) = (&self.current, self.stack_base)
+        {
+            // The frame of the current call starts at `$fp`, everything between the
+            // stack of the script and it belongs to the callers
+            if current.fp != 0 && range.start < current.fp && range.end > stack_base {
+                self.report(MemoryDiagnosticKind::CallerStackWrite, range);
+            }
+        }
+    }
+}
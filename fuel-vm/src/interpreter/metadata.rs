@@ -46,7 +46,7 @@ use fuel_types::{
 #[cfg(test)]
 mod tests;
 
-impl<M, S, Tx, Ecal> Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Interpreter<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     Tx: ExecutableTransaction,
@@ -189,6 +189,14 @@ impl<Tx> GTFInput<'_, Tx> {
                 .policies()
                 .get(PolicyType::MaxFee)
                 .ok_or(PanicReason::PolicyIsNotSet)?,
+            GTFArgs::PolicyExpiration => tx
+                .policies()
+                .get(PolicyType::Expiration)
+                .ok_or(PanicReason::PolicyIsNotSet)?,
+            GTFArgs::PolicyOwner => tx
+                .policies()
+                .get(PolicyType::Owner)
+                .ok_or(PanicReason::PolicyIsNotSet)?,
             GTFArgs::ScriptInputsCount | GTFArgs::CreateInputsCount => {
                 tx.inputs().len() as Word
             }
@@ -6,10 +6,14 @@ use core::{
 use fuel_asm::PanicReason;
 
 use fuel_merkle::binary::root_calculator::MerkleRootCalculator as MerkleTree;
-use fuel_tx::Receipt;
+use fuel_tx::{
+    Receipt,
+    VmParameters,
+};
 use fuel_types::{
     canonical::Serialize,
     Bytes32,
+    Word,
 };
 
 use crate::{
@@ -20,11 +24,25 @@ use crate::{
     },
 };
 
+#[allow(clippy::cast_possible_truncation)] // Safety: the minimum is small
+const MIN_RECEIPTS: usize = VmParameters::MIN_RECEIPTS as usize;
+
 /// Receipts and the associated Merkle tree
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct ReceiptsCtx {
     receipts: Vec<Receipt>,
     receipts_tree: MerkleTree,
+    max_receipts: usize,
+}
+
+impl Default for ReceiptsCtx {
+    fn default() -> Self {
+        Self {
+            receipts: Vec::new(),
+            receipts_tree: MerkleTree::new(),
+            max_receipts: Self::MAX_RECEIPTS,
+        }
+    }
 }
 
 impl ReceiptsCtx {
@@ -35,15 +53,16 @@ impl ReceiptsCtx {
     /// Add a new receipt, updating the Merkle tree as well.
     /// Returns a panic if the context is full.
     pub fn push(&mut self, receipt: Receipt) -> SimpleResult<()> {
-        if self.receipts.len() == Self::MAX_RECEIPTS {
+        if self.receipts.len() >= self.max_receipts {
             return Err(Bug::new(BugVariant::ReceiptsCtxFull).into())
         }
 
         // Last two slots can be only used for ending the script,
         // with a script result optinally preceded by a panic
-        if (self.receipts.len() == Self::MAX_RECEIPTS - 1
+        #[allow(clippy::arithmetic_side_effects)] // Safety: at least two receipts
+        if (self.receipts.len() == self.max_receipts - 1
             && !matches!(receipt, Receipt::ScriptResult { .. }))
-            || (self.receipts.len() == Self::MAX_RECEIPTS - 2
+            || (self.receipts.len() == self.max_receipts - 2
                 && !matches!(
                     receipt,
                     Receipt::ScriptResult { .. } | Receipt::Panic { .. }
@@ -57,6 +76,19 @@ impl ReceiptsCtx {
         Ok(())
     }
 
+    /// The maximum number of receipts in this context.
+    pub fn max_receipts(&self) -> usize {
+        self.max_receipts
+    }
+
+    /// Sets the maximum number of receipts in this context, clamped to
+    /// [`VmParameters::MIN_RECEIPTS`]`..=`[`Self::MAX_RECEIPTS`].
+    pub fn set_max_receipts(&mut self, max_receipts: Word) {
+        self.max_receipts = usize::try_from(max_receipts)
+            .unwrap_or(usize::MAX)
+            .clamp(MIN_RECEIPTS, Self::MAX_RECEIPTS);
+    }
+
     /// Reset the context to an empty state
     pub fn clear(&mut self) {
         self.receipts_tree = MerkleTree::new();
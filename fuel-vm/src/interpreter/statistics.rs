@@ -0,0 +1,204 @@
+//! Counters of the execution collected by an [`ExecutionHook`].
+//!
+//! See [`ExecutionStatistics`].
+
+use fuel_asm::{
+    Instruction,
+    Opcode,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::Receipt;
+use fuel_types::Word;
+use hashbrown::HashMap;
+
+use crate::{
+    call::CallFrame,
+    consts::{
+        VM_MAX_RAM,
+        VM_REGISTER_COUNT,
+    },
+};
+
+use super::{
+    ExecutionHook,
+    MemoryInstance,
+    StorageAccess,
+    StorageAccessKind,
+};
+
+/// Cheap counters of the execution of a transaction, e.g. to tune the gas costs or to
+/// detect pathological transactions.
+///
+/// The statistics are collected when it is used as the [`ExecutionHook`] of the
+/// interpreter, and are reset when a new transaction is initialized.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecutionStatistics {
+    instructions: HashMap<Opcode, u64>,
+    calls: u64,
+    call_depth: u64,
+    max_call_depth: u64,
+    storage_slots_read: u64,
+    storage_slots_written: u64,
+    bytes_copied: u64,
+    peak_stack: Word,
+    peak_heap: Word,
+    receipts: u64,
+    /// Bytes copied by the current instruction, counted if it succeeds
+    pending_copy: Option<(Word, Word)>,
+}
+
+impl ExecutionStatistics {
+    /// Create empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of the executed instructions per opcode, including the instruction
+    /// that caused a panic.
+    pub fn instructions(&self) -> &HashMap<Opcode, u64> {
+        &self.instructions
+    }
+
+    /// Total number of the executed instructions.
+    pub fn total_instructions(&self) -> u64 {
+        self.instructions
+            .values()
+            .fold(0, |total, count| total.saturating_add(*count))
+    }
+
+    /// Number of the contract calls.
+    pub const fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Maximum depth of the nested contract calls. `0` if no contracts were called.
+    pub const fn max_call_depth(&self) -> u64 {
+        self.max_call_depth
+    }
+
+    /// Number of the contract storage slots read successfully.
+    pub const fn storage_slots_read(&self) -> u64 {
+        self.storage_slots_read
+    }
+
+    /// Number of the contract storage slots written or cleared successfully.
+    pub const fn storage_slots_written(&self) -> u64 {
+        self.storage_slots_written
+    }
+
+    /// Number of bytes copied by `MCP`, `MCPI`, `CCP` and `LDC`.
+    pub const fn bytes_copied(&self) -> u64 {
+        self.bytes_copied
+    }
+
+    /// Peak size of the stack in bytes, i.e. the maximum of `$sp`. It includes the
+    /// transaction and the call frames.
+    pub const fn peak_stack(&self) -> Word {
+        self.peak_stack
+    }
+
+    /// Peak size of the heap in bytes, i.e. `VM_MAX_RAM` minus the minimum of `$hp`.
+    pub const fn peak_heap(&self) -> Word {
+        self.peak_heap
+    }
+
+    /// Number of the emitted receipts.
+    pub const fn receipts(&self) -> u64 {
+        self.receipts
+    }
+
+    /// Resets all counters.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn update_memory_peaks(&mut self, registers: &[Word; VM_REGISTER_COUNT]) {
+        self.peak_stack = self.peak_stack.max(registers[RegId::SP]);
+        self.peak_heap = self
+            .peak_heap
+            .max(VM_MAX_RAM.saturating_sub(registers[RegId::HP]));
+    }
+}
+
+impl ExecutionHook for ExecutionStatistics {
+    fn on_transaction_start(&mut self) {
+        self.reset();
+    }
+
+    fn before_instruction(
+        &mut self,
+        registers: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        instruction: RawInstruction,
+    ) {
+        let Ok(instruction) = Instruction::try_from(instruction) else {
+            return
+        };
+
+        let count = self.instructions.entry(instruction.opcode()).or_default();
+        *count = count.saturating_add(1);
+
+        let copied = match instruction {
+            Instruction::MCP(mcp) => Some(registers[mcp.unpack().2]),
+            Instruction::MCPI(mcpi) => Some(mcpi.unpack().2.into()),
+            Instruction::CCP(ccp) => Some(registers[ccp.unpack().3]),
+            Instruction::LDC(ldc) => Some(registers[ldc.unpack().2]),
+            _ => None,
+        };
+        self.pending_copy = copied.map(|bytes| (registers[RegId::PC], bytes));
+    }
+
+    fn after_instruction(
+        &mut self,
+        registers: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        _: RawInstruction,
+    ) {
+        // The copy instructions don't jump, so the `$pc` only changes on success
+        if let Some((pc, bytes)) = self.pending_copy.take() {
+            if registers[RegId::PC] != pc {
+                self.bytes_copied = self.bytes_copied.saturating_add(bytes);
+            }
+        }
+
+        self.update_memory_peaks(registers);
+    }
+
+    fn on_call(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        _: &CallFrame,
+    ) {
+        self.calls = self.calls.saturating_add(1);
+        self.call_depth = self.call_depth.saturating_add(1);
+        self.max_call_depth = self.max_call_depth.max(self.call_depth);
+    }
+
+    fn on_return(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        _: &CallFrame,
+    ) {
+        self.call_depth = self.call_depth.saturating_sub(1);
+    }
+
+    fn on_storage_access(&mut self, access: &StorageAccess) {
+        match access.kind {
+            StorageAccessKind::Read => {
+                self.storage_slots_read =
+                    self.storage_slots_read.saturating_add(access.slots);
+            }
+            StorageAccessKind::Write | StorageAccessKind::Clear => {
+                self.storage_slots_written =
+                    self.storage_slots_written.saturating_add(access.slots);
+            }
+        }
+    }
+
+    fn on_receipt(&mut self, _: &Receipt) {
+        self.receipts = self.receipts.saturating_add(1);
+    }
+}
@@ -23,7 +23,11 @@ extern crate libm as _; // Not needed with stdlib
 use criterion as _;
 
 pub mod backtrace;
+pub mod block_executor;
+#[cfg(feature = "calibration")]
+pub mod calibration;
 pub mod call;
+pub mod call_tree;
 pub mod checked_transaction;
 pub mod constraints;
 pub mod consts;
@@ -34,12 +38,16 @@ pub mod error;
 pub mod interpreter;
 #[cfg(feature = "test-helpers")]
 pub mod memory_client;
+pub mod message_outbox;
 pub mod pool;
 pub mod predicate;
+#[cfg(feature = "source-map")]
+pub mod source_map;
 pub mod state;
 pub mod storage;
 pub mod transactor;
 pub mod util;
+pub mod utxo;
 
 #[cfg(feature = "profile-any")]
 pub mod profiler;
@@ -165,6 +173,7 @@ pub mod prelude {
     pub use crate::state::{
         Breakpoint,
         DebugEval,
+        ExecutionBudget,
     };
 
     #[cfg(any(test, feature = "test-helpers"))]
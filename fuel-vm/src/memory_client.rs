@@ -10,12 +10,22 @@ use crate::{
         Memory,
         NotSupportedEcal,
     },
-    state::StateTransitionRef,
-    storage::MemoryStorage,
-    transactor::Transactor,
+    state::{
+        StateTransition,
+        StateTransitionRef,
+    },
+    storage::{
+        MemoryStorage,
+        StateOverrides,
+    },
+    transactor::{
+        ScriptGasEstimation,
+        Transactor,
+    },
 };
 use core::convert::Infallible;
 use fuel_tx::{
+    ConsensusParameters,
     Create,
     FeeParameters,
     GasCosts,
@@ -144,6 +154,30 @@ where
         self.transactor.receipts().unwrap_or_default()
     }
 
+    /// Finds the minimal `script_gas_limit` for the transaction without modifying
+    /// the storage.
+    ///
+    /// See [`Transactor::estimate_script_gas`].
+    pub fn estimate_script_gas(
+        &mut self,
+        tx: Checked<Script>,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<ScriptGasEstimation, InterpreterError<Infallible>> {
+        self.transactor.estimate_script_gas(tx, consensus_params)
+    }
+
+    /// Executes the script with the state `overrides` applied without modifying the
+    /// storage.
+    ///
+    /// See [`Transactor::dry_run_with_overrides`].
+    pub fn dry_run_with_overrides(
+        &mut self,
+        tx: Checked<Script>,
+        overrides: &StateOverrides,
+    ) -> Result<StateTransition<Script>, InterpreterError<Infallible>> {
+        self.transactor.dry_run_with_overrides(tx, overrides)
+    }
+
     /// Persist the changes caused by [`Self::transact`].
     pub fn persist(&mut self) {
         self.as_mut().persist();
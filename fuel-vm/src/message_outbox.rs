@@ -0,0 +1,157 @@
+//! Merkle tree of the messages sent by a block and the proofs of their inclusion.
+//!
+//! The leaves of the tree are the ids of the messages in the order of the
+//! `Receipt::MessageOut` receipts of the block. The id is computed like the id of the
+//! message input spending it on the other side of the bridge, so the proof can be
+//! verified with the fields of the message only.
+
+use alloc::vec::Vec;
+
+use fuel_merkle::binary::{
+    self,
+    in_memory::MerkleTree,
+};
+use fuel_tx::{
+    Input,
+    Receipt,
+};
+use fuel_types::{
+    Address,
+    Bytes32,
+    MessageId,
+    Nonce,
+    Word,
+};
+
+/// Error building the outbox from the receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum MessageOutboxError {
+    /// The data of the message isn't in the receipt, so its id can't be computed.
+    /// The data is lost when the receipt is deserialized.
+    #[display(fmt = "The data of the message with the nonce {} is missing", _0)]
+    MissingData(Nonce),
+}
+
+/// The Merkle tree of the messages sent by a block.
+#[derive(Debug, Clone, Default)]
+pub struct MessageOutbox {
+    message_ids: Vec<MessageId>,
+    tree: MerkleTree,
+}
+
+impl MessageOutbox {
+    /// Create an empty outbox.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the outbox of the `Receipt::MessageOut` receipts in the order of the
+    /// iterator. The other receipts are ignored.
+    pub fn from_receipts<'a, I>(receipts: I) -> Result<Self, MessageOutboxError>
+    where
+        I: IntoIterator<Item = &'a Receipt>,
+    {
+        let mut outbox = Self::new();
+        for receipt in receipts {
+            if let Receipt::MessageOut { nonce, .. } = receipt {
+                let message_id = receipt
+                    .message_id()
+                    .ok_or(MessageOutboxError::MissingData(*nonce))?;
+                outbox.push(message_id);
+            }
+        }
+        Ok(outbox)
+    }
+
+    /// Append the message to the outbox.
+    pub fn push(&mut self, message_id: MessageId) {
+        self.tree.push(message_id.as_ref());
+        self.message_ids.push(message_id);
+    }
+
+    /// The ids of the messages, in the order of the leaves.
+    pub fn message_ids(&self) -> &[MessageId] {
+        &self.message_ids
+    }
+
+    /// The number of messages in the outbox.
+    pub fn len(&self) -> usize {
+        self.message_ids.len()
+    }
+
+    /// Returns `true` if the block didn't send messages.
+    pub fn is_empty(&self) -> bool {
+        self.message_ids.is_empty()
+    }
+
+    /// The Merkle root of the outbox.
+    pub fn root(&self) -> Bytes32 {
+        self.tree.root().into()
+    }
+
+    /// Prove the inclusion of the message into the outbox.
+    ///
+    /// Returns `None` if the outbox doesn't contain the message. If the message is
+    /// sent several times, the proof is for its first occurrence.
+    pub fn prove(&self, message_id: &MessageId) -> Option<MessageProof> {
+        let index = self.message_ids.iter().position(|id| id == message_id)?;
+        self.prove_index(index)
+    }
+
+    /// Prove the inclusion of the message at the `index` of the outbox.
+    pub fn prove_index(&self, index: usize) -> Option<MessageProof> {
+        let message_id = *self.message_ids.get(index)?;
+        let (_, proof_set) = self.tree.prove(index as u64)?;
+
+        Some(MessageProof {
+            message_id,
+            proof_index: index as u64,
+            proof_set: proof_set.into_iter().map(Into::into).collect(),
+            leaves_count: self.message_ids.len() as u64,
+        })
+    }
+}
+
+/// Proof of the inclusion of the message into the outbox of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageProof {
+    /// The id of the proven message.
+    pub message_id: MessageId,
+    /// The index of the message in the outbox.
+    pub proof_index: u64,
+    /// The sibling hashes from the leaf to the root.
+    pub proof_set: Vec<Bytes32>,
+    /// The number of messages in the outbox.
+    pub leaves_count: u64,
+}
+
+impl MessageProof {
+    /// Verify the proof against the outbox `root`.
+    pub fn verify(&self, root: &Bytes32) -> bool {
+        let proof_set: Vec<_> = self.proof_set.iter().map(|hash| **hash).collect();
+        binary::verify(
+            root,
+            &self.message_id,
+            &proof_set,
+            self.proof_index,
+            self.leaves_count,
+        )
+    }
+
+    /// Verify the proof against the outbox `root`, checking that it proves the message
+    /// with the given fields.
+    pub fn verify_message(
+        &self,
+        root: &Bytes32,
+        sender: &Address,
+        recipient: &Address,
+        nonce: &Nonce,
+        amount: Word,
+        data: &[u8],
+    ) -> bool {
+        Input::compute_message_id(sender, recipient, nonce, amount, data)
+            == self.message_id
+            && self.verify(root)
+    }
+}
@@ -2,9 +2,19 @@
 
 use crate::interpreter::Memory;
 
-#[cfg(any(test, feature = "test-helpers"))]
+#[cfg(any(test, feature = "test-helpers", feature = "std"))]
 use crate::interpreter::MemoryInstance;
 
+#[cfg(feature = "std")]
+use std::sync::{
+    atomic::{
+        AtomicU64,
+        Ordering,
+    },
+    Arc,
+    Mutex,
+};
+
 /// Trait for a VM memory pool.
 pub trait VmMemoryPool: Sync {
     /// The memory instance returned by this pool.
@@ -27,3 +37,279 @@ impl VmMemoryPool for DummyPool {
         core::future::ready(MemoryInstance::new())
     }
 }
+
+/// Pool that recycles the VM memory instances to avoid reallocating the buffers.
+///
+/// The memory is returned to the pool when the [`PooledMemory`] is dropped. It is
+/// reset, but keeps its allocations, unless the pool already holds `max_instances`
+/// or the total allocated size of the pooled instances would exceed `max_size`. In
+/// that case the instance is discarded.
+///
+/// The pool is cheap to clone, and the clones share the same instances.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct MemoryPool {
+    inner: Arc<MemoryPoolInner>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct MemoryPoolInner {
+    max_instances: usize,
+    max_size: usize,
+    pooled: Mutex<PooledInstances>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    discarded: AtomicU64,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct PooledInstances {
+    instances: Vec<MemoryInstance>,
+    size: usize,
+}
+
+/// Statistics of the [`MemoryPool`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryPoolMetrics {
+    /// Number of requests served with a recycled instance.
+    pub hits: u64,
+    /// Number of requests that allocated a new instance.
+    pub misses: u64,
+    /// Number of returned instances that were dropped due to the limits of the pool.
+    pub discarded: u64,
+    /// Number of instances currently held by the pool.
+    pub pooled: usize,
+    /// Total allocated size in bytes of the instances held by the pool.
+    pub pooled_size: usize,
+}
+
+#[cfg(feature = "std")]
+impl MemoryPool {
+    /// Create an empty pool holding at most `max_instances` instances with
+    /// `max_size` bytes allocated in total.
+    pub fn new(max_instances: usize, max_size: usize) -> Self {
+        Self {
+            inner: Arc::new(MemoryPoolInner {
+                max_instances,
+                max_size,
+                pooled: Mutex::new(PooledInstances::default()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                discarded: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Gets a recycled memory instance, or allocates a new one if the pool is empty.
+    pub fn get(&self) -> PooledMemory {
+        let recycled = {
+            let mut pooled = self.inner.lock();
+            let memory = pooled.instances.pop();
+            if let Some(memory) = &memory {
+                pooled.size = pooled.size.saturating_sub(memory.allocated_size());
+            }
+            memory
+        };
+
+        let memory = match recycled {
+            Some(memory) => {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                memory
+            }
+            None => {
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                MemoryInstance::new()
+            }
+        };
+
+        PooledMemory {
+            memory: Some(memory),
+            pool: self.inner.clone(),
+        }
+    }
+
+    /// Statistics of the pool since its creation.
+    pub fn metrics(&self) -> MemoryPoolMetrics {
+        let (pooled, pooled_size) = {
+            let pooled = self.inner.lock();
+            (pooled.instances.len(), pooled.size)
+        };
+
+        MemoryPoolMetrics {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            discarded: self.inner.discarded.load(Ordering::Relaxed),
+            pooled,
+            pooled_size,
+        }
+    }
+
+    /// Drops all instances held by the pool.
+    pub fn clear(&self) {
+        *self.inner.lock() = PooledInstances::default();
+    }
+}
+
+#[cfg(feature = "std")]
+impl MemoryPoolInner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, PooledInstances> {
+        // The pooled instances are always consistent, so the poisoning can be ignored
+        self.pooled
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn recycle(&self, mut memory: MemoryInstance) {
+        memory.reset();
+        let size = memory.allocated_size();
+
+        let mut pooled = self.lock();
+        let fits = pooled.instances.len() < self.max_instances
+            && pooled.size.saturating_add(size) <= self.max_size;
+        if fits {
+            pooled.size = pooled.size.saturating_add(size);
+            pooled.instances.push(memory);
+        } else {
+            drop(pooled);
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl VmMemoryPool for MemoryPool {
+    type Memory = PooledMemory;
+
+    fn get_new(&self) -> impl core::future::Future<Output = Self::Memory> + Send {
+        core::future::ready(self.get())
+    }
+}
+
+/// Memory instance borrowed from the [`MemoryPool`]. Returns to the pool on drop.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PooledMemory {
+    memory: Option<MemoryInstance>,
+    pool: Arc<MemoryPoolInner>,
+}
+
+#[cfg(feature = "std")]
+impl AsRef<MemoryInstance> for PooledMemory {
+    fn as_ref(&self) -> &MemoryInstance {
+        self.memory
+            .as_ref()
+            .expect("The memory is taken only on drop")
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsMut<MemoryInstance> for PooledMemory {
+    fn as_mut(&mut self) -> &mut MemoryInstance {
+        self.memory
+            .as_mut()
+            .expect("The memory is taken only on drop")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for PooledMemory {
+    fn drop(&mut self) {
+        if let Some(memory) = self.memory.take() {
+            self.pool.recycle(memory);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use fuel_types::Word;
+
+    use crate::{
+        constraints::reg_key::*,
+        consts::VM_MAX_RAM,
+    };
+
+    fn allocate_heap(memory: &mut MemoryInstance, amount: Word) {
+        let sp = 0;
+        let mut hp = VM_MAX_RAM;
+        memory
+            .grow_heap_by(Reg::<SP>::new(&sp), RegMut::<HP>::new(&mut hp), amount)
+            .expect("Heap allocation should succeed");
+    }
+
+    #[test]
+    fn get__recycles_returned_instances() {
+        let pool = MemoryPool::new(2, usize::MAX);
+
+        let mut memory = pool.get();
+        allocate_heap(memory.as_mut(), 1024);
+        let allocated = memory.as_ref().allocated_size();
+        drop(memory);
+
+        assert_eq!(
+            pool.metrics(),
+            MemoryPoolMetrics {
+                hits: 0,
+                misses: 1,
+                discarded: 0,
+                pooled: 1,
+                pooled_size: allocated,
+            }
+        );
+
+        let memory = pool.get();
+        assert_eq!(memory.as_ref().allocated_size(), allocated);
+        assert_eq!(memory.as_ref(), &MemoryInstance::new());
+        assert_eq!(pool.metrics().hits, 1);
+        assert_eq!(pool.metrics().pooled, 0);
+    }
+
+    #[test]
+    fn recycle__respects_max_instances() {
+        let pool = MemoryPool::new(1, usize::MAX);
+
+        let first = pool.get();
+        let second = pool.get();
+        drop(first);
+        drop(second);
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.misses, 2);
+        assert_eq!(metrics.pooled, 1);
+        assert_eq!(metrics.discarded, 1);
+    }
+
+    #[test]
+    fn recycle__respects_max_size() {
+        let pool = MemoryPool::new(10, 1024);
+
+        let mut large = pool.get();
+        allocate_heap(large.as_mut(), 4096);
+        drop(large);
+        let small = pool.get();
+        drop(small);
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.discarded, 1);
+        assert_eq!(metrics.pooled, 1);
+        assert_eq!(metrics.pooled_size, 0);
+    }
+
+    #[test]
+    fn clones__share_instances() {
+        let pool = MemoryPool::new(1, usize::MAX);
+        let clone = pool.clone();
+
+        drop(pool.get());
+        drop(clone.get());
+
+        assert_eq!(pool.metrics().hits, 1);
+        pool.clear();
+        assert_eq!(clone.metrics().pooled, 0);
+    }
+}
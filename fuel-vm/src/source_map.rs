@@ -0,0 +1,417 @@
+//! Mapping of the instruction offsets back to the source code.
+//!
+//! The compilers can emit a JSON debug-info file describing, for every program, the
+//! source location of its instructions. [`SourceMaps`] loads such files and
+//! symbolises the locations reported by the VM: a [`Backtrace`], or the `Panic` and
+//! `Revert` receipts, rendered as a [`SymbolizedTrace`].
+//!
+//! The programs are identified by their code root for the contracts, see
+//! [`Contract::root_from_code`], and by the hash of the bytecode for the scripts, see
+//! [`SourceMaps::script_id`]. The offsets are in bytes from the start of the program,
+//! i.e. `$pc - $is`.
+//!
+//! The debug-info file has the following format:
+//!
+//! ```json
+//! {
+//!   "programs": [
+//!     {
+//!       "id": "0x1f2d...",
+//!       "locations": [
+//!         { "offset": 0, "file": "src/main.sw", "line": 3, "column": 5, "function": "main" },
+//!         { "offset": 24, "file": "src/main.sw", "line": 4 }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! A location covers the instructions from its offset up to the next location.
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+use std::{
+    collections::HashMap,
+    io,
+    path::Path,
+};
+
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_crypto::Hasher;
+use fuel_tx::{
+    Contract,
+    Receipt,
+    ScriptExecutionResult,
+};
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    backtrace::Backtrace,
+    constraints::InstructionLocation,
+};
+
+/// Location in the source code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct SourceLocation {
+    /// Path of the source file.
+    pub file: String,
+    /// Line in the source file, starting from 1.
+    pub line: u32,
+    /// Column in the line, starting from 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    /// Name of the enclosing function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Source location of the instructions starting at the `offset`, an entry of the
+/// debug-info file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct SourceMapEntry {
+    /// Offset of the first instruction in bytes from the start of the program.
+    pub offset: Word,
+    /// The source location.
+    #[serde(flatten)]
+    pub location: SourceLocation,
+}
+
+/// Source map of a program, described by the debug-info file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ProgramDebugInfo {
+    /// The code root of the contract, or the hash of the script.
+    pub id: Bytes32,
+    /// The source locations of the instructions.
+    pub locations: Vec<SourceMapEntry>,
+}
+
+/// Contents of the debug-info file.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct DebugInfo {
+    /// The programs described by the file.
+    pub programs: Vec<ProgramDebugInfo>,
+}
+
+/// Maps the instruction offsets of a program to the source locations.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    locations: BTreeMap<Word, SourceLocation>,
+}
+
+impl SourceMap {
+    /// Create an empty source map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the source location of the instructions starting at the `offset`, up to
+    /// the next location.
+    pub fn insert(&mut self, offset: Word, location: SourceLocation) {
+        self.locations.insert(offset, location);
+    }
+
+    /// Source location of the instruction at the `offset`.
+    pub fn lookup(&self, offset: Word) -> Option<&SourceLocation> {
+        self.locations
+            .range(..=offset)
+            .next_back()
+            .map(|(_, location)| location)
+    }
+
+    /// Returns `true` if the map has no locations.
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+impl FromIterator<SourceMapEntry> for SourceMap {
+    fn from_iter<T: IntoIterator<Item = SourceMapEntry>>(iter: T) -> Self {
+        Self {
+            locations: iter
+                .into_iter()
+                .map(|entry| (entry.offset, entry.location))
+                .collect(),
+        }
+    }
+}
+
+/// Error of loading the debug-info file.
+#[derive(Debug, derive_more::Display)]
+pub enum SourceMapError {
+    /// The file can't be read.
+    #[display(fmt = "Failed to read the debug info: {}", _0)]
+    Io(io::Error),
+    /// The file isn't a valid debug info.
+    #[display(fmt = "Invalid debug info: {}", _0)]
+    Json(serde_json::Error),
+}
+
+impl std::error::Error for SourceMapError {}
+
+impl From<io::Error> for SourceMapError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SourceMapError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// The source maps of the programs, and the contracts using them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMaps {
+    programs: HashMap<Bytes32, SourceMap>,
+    contracts: HashMap<ContractId, Bytes32>,
+    script: Option<Bytes32>,
+}
+
+impl SourceMaps {
+    /// Create the source maps without programs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the debug-info file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SourceMapError> {
+        let json = std::fs::read(path)?;
+        Self::from_json(&json)
+    }
+
+    /// Parses the debug-info file.
+    pub fn from_json(json: &[u8]) -> Result<Self, SourceMapError> {
+        let debug_info: DebugInfo = serde_json::from_slice(json)?;
+        Ok(Self::from(debug_info))
+    }
+
+    /// The id of the script in the debug info: the hash of its bytecode.
+    pub fn script_id(script: &[u8]) -> Bytes32 {
+        Hasher::hash(script)
+    }
+
+    /// Adds the source map of the program with the `id`, replacing the previous one.
+    pub fn insert(&mut self, id: Bytes32, source_map: SourceMap) {
+        self.programs.insert(id, source_map);
+    }
+
+    /// Adds the programs of the debug info, replacing the ones with the same id.
+    pub fn extend(&mut self, debug_info: DebugInfo) {
+        self.programs.extend(
+            debug_info
+                .programs
+                .into_iter()
+                .map(|program| (program.id, program.locations.into_iter().collect())),
+        );
+    }
+
+    /// Source map of the program with the `id`.
+    pub fn get(&self, id: &Bytes32) -> Option<&SourceMap> {
+        self.programs.get(id)
+    }
+
+    /// Binds the contract to the source map of its code, identified by the
+    /// `code_root`.
+    pub fn bind_contract(&mut self, contract_id: ContractId, code_root: Bytes32) {
+        self.contracts.insert(contract_id, code_root);
+    }
+
+    /// Binds the contract to the source map of its `bytecode`.
+    pub fn bind_contract_code(&mut self, contract_id: ContractId, bytecode: &[u8]) {
+        self.bind_contract(contract_id, Contract::root_from_code(bytecode));
+    }
+
+    /// Sets the executed script, used for the locations outside of the contracts.
+    pub fn set_script(&mut self, script: &[u8]) {
+        self.script = Some(Self::script_id(script));
+    }
+
+    /// Source location of the instruction at the `location`. `None` if the program
+    /// is unknown or has no location for the offset.
+    pub fn resolve(&self, location: &InstructionLocation) -> Option<&SourceLocation> {
+        let id = match &location.context {
+            Some(contract_id) => self.contracts.get(contract_id)?,
+            None => self.script.as_ref()?,
+        };
+        self.programs.get(id)?.lookup(location.offset)
+    }
+
+    /// Symbolises the location.
+    pub fn symbolize(&self, location: InstructionLocation) -> StackFrame {
+        StackFrame {
+            source: self.resolve(&location).cloned(),
+            location,
+        }
+    }
+
+    /// Symbolises the call stack of the backtrace, from the instruction that failed
+    /// to the script.
+    pub fn symbolize_backtrace(&self, backtrace: &Backtrace) -> SymbolizedTrace {
+        let call_stack = backtrace.call_stack();
+        let registers = backtrace.registers();
+
+        let current = InstructionLocation {
+            context: call_stack.last().map(|frame| *frame.to()),
+            offset: program_offset(registers),
+        };
+        let callers = call_stack.iter().enumerate().rev().map(|(index, frame)| {
+            let caller = index
+                .checked_sub(1)
+                .and_then(|index| call_stack.get(index))
+                .map(|frame| *frame.to());
+            InstructionLocation {
+                context: caller,
+                offset: program_offset(frame.registers()),
+            }
+        });
+
+        let cause = match backtrace.result() {
+            ScriptExecutionResult::Panic => TraceCause::Panic(None),
+            ScriptExecutionResult::Revert => TraceCause::Revert(None),
+            result => TraceCause::Result(*result),
+        };
+
+        SymbolizedTrace {
+            cause,
+            frames: core::iter::once(current)
+                .chain(callers)
+                .map(|location| self.symbolize(location))
+                .collect(),
+        }
+    }
+
+    /// Symbolises the location of the `Panic` or `Revert` receipt. `None` for the
+    /// other receipts.
+    ///
+    /// The receipts don't describe the callers, so the trace has a single frame.
+    pub fn symbolize_receipt(&self, receipt: &Receipt) -> Option<SymbolizedTrace> {
+        let (cause, id, pc, is) = match receipt {
+            Receipt::Panic {
+                id, reason, pc, is, ..
+            } => (TraceCause::Panic(Some(*reason.reason())), id, pc, is),
+            Receipt::Revert { id, ra, pc, is, .. } => {
+                (TraceCause::Revert(Some(*ra)), id, pc, is)
+            }
+            _ => return None,
+        };
+
+        // The receipts of the script use the zeroed id
+        let context = (*id != ContractId::zeroed()).then_some(*id);
+        let location = InstructionLocation {
+            context,
+            offset: pc.saturating_sub(*is),
+        };
+
+        Some(SymbolizedTrace {
+            cause,
+            frames: Vec::from([self.symbolize(location)]),
+        })
+    }
+}
+
+impl From<DebugInfo> for SourceMaps {
+    fn from(debug_info: DebugInfo) -> Self {
+        let mut source_maps = Self::new();
+        source_maps.extend(debug_info);
+        source_maps
+    }
+}
+
+fn program_offset(registers: &[Word]) -> Word {
+    registers[RegId::PC].saturating_sub(registers[RegId::IS])
+}
+
+/// The reason of the trace.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TraceCause {
+    /// The VM panicked, with the reason if it's known.
+    Panic(Option<PanicReason>),
+    /// The program reverted, with the code if it's known.
+    Revert(Option<Word>),
+    /// Other result of the script.
+    Result(ScriptExecutionResult),
+}
+
+impl fmt::Display for TraceCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(Some(reason)) => write!(f, "panic: {reason}"),
+            Self::Panic(None) => write!(f, "panic"),
+            Self::Revert(Some(code)) => write!(f, "revert: {code:#x}"),
+            Self::Revert(None) => write!(f, "revert"),
+            Self::Result(result) => write!(f, "{result:?}"),
+        }
+    }
+}
+
+/// Frame of the [`SymbolizedTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StackFrame {
+    /// Location of the instruction.
+    pub location: InstructionLocation,
+    /// Source location of the instruction, if it's known.
+    pub source: Option<SourceLocation>,
+}
+
+impl fmt::Display for StackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let function = self
+            .source
+            .as_ref()
+            .and_then(|source| source.function.as_deref())
+            .unwrap_or("<unknown>");
+        write!(f, "{function}")?;
+        if let Some(source) = &self.source {
+            write!(f, " at {source}")?;
+        }
+
+        match &self.location.context {
+            Some(contract_id) => write!(f, " (contract {contract_id}")?,
+            None => write!(f, " (script")?,
+        }
+        write!(f, " +{:#x})", self.location.offset)
+    }
+}
+
+/// Symbolised stack trace, from the instruction that failed to the script.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolizedTrace {
+    /// The reason of the trace.
+    pub cause: TraceCause,
+    /// The frames, starting from the innermost one.
+    pub frames: Vec<StackFrame>,
+}
+
+impl fmt::Display for SymbolizedTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cause)?;
+        self.frames
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, frame)| write!(f, "\n  {index:>3}: {frame}"))
+    }
+}
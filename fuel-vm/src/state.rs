@@ -15,6 +15,7 @@ mod debugger;
 pub use debug::{
     Breakpoint,
     DebugEval,
+    ExecutionBudget,
 };
 
 pub use debugger::Debugger;
@@ -97,6 +98,11 @@ impl ProgramState {
     pub const fn is_debug(&self) -> bool {
         self.debug_ref().is_some()
     }
+
+    /// Return `true` if the VM was preempted due to the exhausted execution budget.
+    pub const fn is_preempted(&self) -> bool {
+        matches!(self.debug_ref(), Some(DebugEval::Preempted))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
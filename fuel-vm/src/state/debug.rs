@@ -58,6 +58,21 @@ impl Breakpoint {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Limit of the execution after which the VM is preempted. The program can be resumed
+/// with the same budget for the next slice.
+///
+/// The budget is checked before each instruction, and at least one instruction is
+/// executed per slice, so the gas budget can be exceeded by the cost of the last
+/// instruction of the slice.
+pub enum ExecutionBudget {
+    /// Preempt the VM after the number of instructions is executed.
+    Instructions(u64),
+    /// Preempt the VM after the amount of gas is consumed.
+    Gas(Word),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// State evaluation of the interpreter that will describe if a program should
@@ -66,6 +81,8 @@ pub enum DebugEval {
     /// This evaluation should break the program in the location described in
     /// `Breakpoint`.
     Breakpoint(Breakpoint),
+    /// The execution budget of the slice is exhausted, see [`ExecutionBudget`].
+    Preempted,
     /// This evaluation should not break the program.
     Continue,
 }
@@ -88,6 +105,11 @@ impl DebugEval {
         matches!(self, Self::Continue)
     }
 
+    /// Return `true` if the execution budget of the slice is exhausted.
+    pub const fn is_preempted(&self) -> bool {
+        matches!(self, Self::Preempted)
+    }
+
     /// Return a breakpoint description if the current evaluation should break;
     /// return `None` otherwise.
     pub const fn breakpoint(&self) -> Option<&Breakpoint> {
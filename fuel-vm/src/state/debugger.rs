@@ -1,6 +1,7 @@
 use crate::state::{
     Breakpoint,
     DebugEval,
+    ExecutionBudget,
     ProgramState,
 };
 
@@ -23,6 +24,11 @@ pub struct Debugger {
     single_stepping: bool,
     breakpoints: HashMap<ContractId, HashSet<Word>>,
     last_state: Option<ProgramState>,
+    budget: Option<ExecutionBudget>,
+    /// Instructions executed in the current slice
+    slice_instructions: u64,
+    /// Remaining global gas at the start of the current slice
+    slice_gas_start: Option<Word>,
 }
 
 impl Debugger {
@@ -100,6 +106,49 @@ impl Debugger {
             .unwrap_or_default()
     }
 
+    /// Execution budget of a slice, if set.
+    pub const fn execution_budget(&self) -> Option<ExecutionBudget> {
+        self.budget
+    }
+
+    /// Set the execution budget of a slice, or remove it with `None`.
+    pub fn set_execution_budget(&mut self, budget: Option<ExecutionBudget>) {
+        self.is_active =
+            budget.is_some() || self.single_stepping || !self.breakpoints.is_empty();
+        self.budget = budget;
+        self.start_budget_slice();
+    }
+
+    /// Start a new slice of the execution budget.
+    pub(crate) fn start_budget_slice(&mut self) {
+        self.slice_instructions = 0;
+        self.slice_gas_start = None;
+    }
+
+    /// Evaluate whether the execution budget of the current slice is exhausted before
+    /// executing the next instruction, with `gas` remaining.
+    pub fn eval_budget(&mut self, gas: Word) -> DebugEval {
+        let Some(budget) = self.budget else {
+            return DebugEval::Continue
+        };
+        let gas_start = *self.slice_gas_start.get_or_insert(gas);
+
+        // At least one instruction is executed per slice to guarantee the progress
+        let exhausted = self.slice_instructions > 0
+            && match budget {
+                ExecutionBudget::Instructions(limit) => self.slice_instructions >= limit,
+                ExecutionBudget::Gas(limit) => gas_start.saturating_sub(gas) >= limit,
+            };
+
+        if exhausted {
+            self.start_budget_slice();
+            DebugEval::Preempted
+        } else {
+            self.slice_instructions = self.slice_instructions.saturating_add(1);
+            DebugEval::Continue
+        }
+    }
+
     /// Overwrite the last known state of the VM.
     pub fn set_last_state(&mut self, state: ProgramState) {
         self.is_active = true;
@@ -12,6 +12,7 @@ mod contracts_state;
 mod interpreter;
 #[cfg(feature = "test-helpers")]
 mod memory;
+mod overlay;
 pub(crate) mod predicate;
 
 pub use contracts_assets::{
@@ -29,6 +30,12 @@ pub use interpreter::{
 };
 #[cfg(feature = "test-helpers")]
 pub use memory::MemoryStorage;
+pub use overlay::{
+    OverlayChanges,
+    OverlayStorage,
+    OverlayTable,
+    StateOverrides,
+};
 pub use predicate::PredicateStorage;
 
 #[cfg(feature = "alloc")]
@@ -70,6 +77,20 @@ impl Mappable for ContractsRawCode {
     type Value = [u8];
 }
 
+/// Increments the big-endian 256-bit number in place. Returns `true` on overflow.
+fn add_one(a: &mut [u8; 32]) -> bool {
+    let right = u128::from_be_bytes(a[16..].try_into().unwrap());
+    let (right, of) = right.overflowing_add(1);
+    a[16..].copy_from_slice(&right.to_be_bytes()[..]);
+    if of {
+        let left = u128::from_be_bytes(a[..16].try_into().unwrap());
+        let (left, of) = left.overflowing_add(1);
+        a[..16].copy_from_slice(&left.to_be_bytes()[..]);
+        return of
+    }
+    false
+}
+
 /// The macro defines a new type of double storage key. It is a merge of the two
 /// types into one general type that represents the storage key of some entity.
 ///
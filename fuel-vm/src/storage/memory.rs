@@ -13,8 +13,14 @@ use crate::storage::{
 };
 
 use fuel_crypto::Hasher;
+use fuel_merkle::sparse::{
+    in_memory::MerkleTree,
+    MerkleTreeKey,
+};
 use fuel_storage::{
     Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
     StorageAsRef,
     StorageInspect,
     StorageMutate,
@@ -41,7 +47,10 @@ use alloc::{
 };
 use core::convert::Infallible;
 
-use super::interpreter::ContractsAssetsStorage;
+use super::{
+    add_one,
+    interpreter::ContractsAssetsStorage,
+};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 struct MemoryStorageInner {
@@ -348,6 +357,23 @@ impl StorageMutate<ContractsAssets> for MemoryStorage {
     }
 }
 
+/// The balances root is the root of the sparse Merkle tree of the balances of the
+/// contract, keyed by the asset id.
+impl MerkleRootStorage<ContractId, ContractsAssets> for MemoryStorage {
+    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
+        let balances = self
+            .memory
+            .balances
+            .iter()
+            .filter(|(key, _)| key.contract_id() == parent)
+            .map(|(key, balance)| {
+                (MerkleTreeKey::new(key.asset_id()), balance.to_be_bytes())
+            });
+
+        Ok(MerkleTree::root_from_set(balances))
+    }
+}
+
 impl StorageInspect<ContractsState> for MemoryStorage {
     type Error = Infallible;
 
@@ -461,6 +487,21 @@ impl StorageRead<ContractsState> for MemoryStorage {
     }
 }
 
+/// The state root is the root of the sparse Merkle tree of the storage slots of the
+/// contract, the same as [`Contract::initial_state_root`] for a deployed contract.
+impl MerkleRootStorage<ContractId, ContractsState> for MemoryStorage {
+    fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
+        let slots = self
+            .memory
+            .contract_state
+            .iter()
+            .filter(|(key, _)| key.contract_id() == parent)
+            .map(|(key, value)| (MerkleTreeKey::new(key.state_key()), value.as_ref()));
+
+        Ok(MerkleTree::root_from_set(slots))
+    }
+}
+
 impl ContractsAssetsStorage for MemoryStorage {}
 
 impl InterpreterStorage for MemoryStorage {
@@ -616,19 +657,6 @@ impl InterpreterStorage for MemoryStorage {
     }
 }
 
-fn add_one(a: &mut [u8; 32]) -> bool {
-    let right = u128::from_be_bytes(a[16..].try_into().unwrap());
-    let (right, of) = right.overflowing_add(1);
-    a[16..].copy_from_slice(&right.to_be_bytes()[..]);
-    if of {
-        let left = u128::from_be_bytes(a[..16].try_into().unwrap());
-        let (left, of) = left.overflowing_add(1);
-        a[..16].copy_from_slice(&left.to_be_bytes()[..]);
-        return of
-    }
-    false
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,871 @@
+//! Copy-on-write storage that applies state overrides on top of another storage.
+
+use crate::storage::{
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateData,
+    ContractsStateKey,
+    InterpreterStorage,
+    UploadedBytecode,
+    UploadedBytecodes,
+};
+
+use fuel_storage::{
+    Mappable,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+    StorageWrite,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+};
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use alloc::{
+    borrow::{
+        Cow,
+        ToOwned,
+    },
+    collections::BTreeMap,
+    vec::Vec,
+};
+
+use super::add_one;
+
+/// State overrides applied by [`OverlayStorage`] on top of the underlying storage.
+///
+/// Used to simulate the execution against a modified chain state, e.g. "what if this
+/// contract were upgraded?", without touching the real storage.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StateOverrides {
+    contracts: BTreeMap<ContractId, Contract>,
+    contract_state: BTreeMap<ContractsStateKey, ContractsStateData>,
+    balances: BTreeMap<ContractsAssetKey, Word>,
+    block_height: Option<BlockHeight>,
+    timestamp: Option<Word>,
+    coinbase: Option<ContractId>,
+    skip_signature_checks: bool,
+}
+
+impl StateOverrides {
+    /// Create an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the bytecode of the contract.
+    pub fn with_contract_code(mut self, id: ContractId, code: Contract) -> Self {
+        self.contracts.insert(id, code);
+        self
+    }
+
+    /// Override the value of the storage slot of the contract.
+    pub fn with_storage_slot(
+        mut self,
+        id: ContractId,
+        key: Bytes32,
+        value: ContractsStateData,
+    ) -> Self {
+        self.contract_state.insert((&id, &key).into(), value);
+        self
+    }
+
+    /// Force the balance of the asset for the contract.
+    pub fn with_balance(
+        mut self,
+        id: ContractId,
+        asset_id: AssetId,
+        amount: Word,
+    ) -> Self {
+        self.balances.insert((&id, &asset_id).into(), amount);
+        self
+    }
+
+    /// Override the current block height.
+    pub fn with_block_height(mut self, block_height: BlockHeight) -> Self {
+        self.block_height = Some(block_height);
+        self
+    }
+
+    /// Override the timestamp of the current block.
+    pub fn with_timestamp(mut self, timestamp: Word) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Override the contract receiving the fees of the current block.
+    pub fn with_coinbase(mut self, coinbase: ContractId) -> Self {
+        self.coinbase = Some(coinbase);
+        self
+    }
+
+    /// Skip the verification of the signatures of the transaction.
+    pub fn with_skipped_signature_checks(mut self) -> Self {
+        self.skip_signature_checks = true;
+        self
+    }
+
+    /// Returns `true` if the signatures of the transaction shouldn't be verified.
+    pub fn skip_signature_checks(&self) -> bool {
+        self.skip_signature_checks
+    }
+}
+
+/// Storage that reads from the underlying storage and keeps all writes in memory.
+///
+/// The underlying storage is only borrowed immutably, so it stays untouched regardless
+/// of the execution. The [`StateOverrides`] are applied as the initial changes.
+#[derive(Debug, Clone)]
+pub struct OverlayStorage<'a, S> {
+    inner: &'a S,
+    block_height: Option<BlockHeight>,
+    timestamp: Option<Word>,
+    coinbase: Option<ContractId>,
+    contracts: BTreeMap<ContractId, Option<Contract>>,
+    balances: BTreeMap<ContractsAssetKey, Option<Word>>,
+    contract_state: BTreeMap<ContractsStateKey, Option<ContractsStateData>>,
+    uploaded_bytecodes: BTreeMap<Bytes32, Option<UploadedBytecode>>,
+    consensus_parameters_versions: BTreeMap<u32, ConsensusParameters>,
+    state_transition_bytecodes_versions: BTreeMap<u32, Bytes32>,
+}
+
+impl<'a, S> OverlayStorage<'a, S> {
+    /// Create a new overlay on top of the `inner` storage.
+    pub fn new(inner: &'a S) -> Self {
+        Self {
+            inner,
+            block_height: None,
+            timestamp: None,
+            coinbase: None,
+            contracts: Default::default(),
+            balances: Default::default(),
+            contract_state: Default::default(),
+            uploaded_bytecodes: Default::default(),
+            consensus_parameters_versions: Default::default(),
+            state_transition_bytecodes_versions: Default::default(),
+        }
+    }
+
+    /// Create a new overlay on top of the `inner` storage with `overrides` applied.
+    pub fn with_overrides(inner: &'a S, overrides: &StateOverrides) -> Self {
+        let mut storage = Self::new(inner);
+        storage.block_height = overrides.block_height;
+        storage.timestamp = overrides.timestamp;
+        storage.coinbase = overrides.coinbase;
+        storage.contracts.extend(
+            overrides
+                .contracts
+                .iter()
+                .map(|(id, code)| (*id, Some(code.clone()))),
+        );
+        storage.balances.extend(
+            overrides
+                .balances
+                .iter()
+                .map(|(key, amount)| (*key, Some(*amount))),
+        );
+        storage.contract_state.extend(
+            overrides
+                .contract_state
+                .iter()
+                .map(|(key, value)| (*key, Some(value.clone()))),
+        );
+        storage
+    }
+
+    /// The underlying storage.
+    pub fn inner(&self) -> &'a S {
+        self.inner
+    }
+
+    /// Takes the changes recorded so far, including the applied overrides, leaving
+    /// the overlay without changes.
+    ///
+    /// The changes don't borrow the underlying storage, so they can be applied to it
+    /// with [`OverlayChanges::apply`] once the overlay is dropped.
+    pub fn take_changes(&mut self) -> OverlayChanges {
+        OverlayChanges {
+            contracts: core::mem::take(&mut self.contracts),
+            balances: core::mem::take(&mut self.balances),
+            contract_state: core::mem::take(&mut self.contract_state),
+            uploaded_bytecodes: core::mem::take(&mut self.uploaded_bytecodes),
+            consensus_parameters_versions: core::mem::take(
+                &mut self.consensus_parameters_versions,
+            ),
+            state_transition_bytecodes_versions: core::mem::take(
+                &mut self.state_transition_bytecodes_versions,
+            ),
+        }
+    }
+}
+
+/// The writes recorded by [`OverlayStorage`], detached from the underlying storage.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OverlayChanges {
+    contracts: BTreeMap<ContractId, Option<Contract>>,
+    balances: BTreeMap<ContractsAssetKey, Option<Word>>,
+    contract_state: BTreeMap<ContractsStateKey, Option<ContractsStateData>>,
+    uploaded_bytecodes: BTreeMap<Bytes32, Option<UploadedBytecode>>,
+    consensus_parameters_versions: BTreeMap<u32, ConsensusParameters>,
+    state_transition_bytecodes_versions: BTreeMap<u32, Bytes32>,
+}
+
+impl OverlayChanges {
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+            && self.balances.is_empty()
+            && self.contract_state.is_empty()
+            && self.uploaded_bytecodes.is_empty()
+            && self.consensus_parameters_versions.is_empty()
+            && self.state_transition_bytecodes_versions.is_empty()
+    }
+
+    /// Writes the changes into the `storage`.
+    pub fn apply<S>(self, storage: &mut S) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        for (id, code) in self.contracts {
+            match code {
+                Some(code) => StorageMutate::<ContractsRawCode>::insert(
+                    storage,
+                    &id,
+                    code.as_ref(),
+                )?,
+                None => StorageMutate::<ContractsRawCode>::remove(storage, &id)?,
+            }
+        }
+        for (key, balance) in self.balances {
+            match balance {
+                Some(balance) => {
+                    StorageMutate::<ContractsAssets>::insert(storage, &key, &balance)?
+                }
+                None => StorageMutate::<ContractsAssets>::remove(storage, &key)?,
+            }
+        }
+        for (key, value) in self.contract_state {
+            match value {
+                Some(value) => StorageMutate::<ContractsState>::insert(
+                    storage,
+                    &key,
+                    value.as_ref(),
+                )?,
+                None => StorageMutate::<ContractsState>::remove(storage, &key)?,
+            }
+        }
+        for (root, bytecode) in self.uploaded_bytecodes {
+            match bytecode {
+                Some(bytecode) => {
+                    StorageMutate::<UploadedBytecodes>::insert(storage, &root, &bytecode)?
+                }
+                None => StorageMutate::<UploadedBytecodes>::remove(storage, &root)?,
+            }
+        }
+        for (version, consensus_parameters) in self.consensus_parameters_versions {
+            storage.set_consensus_parameters(version, &consensus_parameters)?;
+        }
+        for (version, root) in self.state_transition_bytecodes_versions {
+            storage.set_state_transition_bytecode(version, &root)?;
+        }
+        Ok(())
+    }
+}
+
+/// A table of [`OverlayStorage`] that tracks its own changes.
+///
+/// A change of `None` means that the value was removed.
+pub trait OverlayTable: Mappable {
+    /// Returns the change of the value under the `key`, if any.
+    fn change<'s, S>(
+        storage: &'s OverlayStorage<'_, S>,
+        key: &Self::Key,
+    ) -> Option<&'s Option<Self::OwnedValue>>;
+
+    /// Records the change of the value under the `key`.
+    fn set_change<S>(
+        storage: &mut OverlayStorage<'_, S>,
+        key: &Self::Key,
+        value: Option<Self::OwnedValue>,
+    );
+}
+
+impl OverlayTable for ContractsRawCode {
+    fn change<'s, S>(
+        storage: &'s OverlayStorage<'_, S>,
+        key: &ContractId,
+    ) -> Option<&'s Option<Contract>> {
+        storage.contracts.get(key)
+    }
+
+    fn set_change<S>(
+        storage: &mut OverlayStorage<'_, S>,
+        key: &ContractId,
+        value: Option<Contract>,
+    ) {
+        storage.contracts.insert(*key, value);
+    }
+}
+
+impl OverlayTable for ContractsAssets {
+    fn change<'s, S>(
+        storage: &'s OverlayStorage<'_, S>,
+        key: &ContractsAssetKey,
+    ) -> Option<&'s Option<Word>> {
+        storage.balances.get(key)
+    }
+
+    fn set_change<S>(
+        storage: &mut OverlayStorage<'_, S>,
+        key: &ContractsAssetKey,
+        value: Option<Word>,
+    ) {
+        storage.balances.insert(*key, value);
+    }
+}
+
+impl OverlayTable for ContractsState {
+    fn change<'s, S>(
+        storage: &'s OverlayStorage<'_, S>,
+        key: &ContractsStateKey,
+    ) -> Option<&'s Option<ContractsStateData>> {
+        storage.contract_state.get(key)
+    }
+
+    fn set_change<S>(
+        storage: &mut OverlayStorage<'_, S>,
+        key: &ContractsStateKey,
+        value: Option<ContractsStateData>,
+    ) {
+        storage.contract_state.insert(*key, value);
+    }
+}
+
+impl OverlayTable for UploadedBytecodes {
+    fn change<'s, S>(
+        storage: &'s OverlayStorage<'_, S>,
+        key: &Bytes32,
+    ) -> Option<&'s Option<UploadedBytecode>> {
+        storage.uploaded_bytecodes.get(key)
+    }
+
+    fn set_change<S>(
+        storage: &mut OverlayStorage<'_, S>,
+        key: &Bytes32,
+        value: Option<UploadedBytecode>,
+    ) {
+        storage.uploaded_bytecodes.insert(*key, value);
+    }
+}
+
+impl<Type: OverlayTable, S> StorageInspect<Type> for OverlayStorage<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        match Type::change(self, key) {
+            Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+            None => <S as StorageInspect<Type>>::get(self.inner, key),
+        }
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        match Type::change(self, key) {
+            Some(value) => Ok(value.is_some()),
+            None => <S as StorageInspect<Type>>::contains_key(self.inner, key),
+        }
+    }
+}
+
+impl<Type: OverlayTable, S> StorageMutate<Type> for OverlayStorage<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    fn replace(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let prev = StorageInspect::<Type>::get(self, key)?.map(Cow::into_owned);
+        Type::set_change(self, key, Some(value.to_owned().into()));
+        Ok(prev)
+    }
+
+    fn take(&mut self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let prev = StorageInspect::<Type>::get(self, key)?.map(Cow::into_owned);
+        Type::set_change(self, key, None);
+        Ok(prev)
+    }
+}
+
+impl<Type: OverlayTable, S> StorageSize<Type> for OverlayStorage<'_, S>
+where
+    Type::OwnedValue: AsRef<[u8]>,
+    S: StorageSize<Type>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        match Type::change(self, key) {
+            Some(value) => Ok(value.as_ref().map(|value| value.as_ref().len())),
+            None => <S as StorageSize<Type>>::size_of_value(self.inner, key),
+        }
+    }
+}
+
+impl<Type: OverlayTable, S> StorageRead<Type> for OverlayStorage<'_, S>
+where
+    Type::OwnedValue: AsRef<[u8]>,
+    S: StorageRead<Type>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        match Type::change(self, key) {
+            Some(value) => Ok(value.as_ref().map(|value| {
+                let bytes = value.as_ref();
+                let len = buf.len().min(bytes.len());
+                buf[..len].copy_from_slice(&bytes[..len]);
+                len
+            })),
+            None => <S as StorageRead<Type>>::read(self.inner, key, buf),
+        }
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        match Type::change(self, key) {
+            Some(value) => Ok(value.as_ref().map(|value| value.as_ref().to_vec())),
+            None => <S as StorageRead<Type>>::read_alloc(self.inner, key),
+        }
+    }
+}
+
+impl<Type, S> StorageWrite<Type> for OverlayStorage<'_, S>
+where
+    Type: OverlayTable<Value = [u8]>,
+    Type::OwnedValue: AsRef<[u8]>,
+    S: StorageInspect<Type>,
+{
+    fn write_bytes(&mut self, key: &Type::Key, buf: &[u8]) -> Result<usize, Self::Error> {
+        Type::set_change(self, key, Some(buf.to_vec().into()));
+        Ok(buf.len())
+    }
+
+    fn replace_bytes(
+        &mut self,
+        key: &Type::Key,
+        buf: &[u8],
+    ) -> Result<(usize, Option<Vec<u8>>), Self::Error> {
+        let prev = StorageInspect::<Type>::get(self, key)?
+            .map(|value| value.as_ref().as_ref().to_vec());
+        Type::set_change(self, key, Some(buf.to_vec().into()));
+        Ok((buf.len(), prev))
+    }
+
+    fn take_bytes(&mut self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        let prev = StorageInspect::<Type>::get(self, key)?
+            .map(|value| value.as_ref().as_ref().to_vec());
+        Type::set_change(self, key, None);
+        Ok(prev)
+    }
+}
+
+impl<S: InterpreterStorage> ContractsAssetsStorage for OverlayStorage<'_, S> {}
+
+impl<S> InterpreterStorage for OverlayStorage<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        match self.block_height {
+            Some(block_height) => Ok(block_height),
+            None => self.inner.block_height(),
+        }
+    }
+
+    /// The latest of the versions of the underlying storage and the ones set in the
+    /// overlay.
+    fn consensus_parameters_version(&self) -> Result<u32, Self::DataError> {
+        let version = self.inner.consensus_parameters_version()?;
+        Ok(latest_version(version, &self.consensus_parameters_versions))
+    }
+
+    /// The latest of the versions of the underlying storage and the ones set in the
+    /// overlay.
+    fn state_transition_version(&self) -> Result<u32, Self::DataError> {
+        let version = self.inner.state_transition_version()?;
+        Ok(latest_version(
+            version,
+            &self.state_transition_bytecodes_versions,
+        ))
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        match self.timestamp {
+            Some(timestamp) if height == self.block_height()? => Ok(timestamp),
+            _ => self.inner.timestamp(height),
+        }
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.inner.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<ContractId, Self::DataError> {
+        match self.coinbase {
+            Some(coinbase) => Ok(coinbase),
+            None => self.inner.coinbase(),
+        }
+    }
+
+    /// Only the consensus parameters set in the overlay are returned as the previous
+    /// ones, since the underlying storage doesn't expose them.
+    fn set_consensus_parameters(
+        &mut self,
+        version: u32,
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<ConsensusParameters>, Self::DataError> {
+        Ok(self
+            .consensus_parameters_versions
+            .insert(version, consensus_parameters.clone()))
+    }
+
+    /// Only the bytecode set in the overlay is returned as the previous one, since the
+    /// underlying storage doesn't expose it.
+    fn set_state_transition_bytecode(
+        &mut self,
+        version: u32,
+        hash: &Bytes32,
+    ) -> Result<Option<Bytes32>, Self::DataError> {
+        Ok(self
+            .state_transition_bytecodes_versions
+            .insert(version, *hash))
+    }
+
+    fn contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Vec<Option<Cow<'_, ContractsStateData>>>, Self::DataError> {
+        state_keys(start_key)
+            .take(range)
+            .map(|key| self.contract_state(id, &key))
+            .collect()
+    }
+
+    fn contract_state_insert_range<'v, I>(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: I,
+    ) -> Result<usize, Self::DataError>
+    where
+        I: Iterator<Item = &'v [u8]>,
+    {
+        let mut unset_count: usize = 0;
+        for (key, value) in state_keys(start_key).zip(values) {
+            let key: ContractsStateKey = (contract, &key).into();
+            if !StorageInspect::<ContractsState>::contains_key(self, &key)? {
+                unset_count = unset_count.saturating_add(1);
+            }
+            StorageWrite::<ContractsState>::write_bytes(self, &key, value)?;
+        }
+        Ok(unset_count)
+    }
+
+    fn contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: usize,
+    ) -> Result<Option<()>, Self::DataError> {
+        let mut all_set_key = true;
+        for key in state_keys(start_key).take(range) {
+            let key: ContractsStateKey = (contract, &key).into();
+            all_set_key &= StorageInspect::<ContractsState>::contains_key(self, &key)?;
+            StorageMutate::<ContractsState>::remove(self, &key)?;
+        }
+        Ok(all_set_key.then_some(()))
+    }
+}
+
+/// The greater of the `version` and the versions set in the overlay.
+fn latest_version<V>(version: u32, versions: &BTreeMap<u32, V>) -> u32 {
+    versions
+        .keys()
+        .next_back()
+        .map_or(version, |latest| version.max(*latest))
+}
+
+/// Consecutive storage keys starting from `start_key`, until the key space ends.
+fn state_keys(start_key: &Bytes32) -> impl Iterator<Item = Bytes32> {
+    core::iter::successors(Some(**start_key), |n| {
+        let mut n = *n;
+        if add_one(&mut n) {
+            None
+        } else {
+            Some(n)
+        }
+    })
+    .map(Bytes32::from)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use alloc::vec;
+
+    const CONTRACT: ContractId = ContractId::new([1; 32]);
+    const ASSET: AssetId = AssetId::new([2; 32]);
+
+    fn key(k: u8) -> Bytes32 {
+        let mut key = Bytes32::zeroed();
+        key[31] = k;
+        key
+    }
+
+    fn storage() -> MemoryStorage {
+        let mut storage = MemoryStorage::default();
+        storage
+            .storage_contract_insert(&CONTRACT, &vec![1u8; 4].into())
+            .unwrap();
+        storage
+            .contract_state_insert(&CONTRACT, &key(0), &[1; 32])
+            .unwrap();
+        storage
+            .contract_asset_id_balance_insert(&CONTRACT, &ASSET, 10)
+            .unwrap();
+        storage
+    }
+
+    #[test]
+    fn overrides__are_visible_through_the_overlay() {
+        let storage = storage();
+        let overrides = StateOverrides::new()
+            .with_contract_code(CONTRACT, vec![2u8; 8].into())
+            .with_storage_slot(CONTRACT, key(1), vec![3; 32].into())
+            .with_balance(CONTRACT, ASSET, 100)
+            .with_block_height(42.into())
+            .with_timestamp(1234)
+            .with_coinbase(CONTRACT);
+
+        let overlay = OverlayStorage::with_overrides(&storage, &overrides);
+
+        assert_eq!(
+            overlay
+                .storage_contract(&CONTRACT)
+                .unwrap()
+                .unwrap()
+                .as_ref()
+                .as_ref(),
+            &[2u8; 8]
+        );
+        assert_eq!(overlay.storage_contract_size(&CONTRACT).unwrap(), Some(8));
+        assert_eq!(
+            overlay
+                .contract_asset_id_balance(&CONTRACT, &ASSET)
+                .unwrap(),
+            Some(100)
+        );
+        assert_eq!(overlay.block_height().unwrap(), 42.into());
+        assert_eq!(overlay.timestamp(42.into()).unwrap(), 1234);
+        assert_eq!(overlay.coinbase().unwrap(), CONTRACT);
+        assert_eq!(
+            overlay.timestamp(1.into()).unwrap(),
+            storage.timestamp(1.into()).unwrap()
+        );
+
+        let range = overlay
+            .contract_state_range(&CONTRACT, &key(0), 3)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.map(Cow::into_owned))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            range,
+            vec![Some(vec![1; 32].into()), Some(vec![3; 32].into()), None]
+        );
+    }
+
+    #[test]
+    fn reads__fall_back_to_the_underlying_storage() {
+        let storage = storage();
+        let mut overlay = OverlayStorage::new(&storage);
+
+        overlay
+            .contract_state_insert(&CONTRACT, &key(1), &[3; 32])
+            .unwrap();
+
+        assert_eq!(
+            overlay
+                .storage_contract(&CONTRACT)
+                .unwrap()
+                .unwrap()
+                .as_ref()
+                .as_ref(),
+            &[1u8; 4]
+        );
+        assert_eq!(overlay.storage_contract_size(&CONTRACT).unwrap(), Some(4));
+        assert_eq!(
+            overlay
+                .contract_asset_id_balance(&CONTRACT, &ASSET)
+                .unwrap(),
+            Some(10)
+        );
+
+        let range = overlay
+            .contract_state_range(&CONTRACT, &key(0), 3)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.map(Cow::into_owned))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            range,
+            vec![Some(vec![1; 32].into()), Some(vec![3; 32].into()), None]
+        );
+    }
+
+    #[test]
+    fn writes__do_not_modify_the_underlying_storage() {
+        let storage = storage();
+        let mut overlay = OverlayStorage::new(&storage);
+
+        overlay
+            .contract_state_insert(&CONTRACT, &key(0), &[9; 32])
+            .unwrap();
+        overlay
+            .contract_asset_id_balance_insert(&CONTRACT, &ASSET, 0)
+            .unwrap();
+        StorageMutate::<ContractsRawCode>::remove(&mut overlay, &CONTRACT).unwrap();
+
+        assert_eq!(
+            overlay.contract_state(&CONTRACT, &key(0)).unwrap(),
+            Some(Cow::Owned(vec![9; 32].into()))
+        );
+        assert!(!overlay.storage_contract_exists(&CONTRACT).unwrap());
+        assert_eq!(
+            storage.contract_state(&CONTRACT, &key(0)).as_ref(),
+            &ContractsStateData::from(vec![1; 32])
+        );
+        assert!(storage.storage_contract_exists(&CONTRACT).unwrap());
+        assert_eq!(
+            storage
+                .contract_asset_id_balance(&CONTRACT, &ASSET)
+                .unwrap(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn take_changes__applies_writes_to_the_underlying_storage() {
+        let mut storage = storage();
+        let mut overlay = OverlayStorage::new(&storage);
+
+        overlay
+            .contract_state_insert(&CONTRACT, &key(1), &[9; 32])
+            .unwrap();
+        overlay
+            .contract_asset_id_balance_insert(&CONTRACT, &ASSET, 20)
+            .unwrap();
+        StorageMutate::<ContractsState>::remove(
+            &mut overlay,
+            &(&CONTRACT, &key(0)).into(),
+        )
+        .unwrap();
+        let changes = overlay.take_changes();
+        assert!(!changes.is_empty());
+        assert!(overlay.take_changes().is_empty());
+
+        changes.apply(&mut storage).unwrap();
+
+        assert_eq!(
+            storage.contract_state(&CONTRACT, &key(1)).as_ref(),
+            &ContractsStateData::from(vec![9; 32])
+        );
+        assert!(!StorageInspect::<ContractsState>::contains_key(
+            &storage,
+            &(&CONTRACT, &key(0)).into()
+        )
+        .unwrap());
+        assert_eq!(
+            storage
+                .contract_asset_id_balance(&CONTRACT, &ASSET)
+                .unwrap(),
+            Some(20)
+        );
+    }
+
+    #[test]
+    fn contract_state_remove_range__reports_unset_keys() {
+        let storage = storage();
+        let mut overlay = OverlayStorage::new(&storage);
+
+        let all_set = overlay
+            .contract_state_remove_range(&CONTRACT, &key(0), 2)
+            .unwrap();
+
+        assert_eq!(all_set, None);
+        assert!(overlay
+            .contract_state(&CONTRACT, &key(0))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            overlay
+                .contract_state_insert_range(
+                    &CONTRACT,
+                    &key(0),
+                    [&[1u8; 32][..], &[2u8; 32][..]].into_iter()
+                )
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn versions__include_the_ones_set_in_the_overlay() {
+        let mut storage = storage();
+        storage.set_consensus_parameters_version(2);
+        storage.set_state_transition_version(2);
+        let mut overlay = OverlayStorage::new(&storage);
+
+        overlay
+            .set_consensus_parameters(1, &ConsensusParameters::standard())
+            .unwrap();
+        overlay
+            .set_state_transition_bytecode(1, &Bytes32::zeroed())
+            .unwrap();
+        assert_eq!(overlay.consensus_parameters_version().unwrap(), 2);
+        assert_eq!(overlay.state_transition_version().unwrap(), 2);
+
+        overlay
+            .set_consensus_parameters(3, &ConsensusParameters::standard())
+            .unwrap();
+        overlay
+            .set_state_transition_bytecode(4, &Bytes32::zeroed())
+            .unwrap();
+        assert_eq!(overlay.consensus_parameters_version().unwrap(), 3);
+        assert_eq!(overlay.state_transition_version().unwrap(), 4);
+    }
+}
@@ -0,0 +1,706 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::num::NonZeroUsize;
+
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    field::{
+        Expiration,
+        InputContract,
+        Maturity,
+        MintAmount,
+        OutputContract,
+        TxPointer as _,
+    },
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    TransactionBuilder,
+};
+use fuel_types::canonical::Serialize;
+use rand::Rng;
+
+use crate::{
+    block_executor::{
+        BlockExecutionError,
+        BlockExecutor,
+        BlockHeader,
+        ContractUtxo,
+        SkippedTransaction,
+        TransactionSkipReason,
+    },
+    checked_transaction::{
+        CheckError,
+        Checked,
+        CheckedTransaction,
+    },
+    crypto::ephemeral_merkle_root,
+    error::InterpreterError,
+    prelude::*,
+    storage::{
+        ContractsAssets,
+        ContractsAssetsStorage,
+        ContractsState,
+    },
+};
+
+const HEIGHT: u32 = 10;
+const GAS_PRICE: Word = 1;
+const MAX_FEE_LIMIT: Word = 1_000_000;
+
+fn header(coinbase: ContractId) -> BlockHeader {
+    BlockHeader::new(HEIGHT.into(), 1234, coinbase, GAS_PRICE)
+}
+
+fn test_context() -> TestBuilder {
+    let mut test_context = TestBuilder::new(2322u64);
+    test_context
+        .block_height(HEIGHT.into())
+        .max_fee_limit(MAX_FEE_LIMIT);
+    test_context
+}
+
+/// Contract that stores `1` under the zeroed key.
+fn counter_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, Bytes32::LEN as u32),
+        op::aloc(0x10),
+        op::sww(RegId::HP, 0x11, RegId::ONE),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Script that calls the contract and ends with `last`.
+fn call_script(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+    last: Instruction,
+) -> Checked<Script> {
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            last,
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(100_000)
+        .coin_input(AssetId::BASE, MAX_FEE_LIMIT)
+        .change_output(AssetId::BASE)
+        .contract_input(contract_id)
+        .contract_output(&contract_id)
+        .build()
+}
+
+fn simple_script(
+    test_context: &mut TestBuilder,
+    script: Vec<Instruction>,
+) -> Checked<Script> {
+    test_context
+        .start_script(script, vec![])
+        .script_gas_limit(100_000)
+        .coin_input(AssetId::BASE, MAX_FEE_LIMIT)
+        .change_output(AssetId::BASE)
+        .build()
+}
+
+fn stored_value(storage: &MemoryStorage, contract_id: &ContractId) -> Word {
+    let value = storage.contract_state(contract_id, &Bytes32::zeroed());
+    Word::from_be_bytes(value.as_ref().as_ref()[..8].try_into().unwrap())
+}
+
+#[test]
+fn execute__applies_transactions_and_mints_fees_to_coinbase() {
+    let mut test_context = test_context();
+    let coinbase = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let counter = test_context
+        .setup_contract(counter_contract(), None, None)
+        .contract_id;
+    let txs = vec![
+        call_script(&mut test_context, counter, op::ret(RegId::ONE)),
+        simple_script(&mut test_context, vec![op::ret(RegId::ONE)]),
+    ];
+    let ids: Vec<_> = txs.iter().map(|tx| tx.id()).collect();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(coinbase), txs)
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.skipped().count(), 0);
+    let included: Vec<_> = block.included().collect();
+    assert_eq!(included.len(), 2);
+    for (index, tx) in included.iter().enumerate() {
+        assert_eq!(tx.id, ids[index]);
+        assert_eq!(tx.tx_pointer, TxPointer::new(HEIGHT.into(), index as u16));
+        assert!(!tx.is_reverted());
+        assert!(tx.fee > 0);
+        assert_eq!(
+            tx.receipts_root,
+            ephemeral_merkle_root(tx.receipts.iter().map(|r| r.to_bytes()))
+        );
+    }
+    assert_eq!(block.fees, included.iter().map(|tx| tx.fee).sum::<Word>());
+
+    assert_eq!(block.mint.tx_pointer(), &TxPointer::new(HEIGHT.into(), 2));
+    assert_eq!(*block.mint.mint_amount(), block.fees);
+    assert_eq!(block.mint.input_contract().contract_id, coinbase);
+
+    let transactions = block.transactions();
+    assert_eq!(transactions.len(), 3);
+    assert!(transactions[2].is_mint());
+    assert_eq!(
+        block.transactions_root,
+        ephemeral_merkle_root(transactions.iter().map(|tx| tx.to_bytes()))
+    );
+    assert_eq!(block.receipts_roots().len(), 2);
+
+    let storage = executor.into_storage();
+    assert_eq!(stored_value(&storage, &counter), 1);
+    assert_eq!(
+        storage
+            .contract_asset_id_balance(&coinbase, &AssetId::BASE)
+            .unwrap(),
+        Some(block.fees)
+    );
+}
+
+#[test]
+fn execute__discards_state_changes_of_reverted_script() {
+    let mut test_context = test_context();
+    let counter = test_context
+        .setup_contract(counter_contract(), None, None)
+        .contract_id;
+    let tx = call_script(&mut test_context, counter, op::rvrt(RegId::ONE));
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [tx])
+        .expect("Failed to execute the block");
+
+    let included: Vec<_> = block.included().collect();
+    assert_eq!(included.len(), 1);
+    assert!(included[0].is_reverted());
+    assert!(
+        included[0].fee > 0,
+        "The reverted script still pays the fee"
+    );
+    assert_eq!(stored_value(executor.as_ref(), &counter), 0);
+}
+
+#[test]
+fn execute__skips_invalid_transactions() {
+    let mut test_context = test_context();
+    let missing_contract = ContractId::new([1; 32]);
+    let invalid = call_script(&mut test_context, missing_contract, op::ret(RegId::ONE));
+    let invalid_id = invalid.id();
+    let valid = simple_script(&mut test_context, vec![op::ret(RegId::ONE)]);
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(
+            &header(ContractId::zeroed()),
+            [CheckedTransaction::from(invalid), valid.into()],
+        )
+        .expect("Failed to execute the block");
+
+    let skipped: Vec<_> = block.skipped().collect();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].id, invalid_id);
+    assert!(
+        matches!(
+            skipped[0].reason,
+            TransactionSkipReason::Execution(InterpreterError::Panic(
+                PanicReason::ContractNotInInputs
+            ))
+        ),
+        "{:?}",
+        skipped[0].reason
+    );
+
+    let included: Vec<_> = block.included().collect();
+    assert_eq!(included.len(), 1);
+    assert_eq!(included[0].tx_pointer, TxPointer::new(HEIGHT.into(), 0));
+    assert_eq!(block.mint.tx_pointer(), &TxPointer::new(HEIGHT.into(), 1));
+}
+
+#[test]
+fn execute__skips_mint_transactions() {
+    let test_context = test_context();
+    let params = ConsensusParameters::standard();
+    let mint = Transaction::mint(
+        TxPointer::new(HEIGHT.into(), 0),
+        Default::default(),
+        Default::default(),
+        0,
+        *params.base_asset_id(),
+        0,
+    )
+    .into_checked(HEIGHT.into(), &params)
+    .expect("Valid mint");
+
+    let mut executor = BlockExecutor::new(test_context.get_storage().clone(), params);
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [mint])
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.included().count(), 0);
+    let skipped: Vec<_> = block.skipped().collect();
+    assert!(
+        matches!(skipped[..], [skipped] if matches!(skipped.reason, TransactionSkipReason::Mint))
+    );
+}
+
+#[test]
+fn execute__contract_created_in_block_is_callable_by_next_transaction() {
+    let mut test_context = test_context();
+    let program: Witness = counter_contract().into_iter().collect::<Vec<u8>>().into();
+    let salt: Salt = test_context.rng.gen();
+    let contract = Contract::from(program.as_ref());
+    let contract_id =
+        contract.id(&salt, &contract.root(), &Contract::default_state_root());
+    let create = TransactionBuilder::create(program, salt, vec![])
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_random_fee_input()
+        .add_contract_created()
+        .finalize()
+        .into_checked(HEIGHT.into(), &ConsensusParameters::standard())
+        .expect("Valid create");
+    let call = call_script(&mut test_context, contract_id, op::ret(RegId::ONE));
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(
+            &header(ContractId::zeroed()),
+            [CheckedTransaction::from(create), call.into()],
+        )
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.skipped().count(), 0);
+    assert!(block.included().all(|tx| !tx.is_reverted()));
+    assert_eq!(stored_value(executor.as_ref(), &contract_id), 1);
+}
+
+#[test]
+fn execute__message_outbox_root_covers_sent_messages() {
+    let mut test_context = test_context();
+    let tx = simple_script(
+        &mut test_context,
+        vec![
+            op::smo(RegId::ZERO, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::smo(RegId::ZERO, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(RegId::ONE),
+        ],
+    );
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [tx])
+        .expect("Failed to execute the block");
+
+    let message_ids: Vec<_> = block
+        .included()
+        .flat_map(|tx| tx.receipts.iter().filter_map(Receipt::message_id))
+        .collect();
+    assert_eq!(message_ids.len(), 2);
+    assert_eq!(
+        block.message_outbox_root,
+        ephemeral_merkle_root(message_ids.iter())
+    );
+}
+
+#[test]
+fn execute__fees_are_burned_without_coinbase() {
+    let mut test_context = test_context();
+    let tx = simple_script(&mut test_context, vec![op::ret(RegId::ONE)]);
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [tx])
+        .expect("Failed to execute the block");
+
+    assert!(block.fees > 0);
+    assert_eq!(*block.mint.mint_amount(), 0);
+}
+
+#[test]
+fn execute__fails_when_coinbase_does_not_exist() {
+    let test_context = test_context();
+    let coinbase = ContractId::new([1; 32]);
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let result = executor.execute(&header(coinbase), Vec::<Checked<Script>>::new());
+
+    assert!(matches!(
+        result,
+        Err(BlockExecutionError::CoinbaseNotFound(id)) if id == coinbase
+    ));
+}
+
+#[test]
+fn execute__block_without_transactions_contains_only_mint() {
+    let test_context = test_context();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), Vec::<Checked<Script>>::new())
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.results.len(), 0);
+    assert_eq!(block.mint.tx_pointer(), &TxPointer::new(HEIGHT.into(), 0));
+    assert_eq!(
+        block.message_outbox_root,
+        ephemeral_merkle_root(core::iter::empty::<Bytes32>())
+    );
+}
+
+#[test]
+fn execute__transactions_root_covers_submitted_transactions() {
+    let mut test_context = test_context();
+    // Without the change output, the execution doesn't modify the transactions
+    let txs: Vec<_> = (0..2)
+        .map(|_| {
+            test_context
+                .start_script(vec![op::ret(RegId::ONE)], vec![])
+                .script_gas_limit(100_000)
+                .coin_input(AssetId::BASE, MAX_FEE_LIMIT)
+                .build()
+        })
+        .collect();
+    let mut submitted: Vec<Transaction> = txs
+        .iter()
+        .map(|tx| tx.transaction().clone().into())
+        .collect();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), txs)
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.included().count(), 2);
+    submitted.push(block.mint.clone().into());
+    assert_eq!(
+        block.transactions_root,
+        ephemeral_merkle_root(submitted.iter().map(|tx| tx.to_bytes()))
+    );
+}
+
+#[test]
+fn execute__mint_spends_coinbase_contract_state() {
+    let mut test_context = test_context();
+    let coinbase = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let coinbase_utxo = ContractUtxo {
+        utxo_id: UtxoId::new([7; 32].into(), 1),
+        tx_pointer: TxPointer::new(1.into(), 2),
+    };
+    let tx = simple_script(&mut test_context, vec![op::ret(RegId::ONE)]);
+
+    let params = ConsensusParameters::standard();
+    let mut executor = BlockExecutor::new(test_context.get_storage().clone(), params);
+    executor.set_contract_utxo(coinbase, coinbase_utxo);
+    let balance_root = |executor: &BlockExecutor<MemoryStorage>| {
+        Bytes32::new(
+            MerkleRootStorage::<ContractId, ContractsAssets>::root(
+                executor.as_ref(),
+                &coinbase,
+            )
+            .unwrap(),
+        )
+    };
+    let state_root = Bytes32::new(
+        MerkleRootStorage::<ContractId, ContractsState>::root(
+            executor.as_ref(),
+            &coinbase,
+        )
+        .unwrap(),
+    );
+    let balance_root_before = balance_root(&executor);
+
+    let block = executor
+        .execute(&header(coinbase), [tx])
+        .expect("Failed to execute the block");
+
+    assert!(block.fees > 0);
+    let input = block.mint.input_contract();
+    assert_eq!(input.utxo_id, coinbase_utxo.utxo_id);
+    assert_eq!(input.tx_pointer, coinbase_utxo.tx_pointer);
+    assert_eq!(input.balance_root, balance_root_before);
+    assert_eq!(input.state_root, state_root);
+    let output = block.mint.output_contract();
+    assert_eq!(output.balance_root, balance_root(&executor));
+    assert_ne!(output.balance_root, balance_root_before);
+    assert_eq!(output.state_root, state_root);
+
+    // The next `Mint` spends the output of the previous one
+    let mint_id = block.mint.id(&executor.consensus_params().chain_id());
+    let block = executor
+        .execute(
+            &BlockHeader::new((HEIGHT + 1).into(), 1235, coinbase, GAS_PRICE),
+            Vec::<Checked<Script>>::new(),
+        )
+        .expect("Failed to execute the block");
+    assert_eq!(block.mint.input_contract().utxo_id, UtxoId::new(mint_id, 0));
+    assert_eq!(
+        block.mint.input_contract().tx_pointer,
+        TxPointer::new(HEIGHT.into(), 1)
+    );
+}
+
+#[test]
+fn execute__skips_transactions_not_mature_at_block_height() {
+    let mut test_context = test_context();
+    test_context.block_height((HEIGHT + 1).into());
+    let immature = test_context
+        .start_script(vec![op::ret(RegId::ONE)], vec![])
+        .script_gas_limit(100_000)
+        .coin_input(AssetId::BASE, MAX_FEE_LIMIT)
+        .change_output(AssetId::BASE)
+        .build();
+    let mut immature = immature.transaction().clone();
+    immature.set_maturity((HEIGHT + 1).into());
+    let immature = immature
+        .into_checked_basic((HEIGHT + 1).into(), &ConsensusParameters::standard())
+        .expect("Valid at the next height");
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [immature])
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.included().count(), 0);
+    let skipped: Vec<_> = block.skipped().collect();
+    assert!(
+        matches!(
+            skipped[..],
+            [SkippedTransaction {
+                reason: TransactionSkipReason::Execution(InterpreterError::CheckError(
+                    CheckError::Validity(ValidityError::TransactionMaturity)
+                )),
+                ..
+            }]
+        ),
+        "{skipped:?}"
+    );
+}
+
+#[test]
+fn execute__skips_transactions_expired_at_block_height() {
+    let mut test_context = test_context();
+    test_context.block_height((HEIGHT - 1).into());
+    let expired = test_context
+        .start_script(vec![op::ret(RegId::ONE)], vec![])
+        .script_gas_limit(100_000)
+        .coin_input(AssetId::BASE, MAX_FEE_LIMIT)
+        .change_output(AssetId::BASE)
+        .build();
+    let mut expired = expired.transaction().clone();
+    expired.set_expiration((HEIGHT - 1).into());
+    let expired = expired
+        .into_checked_basic((HEIGHT - 1).into(), &ConsensusParameters::standard())
+        .expect("Valid at the previous height");
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute(&header(ContractId::zeroed()), [expired])
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.included().count(), 0);
+    let skipped: Vec<_> = block.skipped().collect();
+    assert!(
+        matches!(
+            skipped[..],
+            [SkippedTransaction {
+                reason: TransactionSkipReason::Execution(InterpreterError::CheckError(
+                    CheckError::Validity(ValidityError::TransactionExpiration)
+                )),
+                ..
+            }]
+        ),
+        "{skipped:?}"
+    );
+}
+
+/// Contract that increments the value stored under the zeroed key.
+#[cfg(feature = "std")]
+fn increment_contract() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, Bytes32::LEN as u32),
+        op::aloc(0x10),
+        op::srw(0x12, 0x11, RegId::HP),
+        op::addi(0x12, 0x12, 1),
+        op::sww(RegId::HP, 0x11, 0x12),
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn execute_parallel__matches_sequential_execution() {
+    let mut test_context = test_context();
+    let coinbase = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let first = test_context
+        .setup_contract(increment_contract(), None, None)
+        .contract_id;
+    let second = test_context
+        .setup_contract(increment_contract(), None, None)
+        .contract_id;
+    let created: Witness = increment_contract().into_iter().collect::<Vec<u8>>().into();
+    let salt: Salt = test_context.rng.gen();
+    let contract = Contract::from(created.as_ref());
+    let created_id =
+        contract.id(&salt, &contract.root(), &Contract::default_state_root());
+    let create = TransactionBuilder::create(created, salt, vec![])
+        .max_fee_limit(MAX_FEE_LIMIT)
+        .add_random_fee_input()
+        .add_contract_created()
+        .finalize()
+        .into_checked(HEIGHT.into(), &ConsensusParameters::standard())
+        .expect("Valid create");
+
+    let txs: Vec<CheckedTransaction> = vec![
+        call_script(&mut test_context, first, op::ret(RegId::ONE)).into(),
+        call_script(&mut test_context, second, op::ret(RegId::ONE)).into(),
+        call_script(&mut test_context, first, op::rvrt(RegId::ONE)).into(),
+        simple_script(
+            &mut test_context,
+            vec![
+                op::smo(RegId::ZERO, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+        )
+        .into(),
+        call_script(&mut test_context, first, op::ret(RegId::ONE)).into(),
+        // Skipped, since the contract is created by the next transaction
+        call_script(&mut test_context, created_id, op::ret(RegId::ONE)).into(),
+        create.into(),
+        call_script(&mut test_context, created_id, op::ret(RegId::ONE)).into(),
+        call_script(&mut test_context, second, op::ret(RegId::ONE)).into(),
+        call_script(&mut test_context, created_id, op::ret(RegId::ONE)).into(),
+    ];
+
+    let mut sequential = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let expected = sequential
+        .execute(&header(coinbase), txs.clone())
+        .expect("Failed to execute the block");
+    assert_eq!(expected.skipped().count(), 1);
+    let expected_storage = sequential.into_storage();
+    assert_eq!(stored_value(&expected_storage, &first), 2);
+    assert_eq!(stored_value(&expected_storage, &second), 2);
+    assert_eq!(stored_value(&expected_storage, &created_id), 2);
+
+    for threads in [1, 2, 4] {
+        let mut parallel = BlockExecutor::new(
+            test_context.get_storage().clone(),
+            ConsensusParameters::standard(),
+        );
+        let block = parallel
+            .execute_parallel(
+                &header(coinbase),
+                txs.clone(),
+                NonZeroUsize::new(threads).unwrap(),
+            )
+            .expect("Failed to execute the block");
+
+        assert_eq!(
+            block.included().collect::<Vec<_>>(),
+            expected.included().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            block.skipped().map(|tx| tx.id).collect::<Vec<_>>(),
+            expected.skipped().map(|tx| tx.id).collect::<Vec<_>>()
+        );
+        assert_eq!(block.mint, expected.mint);
+        assert_eq!(block.transactions_root, expected.transactions_root);
+        assert_eq!(block.message_outbox_root, expected.message_outbox_root);
+
+        let storage = parallel.into_storage();
+        for contract_id in [first, second, created_id] {
+            assert_eq!(
+                stored_value(&storage, &contract_id),
+                stored_value(&expected_storage, &contract_id)
+            );
+        }
+        assert_eq!(
+            storage
+                .contract_asset_id_balance(&coinbase, &AssetId::BASE)
+                .unwrap(),
+            Some(block.fees)
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn execute_parallel__block_without_transactions_contains_only_mint() {
+    let test_context = test_context();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let block = executor
+        .execute_parallel(
+            &header(ContractId::zeroed()),
+            Vec::<Checked<Script>>::new(),
+            NonZeroUsize::new(4).unwrap(),
+        )
+        .expect("Failed to execute the block");
+
+    assert_eq!(block.results.len(), 0);
+    assert_eq!(block.mint.tx_pointer(), &TxPointer::new(HEIGHT.into(), 0));
+}
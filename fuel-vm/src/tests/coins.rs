@@ -22,6 +22,7 @@ use fuel_tx::{
     ContractId,
     ContractIdExt,
     Output,
+    PanicContext,
     PanicReason,
     Receipt,
     ScriptExecutionResult,
@@ -428,6 +429,60 @@ fn transfer_to_contract_external(
     })
 }
 
+#[test]
+#[allow(non_snake_case)]
+fn transfer_to_contract_external__not_enough_balance_sets_panic_context() {
+    let contract_id_ptr = 0x11;
+    let asset_id_ptr = 0x12;
+    let reg_amount = 0x13;
+
+    let ops = vec![
+        op::movi(reg_amount, 11),
+        op::gtf_args(contract_id_ptr, RegId::ZERO, GTFArgs::ScriptData),
+        op::addi(
+            asset_id_ptr,
+            contract_id_ptr,
+            ContractId::LEN.try_into().unwrap(),
+        ),
+        op::tr(contract_id_ptr, reg_amount, asset_id_ptr),
+        op::ret(RegId::ONE),
+    ];
+
+    let mut test_context = TestBuilder::new(1234u64);
+    let asset_id: AssetId = test_context.rng.gen();
+
+    let contract = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+
+    let script_data: Vec<u8> = contract
+        .to_bytes()
+        .into_iter()
+        .chain(asset_id.to_bytes())
+        .collect();
+
+    let receipts = test_context
+        .start_script(ops, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(contract)
+        .coin_input(asset_id, 10)
+        .fee_input()
+        .contract_output(&contract)
+        .change_output(asset_id)
+        .execute()
+        .receipts()
+        .to_vec();
+
+    let Receipt::Panic {
+        reason, context, ..
+    } = &receipts[receipts.len() - 2]
+    else {
+        panic!("Expected a panic receipt");
+    };
+    assert_eq!(reason.reason(), &PanicReason::NotEnoughBalance);
+    assert_eq!(context, &PanicContext::AssetId(asset_id));
+}
+
 enum TrTo {
     /// Transfer to self
     This,
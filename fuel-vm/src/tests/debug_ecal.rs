@@ -0,0 +1,117 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+use fuel_vm::{
+    interpreter::{
+        DebugEcal,
+        DebugMessage,
+        DebugOp,
+        DebugValue,
+    },
+    prelude::*,
+};
+use itertools::Itertools;
+use test_case::test_case;
+
+fn run_debug_script(
+    script: Vec<Instruction>,
+    script_data: Vec<u8>,
+) -> (Vec<Receipt>, Vec<DebugMessage>) {
+    let mut vm: Interpreter<_, _, Script, DebugEcal> = Interpreter::with_memory_storage();
+
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(script.into_iter().collect(), script_data)
+        .script_gas_limit(1_000_000)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate a checked tx")
+        .test_into_ready();
+    let receipts = vm
+        .transact(tx)
+        .expect("Transaction should be executed")
+        .receipts()
+        .to_vec();
+
+    (receipts, vm.ecal_state().sink().clone())
+}
+
+#[test]
+fn debug_ecal__prints_to_sink_without_receipts() {
+    let script = vec![
+        op::movi(0x20, DebugOp::Register as u32),
+        op::movi(0x10, 42),
+        op::ecal(0x20, 0x10, RegId::ZERO, RegId::ZERO),
+        op::gtf_args(0x10, 0x00, GTFArgs::ScriptData),
+        op::movi(0x11, 2),
+        op::movi(0x20, DebugOp::Hex as u32),
+        op::ecal(0x20, 0x10, 0x11, RegId::ZERO),
+        op::movi(0x20, DebugOp::Utf8 as u32),
+        op::ecal(0x20, 0x10, 0x11, RegId::ZERO),
+        op::movi(0x20, DebugOp::Location as u32),
+        op::ecal(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let (receipts, messages) = run_debug_script(script, b"hi".to_vec());
+
+    assert!(matches!(
+        receipts[..],
+        [Receipt::Return { .. }, Receipt::ScriptResult { .. }]
+    ));
+    let printed = messages
+        .iter()
+        .map(|message| message.to_string())
+        .collect_vec();
+    assert_eq!(
+        printed,
+        vec![
+            "[script +0x8] $r16 = 42 (0x2a)",
+            "[script +0x18] 0x6869",
+            "[script +0x20] hi",
+            "[script +0x28]",
+        ]
+    );
+    assert_eq!(
+        messages[0].value,
+        DebugValue::Register {
+            register: RegId::new(0x10),
+            value: 42
+        }
+    );
+}
+
+#[test_case(4, 0; "unknown operation")]
+#[test_case(DebugOp::Hex as u32, 1; "memory out of bounds")]
+fn debug_ecal__panics_on_invalid_arguments(operation: u32, len: u32) {
+    let script = vec![
+        op::movi(0x20, operation),
+        op::not(0x10, RegId::ZERO),
+        op::movi(0x11, len),
+        op::ecal(0x20, 0x10, 0x11, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let (receipts, messages) = run_debug_script(script, vec![]);
+
+    assert!(messages.is_empty());
+    assert!(matches!(receipts[0], Receipt::Panic { .. }));
+}
@@ -0,0 +1,268 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_crypto::SecretKey;
+use fuel_tx::field::Witnesses;
+use fuel_types::canonical::Serialize;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    checked_transaction::{
+        CheckError,
+        Checked,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::{
+        ContractsAssetsStorage,
+        StateOverrides,
+    },
+};
+
+use super::test_helpers::client;
+
+const GAS_LIMIT: Word = 1_000_000;
+
+fn call_contract(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+) -> Checked<Script> {
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(GAS_LIMIT)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+}
+
+fn contract_receipts(receipts: &[Receipt], contract_id: ContractId) -> Vec<&Receipt> {
+    receipts
+        .iter()
+        .filter(|receipt| receipt.id() == Some(&contract_id))
+        .collect()
+}
+
+#[test]
+fn dry_run_with_overrides__executes_replaced_contract_code() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(vec![op::ret(RegId::ONE)], None, None)
+        .contract_id;
+    let tx = call_contract(&mut test_context, contract_id);
+
+    let upgraded: Vec<Instruction> = vec![op::movi(0x10, 2), op::ret(0x10)];
+    let overrides = StateOverrides::new().with_contract_code(
+        contract_id,
+        upgraded.into_iter().collect::<Vec<u8>>().into(),
+    );
+    let mut client = client(&test_context);
+    let state = client
+        .dry_run_with_overrides(tx, &overrides)
+        .expect("Dry run should succeed");
+
+    assert!(matches!(
+        contract_receipts(state.receipts(), contract_id)[..],
+        [Receipt::Return { val: 2, .. }]
+    ));
+    let code = client
+        .as_ref()
+        .storage_contract(&contract_id)
+        .unwrap()
+        .unwrap()
+        .into_owned();
+    assert_eq!(code.as_ref(), op::ret(RegId::ONE).to_bytes());
+}
+
+#[test]
+fn dry_run_with_overrides__applies_storage_slots_and_balances() {
+    let mut test_context = TestBuilder::new(2322u64);
+    // Logs the first word of the slot with the zero key and the balance of the zero
+    // asset, then overwrites the slot.
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x12, Bytes32::LEN as u32),
+                op::aloc(0x12),
+                op::srw(0x10, 0x11, RegId::HP),
+                op::bal(0x13, RegId::HP, RegId::FP),
+                op::log(0x10, 0x11, 0x13, RegId::ZERO),
+                op::sww(RegId::HP, 0x11, RegId::ONE),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+    let tx = call_contract(&mut test_context, contract_id);
+
+    let mut slot = [0u8; 32];
+    slot[7] = 7;
+    let overrides = StateOverrides::new()
+        .with_storage_slot(contract_id, Bytes32::zeroed(), slot.to_vec().into())
+        .with_balance(contract_id, AssetId::zeroed(), 100);
+    let mut client = client(&test_context);
+    let state = client
+        .dry_run_with_overrides(tx, &overrides)
+        .expect("Dry run should succeed");
+
+    assert!(matches!(
+        contract_receipts(state.receipts(), contract_id)[..],
+        [
+            Receipt::Log {
+                ra: 7,
+                rb: 1,
+                rc: 100,
+                ..
+            },
+            Receipt::Return { .. }
+        ]
+    ));
+    assert_eq!(client.as_ref().all_contract_state().count(), 0);
+    assert_eq!(
+        client
+            .as_ref()
+            .contract_asset_id_balance(&contract_id, &AssetId::zeroed())
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn dry_run_with_overrides__uses_block_height_and_timestamp() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![
+                op::bhei(0x10),
+                op::time(0x11, 0x10),
+                op::log(0x10, 0x11, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(GAS_LIMIT)
+        .fee_input()
+        .build();
+
+    let overrides = StateOverrides::new()
+        .with_block_height(42.into())
+        .with_timestamp(1234);
+    let state = client(&test_context)
+        .dry_run_with_overrides(tx, &overrides)
+        .expect("Dry run should succeed");
+
+    assert!(matches!(
+        state.receipts()[0],
+        Receipt::Log {
+            ra: 42,
+            rb: 1234,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn dry_run_with_overrides__does_not_modify_memory_of_last_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    );
+    let tx = test_context
+        .start_script(
+            vec![
+                op::movi(0x10, 0xab),
+                op::cfei(8),
+                op::sw(RegId::SSP, 0x10, 0),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(GAS_LIMIT)
+        .fee_input()
+        .build();
+    transactor.transact(tx);
+    let memory = transactor.interpreter().memory().clone();
+
+    let tx = test_context
+        .start_script(
+            vec![
+                op::cfei(8),
+                op::sw(RegId::SSP, RegId::ONE, 0),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(GAS_LIMIT)
+        .fee_input()
+        .build();
+    transactor
+        .dry_run_with_overrides(tx, &StateOverrides::new())
+        .expect("Dry run should succeed");
+
+    assert_eq!(transactor.interpreter().memory(), &memory);
+}
+
+#[test]
+fn dry_run_with_overrides__verifies_signatures_unless_skipped() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+    let mut tx =
+        TransactionBuilder::script(op::ret(RegId::ONE).to_bytes().to_vec(), vec![])
+            .script_gas_limit(GAS_LIMIT)
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                1_000,
+                AssetId::zeroed(),
+                rng.gen(),
+            )
+            .finalize();
+    tx.witnesses_mut()[0] = Witness::from(vec![0u8; 64]);
+    let checked = tx
+        .into_checked_basic(Default::default(), &params)
+        .expect("Basic checks should pass");
+
+    let mut client = MemoryClient::default();
+
+    let err = client
+        .dry_run_with_overrides(checked.clone(), &StateOverrides::new())
+        .expect_err("Signature check should fail");
+    assert!(matches!(
+        err,
+        InterpreterError::CheckError(CheckError::Validity(_))
+    ));
+
+    let overrides = StateOverrides::new().with_skipped_signature_checks();
+    let state = client
+        .dry_run_with_overrides(checked, &overrides)
+        .expect("Dry run should succeed");
+    assert!(matches!(
+        state.receipts()[..],
+        [Receipt::Return { val: 1, .. }, Receipt::ScriptResult { .. }]
+    ));
+}
@@ -0,0 +1,242 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RawInstruction,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    call::CallFrame,
+    checked_transaction::Checked,
+    consts::VM_REGISTER_COUNT,
+    interpreter::{
+        ExecutionHook,
+        InterpreterParams,
+        StorageAccess,
+        StorageAccessKind,
+    },
+    prelude::*,
+};
+
+#[derive(Debug, Default)]
+struct RecordingHook {
+    before: Vec<RawInstruction>,
+    after: Vec<RawInstruction>,
+    calls: Vec<ContractId>,
+    returns: Vec<ContractId>,
+    storage: Vec<StorageAccess>,
+    receipts: Vec<Receipt>,
+}
+
+impl ExecutionHook for RecordingHook {
+    fn before_instruction(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        instruction: RawInstruction,
+    ) {
+        self.before.push(instruction);
+    }
+
+    fn after_instruction(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        instruction: RawInstruction,
+    ) {
+        self.after.push(instruction);
+    }
+
+    fn on_call(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        frame: &CallFrame,
+    ) {
+        self.calls.push(*frame.to());
+    }
+
+    fn on_return(
+        &mut self,
+        _: &[Word; VM_REGISTER_COUNT],
+        _: &MemoryInstance,
+        frame: &CallFrame,
+    ) {
+        self.returns.push(*frame.to());
+    }
+
+    fn on_storage_access(&mut self, access: &StorageAccess) {
+        self.storage.push(*access);
+    }
+
+    fn on_receipt(&mut self, receipt: &Receipt) {
+        self.receipts.push(receipt.clone());
+    }
+}
+
+fn run_with_hook(
+    test_context: &TestBuilder,
+    tx: Checked<Script>,
+) -> (Vec<Receipt>, RecordingHook) {
+    let mut vm = Interpreter::<_, _, Script>::with_storage(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+    .with_execution_hook(RecordingHook::default());
+    let receipts = vm
+        .transact(tx.test_into_ready())
+        .expect("Transaction should be executed")
+        .receipts()
+        .to_vec();
+
+    (receipts, core::mem::take(vm.execution_hook_mut()))
+}
+
+#[test]
+fn execution_hook__observes_instructions_calls_storage_and_receipts() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, Bytes32::LEN as u32),
+                op::aloc(0x10),
+                op::movi(0x11, 7),
+                op::sww(RegId::HP, 0x12, 0x11),
+                op::srw(0x13, 0x12, RegId::HP),
+                op::scwq(RegId::HP, 0x12, RegId::ONE),
+                op::ret(0x13),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let (receipts, hook) = run_with_hook(&test_context, tx);
+
+    // 3 script instructions and 7 contract instructions
+    assert_eq!(hook.before.len(), 10);
+    assert_eq!(hook.before, hook.after);
+    assert_eq!(hook.calls, vec![contract_id]);
+    assert_eq!(hook.returns, vec![contract_id]);
+    assert_eq!(
+        hook.storage
+            .iter()
+            .map(|access| (access.kind, access.contract_id, access.key, access.slots))
+            .collect::<Vec<_>>(),
+        vec![
+            (StorageAccessKind::Write, contract_id, Bytes32::zeroed(), 1),
+            (StorageAccessKind::Read, contract_id, Bytes32::zeroed(), 1),
+            (StorageAccessKind::Clear, contract_id, Bytes32::zeroed(), 1),
+        ]
+    );
+    assert_eq!(hook.receipts, receipts);
+    assert!(matches!(
+        hook.receipts[..],
+        [
+            Receipt::Call { .. },
+            Receipt::Return { val: 7, .. },
+            Receipt::Return { val: 1, .. },
+            Receipt::ScriptResult { .. },
+        ]
+    ));
+}
+
+#[test]
+fn execution_hook__observes_panic_receipt() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![
+                op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+                op::div(0x10, RegId::ONE, RegId::ZERO),
+            ],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let (receipts, hook) = run_with_hook(&test_context, tx);
+
+    assert_eq!(hook.before.len(), 2);
+    assert_eq!(hook.receipts, receipts);
+    assert!(matches!(
+        hook.receipts[..],
+        [
+            Receipt::Log { ra: 1, .. },
+            Receipt::Panic { .. },
+            Receipt::ScriptResult { .. },
+        ]
+    ));
+}
+
+#[test]
+fn execution_hook__does_not_observe_failed_storage_access() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, Bytes32::LEN as u32),
+                op::aloc(0x10),
+                op::sww(RegId::HP, RegId::ZERO, RegId::ONE),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let (receipts, hook) = run_with_hook(&test_context, tx);
+
+    assert!(hook.storage.is_empty());
+    assert!(matches!(
+        receipts[..],
+        [
+            Receipt::Call { .. },
+            Receipt::Panic { .. },
+            Receipt::ScriptResult { .. },
+        ]
+    ));
+}
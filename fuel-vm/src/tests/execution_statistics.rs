@@ -0,0 +1,181 @@
+#![allow(non_snake_case)]
+
+use alloc::vec;
+
+use fuel_asm::{
+    op,
+    Opcode,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    interpreter::{
+        ExecutionStatistics,
+        InterpreterParams,
+    },
+    prelude::*,
+};
+
+#[test]
+fn execution_statistics__counts_calls_storage_copies_and_memory() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, 64),
+                op::aloc(0x10),
+                op::movi(0x11, 7),
+                op::sww(RegId::HP, 0x12, 0x11),
+                op::srw(0x13, 0x12, RegId::HP),
+                op::movi(0x14, 32),
+                op::mcp(RegId::HP, 0x10, 0x14),
+                op::ret(0x13),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::mcpi(RegId::HP, 0x10, 8),
+            op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+    .with_execution_hook(ExecutionStatistics::new());
+    transactor.transact(tx.clone());
+
+    let (state_transition, statistics) = transactor
+        .to_owned_state_transition_with_statistics()
+        .expect("The transaction was executed");
+    let receipts = state_transition.receipts().len();
+
+    // 5 script instructions and 8 contract instructions
+    assert_eq!(statistics.total_instructions(), 13);
+    assert_eq!(statistics.instructions().get(&Opcode::MOVI), Some(&4));
+    assert_eq!(statistics.instructions().get(&Opcode::CALL), Some(&1));
+    assert_eq!(statistics.calls(), 1);
+    assert_eq!(statistics.max_call_depth(), 1);
+    assert_eq!(statistics.storage_slots_read(), 1);
+    assert_eq!(statistics.storage_slots_written(), 1);
+    assert_eq!(statistics.bytes_copied(), 32 + 8);
+    assert_eq!(statistics.peak_heap(), 64);
+    assert!(statistics.peak_stack() > 0);
+    assert_eq!(statistics.receipts(), receipts as u64);
+
+    // The statistics are reset for the next transaction
+    transactor.transact(tx);
+    assert_eq!(transactor.statistics(), Some(&statistics));
+}
+
+#[test]
+fn execution_statistics__ignores_failed_copy() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![
+                op::not(0x10, RegId::ZERO),
+                op::movi(0x11, 8),
+                op::mcp(RegId::HP, 0x10, 0x11),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+    .with_execution_hook(ExecutionStatistics::new());
+    transactor.transact(tx);
+
+    let statistics = transactor
+        .statistics()
+        .expect("The transaction was executed");
+    assert_eq!(statistics.total_instructions(), 3);
+    assert_eq!(statistics.bytes_copied(), 0);
+    assert_eq!(statistics.calls(), 0);
+    // Panic and script result
+    assert_eq!(statistics.receipts(), 2);
+}
+
+#[test]
+fn execution_statistics__ignores_failed_storage_access() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, 32),
+                op::aloc(0x10),
+                op::srw(0x11, 0x12, RegId::HP),
+                op::sww(RegId::HP, RegId::ZERO, RegId::ONE),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+    .with_execution_hook(ExecutionStatistics::new());
+    transactor.transact(tx);
+
+    let (state_transition, statistics) = transactor
+        .state_transition_with_statistics()
+        .expect("The transaction was executed");
+    assert!(matches!(
+        state_transition.receipts(),
+        [
+            Receipt::Call { .. },
+            Receipt::Panic { .. },
+            Receipt::ScriptResult { .. },
+        ]
+    ));
+    // The write to the storage panics, so only the read is counted
+    assert_eq!(statistics.storage_slots_read(), 1);
+    assert_eq!(statistics.storage_slots_written(), 0);
+}
@@ -1,7 +1,19 @@
-use alloc::vec;
+#![allow(non_snake_case)]
+
+use alloc::{
+    string::String,
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
 use fuel_asm::{
     op,
     GTFArgs,
+    Instruction,
     RegId,
     Word,
 };
@@ -14,7 +26,13 @@ use fuel_tx::{
     ScriptExecutionResult,
     TransactionBuilder,
 };
-use fuel_vm::prelude::*;
+use fuel_vm::{
+    interpreter::{
+        EcalRegistry,
+        HostError,
+    },
+    prelude::*,
+};
 use itertools::Itertools;
 use test_case::test_case;
 
@@ -40,8 +58,8 @@ fn attempt_ecal_without_handler() {
 pub struct NoopEcal;
 
 impl ::fuel_vm::interpreter::EcalHandler for NoopEcal {
-    fn ecal<M, S, Tx>(
-        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self, Hook>,
         _: RegId,
         _: RegId,
         _: RegId,
@@ -88,8 +106,8 @@ pub struct SumProdEcal;
 impl ::fuel_vm::interpreter::EcalHandler for SumProdEcal {
     /// This ecal fn computes saturating sum and product of inputs (a,b,c,d),
     /// and stores them in a and b respectively. It charges only a single gas.
-    fn ecal<M, S, Tx>(
-        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self, Hook>,
         a: RegId,
         b: RegId,
         c: RegId,
@@ -169,8 +187,8 @@ impl ::fuel_vm::interpreter::EcalHandler for ComplexEcal {
     const INC_PC: bool = false;
 
     /// Ecal meant for testing cornercase behavior of the handler.
-    fn ecal<M, S, Tx>(
-        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self>,
+    fn ecal<M, S, Tx, Hook>(
+        vm: &mut ::fuel_vm::prelude::Interpreter<M, S, Tx, Self, Hook>,
         a: RegId,
         _b: RegId,
         _c: RegId,
@@ -225,3 +243,118 @@ fn complex_ecal_fn(val: u32, result: PanicReason) {
 
     assert_panics(receipts, result);
 }
+
+fn run_registry_script(
+    registry: EcalRegistry,
+    script: Vec<Instruction>,
+    script_data: Vec<u8>,
+) -> Vec<Receipt> {
+    let vm: Interpreter<_, _, Script, EcalRegistry> =
+        Interpreter::with_memory_storage_and_ecal(registry);
+
+    let mut client = MemoryClient::from_txtor(vm.into());
+    let consensus_params = ConsensusParameters::standard();
+    let tx = TransactionBuilder::script(script.into_iter().collect(), script_data)
+        .script_gas_limit(1_000_000)
+        .maturity(Default::default())
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &consensus_params)
+        .expect("failed to generate a checked tx");
+    client.transact(tx);
+    client.receipts().expect("Expected receipts").to_vec()
+}
+
+const COUNTER: Word = 1;
+const SUM: Word = 2;
+const BYTES_SUM: Word = 3;
+const FAILING: Word = 4;
+
+fn test_registry(counter: Arc<AtomicU64>) -> EcalRegistry {
+    EcalRegistry::new()
+        .with_function(COUNTER, 1, move |_, ()| {
+            Ok(counter.fetch_add(1, Ordering::Relaxed).saturating_add(1))
+        })
+        .with_function(SUM, 1, |_, (a, b): (Word, Word)| Ok(a.saturating_add(b)))
+        .with_function(BYTES_SUM, 1, |_, (bytes,): (Vec<u8>,)| {
+            Ok(bytes.into_iter().map(Word::from).sum())
+        })
+        .with_function(FAILING, 1, |_, (text,): (String,)| {
+            text.parse::<Word>().map_err(HostError::failed)
+        })
+}
+
+#[test]
+fn ecal_registry__dispatches_to_registered_functions() {
+    let counter = Arc::new(AtomicU64::new(0));
+    let script = vec![
+        op::movi(0x20, COUNTER as u32),
+        op::ecal(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::movi(0x20, COUNTER as u32),
+        op::ecal(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::movi(0x21, SUM as u32),
+        op::movi(0x10, 40),
+        op::ecal(0x21, 0x10, RegId::ONE, RegId::ZERO),
+        op::movi(0x22, BYTES_SUM as u32),
+        op::gtf_args(0x10, 0x00, GTFArgs::ScriptData),
+        op::movi(0x11, 3),
+        op::ecal(0x22, 0x10, 0x11, RegId::ZERO),
+        op::log(0x20, 0x21, 0x22, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts =
+        run_registry_script(test_registry(counter.clone()), script, vec![1, 2, 3]);
+
+    let Receipt::Log { ra, rb, rc, .. } = receipts.first().unwrap() else {
+        panic!("Expected a log receipt");
+    };
+    assert_eq!((*ra, *rb, *rc), (2, 41, 6));
+    assert_eq!(counter.load(Ordering::Relaxed), 2);
+}
+
+#[test_case(5, vec![]; "unknown selector")]
+#[test_case(FAILING, b"ten".to_vec(); "host function fails")]
+#[test_case(FAILING, vec![0xff]; "invalid utf-8 argument")]
+fn ecal_registry__errors_are_mapped_to_ecal_error(selector: Word, script_data: Vec<u8>) {
+    let script = vec![
+        op::movi(0x20, selector as u32),
+        op::gtf_args(0x10, 0x00, GTFArgs::ScriptData),
+        op::movi(0x11, script_data.len() as u32),
+        op::ecal(0x20, 0x10, 0x11, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts =
+        run_registry_script(test_registry(Default::default()), script, script_data);
+
+    assert_panics(&receipts, PanicReason::EcalError);
+}
+
+#[test]
+fn ecal_registry__propagates_vm_errors() {
+    let script = vec![
+        op::movi(0x20, BYTES_SUM as u32),
+        op::not(0x10, RegId::ZERO),
+        op::ecal(0x20, 0x10, RegId::ONE, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = run_registry_script(test_registry(Default::default()), script, vec![]);
+
+    assert_panics(&receipts, PanicReason::MemoryOverflow);
+}
+
+#[test]
+fn ecal_registry__charges_registered_gas() {
+    let registry = EcalRegistry::new().with_function(1, 2_000_000, |_, ()| Ok(0));
+    let script = vec![
+        op::movi(0x20, 1),
+        op::ecal(0x20, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+
+    let receipts = run_registry_script(registry, script, vec![]);
+
+    assert_panics(&receipts, PanicReason::OutOfGas);
+}
@@ -0,0 +1,186 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::{
+        Inputs,
+        MaxFeeLimit,
+        Outputs,
+        Witnesses,
+    },
+    ConsensusParameters,
+    Finalizable,
+    FundingError,
+    ResourceOwner,
+    SpendableResource,
+};
+use fuel_types::Nonce;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::prelude::*;
+
+use super::test_helpers::script_builder;
+
+const GAS_PRICE: Word = 1;
+
+fn coin(
+    rng: &mut StdRng,
+    secret: SecretKey,
+    amount: Word,
+    asset_id: AssetId,
+) -> SpendableResource {
+    SpendableResource::Coin {
+        utxo_id: rng.gen(),
+        amount,
+        asset_id,
+        tx_pointer: rng.gen(),
+        owner: ResourceOwner::Signer(secret),
+    }
+}
+
+fn change(tx: &Script, asset_id: &AssetId) -> Option<Address> {
+    tx.outputs().iter().find_map(|output| match output {
+        Output::Change {
+            to, asset_id: id, ..
+        } if id == asset_id => Some(*to),
+        _ => None,
+    })
+}
+
+#[test]
+fn fund__selects_largest_resources_and_adds_change() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let secret = SecretKey::random(rng);
+    let (recipient, change_to): (Address, Address) = (rng.gen(), rng.gen());
+    let asset_id: AssetId = rng.gen();
+    let resources = vec![
+        coin(rng, secret, 100, AssetId::BASE),
+        coin(rng, secret, 1_000_000, AssetId::BASE),
+        coin(rng, secret, 30, asset_id),
+        coin(rng, secret, 50, asset_id),
+        coin(rng, secret, 70, asset_id),
+    ];
+
+    let tx = script_builder()
+        .add_output(Output::coin(recipient, 1_000, AssetId::BASE))
+        .add_output(Output::coin(recipient, 100, asset_id))
+        .fund(resources, change_to, GAS_PRICE)
+        .expect("The resources cover the outputs")
+        .finalize();
+
+    let amounts: Vec<_> = tx.inputs().iter().filter_map(Input::amount).collect();
+    assert_eq!(amounts, vec![70, 50, 1_000_000]);
+    assert_eq!(tx.witnesses().len(), 1, "The inputs share the signer");
+    assert_eq!(change(&tx, &AssetId::BASE), Some(change_to));
+    assert_eq!(change(&tx, &asset_id), Some(change_to));
+
+    let ready = tx
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("The funded transaction is valid")
+        .into_ready(GAS_PRICE, &GasCosts::default(), &Default::default())
+        .expect("The max fee covers the fee");
+    let mut vm = Interpreter::<_, _, Script>::with_memory_storage();
+    vm.set_gas_price(GAS_PRICE);
+    let state = vm.transact(ready).expect("Failed to transact");
+    assert!(!state.should_revert());
+}
+
+#[test]
+fn fund__covers_max_fee_with_base_asset() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let secret = SecretKey::random(rng);
+    let gas_price = 1_000_000_000;
+    let resources: Vec<_> = (0..100)
+        .map(|_| coin(rng, secret, 1_000, AssetId::BASE))
+        .collect();
+
+    let tx = script_builder()
+        .fund(resources, rng.gen(), gas_price)
+        .expect("The resources cover the fee")
+        .finalize();
+
+    let params = ConsensusParameters::standard();
+    let max_fee = tx.max_fee(params.gas_costs(), params.fee_params(), gas_price);
+    let funded: Word = tx.inputs().iter().filter_map(Input::amount).sum();
+    assert!(tx.inputs().len() > 1);
+    assert_eq!(tx.max_fee_limit() as u128, max_fee);
+    assert!(funded as u128 >= max_fee);
+    assert!(
+        (funded as u128).saturating_sub(1_000) < max_fee,
+        "Only the required coins are selected"
+    );
+}
+
+#[test]
+fn fund__fails_when_resources_are_insufficient() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let secret = SecretKey::random(rng);
+    let asset_id: AssetId = rng.gen();
+    let resources = vec![
+        coin(rng, secret, 1_000_000, AssetId::BASE),
+        coin(rng, secret, 30, asset_id),
+    ];
+
+    let result = script_builder()
+        .add_output(Output::coin(rng.gen(), 100, asset_id))
+        .fund(resources, rng.gen(), GAS_PRICE)
+        .map(|_| ());
+
+    assert_eq!(
+        result,
+        Err(FundingError::InsufficientFunds {
+            asset_id,
+            required: 100,
+            available: 30,
+        })
+    );
+}
+
+#[test]
+fn fund__spends_predicate_messages() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let predicate: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let owner = ResourceOwner::Predicate {
+        predicate: predicate.clone(),
+        predicate_data: vec![],
+        predicate_gas_used: 1_000,
+    };
+    let (sender, nonce): (Address, Nonce) = (rng.gen(), rng.gen());
+    let resources = vec![SpendableResource::Message {
+        sender,
+        nonce,
+        amount: 1_000_000,
+        owner: owner.clone(),
+    }];
+
+    let tx = script_builder()
+        .fund(resources, rng.gen(), GAS_PRICE)
+        .expect("The message covers the fee")
+        .finalize();
+
+    let expected = Input::message_coin_predicate(
+        sender,
+        owner.address(),
+        1_000_000,
+        nonce,
+        1_000,
+        predicate,
+        vec![],
+    );
+    assert_eq!(tx.inputs(), &[expected]);
+    assert!(tx.witnesses().is_empty());
+    tx.into_checked_basic(Default::default(), &ConsensusParameters::standard())
+        .expect("The funded transaction is valid");
+}
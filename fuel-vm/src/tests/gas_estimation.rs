@@ -0,0 +1,197 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::InterpreterParams,
+    prelude::*,
+    transactor::ScriptGasEstimation,
+};
+
+use super::test_helpers::client;
+
+const GAS_LIMIT: Word = 1_000_000;
+
+fn script_tx(
+    test_context: &mut TestBuilder,
+    script: Vec<Instruction>,
+    script_gas_limit: Word,
+) -> Checked<Script> {
+    test_context
+        .start_script(script, vec![])
+        .script_gas_limit(script_gas_limit)
+        .fee_input()
+        .build()
+}
+
+fn script_result(receipts: &[Receipt]) -> ScriptExecutionResult {
+    *receipts
+        .iter()
+        .find_map(Receipt::result)
+        .expect("Missing script result")
+}
+
+#[test]
+fn estimate_script_gas__limit_is_equal_to_gas_used() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let script = vec![op::noop(); 10]
+        .into_iter()
+        .chain([op::ret(RegId::ONE)])
+        .collect::<Vec<_>>();
+    let tx = script_tx(&mut test_context, script.clone(), GAS_LIMIT);
+
+    let ScriptGasEstimation {
+        script_gas_limit,
+        gas_used,
+        fee,
+        receipts,
+    } = client(&test_context)
+        .estimate_script_gas(tx, &ConsensusParameters::standard())
+        .expect("Estimation should succeed");
+
+    assert_eq!(script_gas_limit, gas_used);
+    assert_eq!(script_result(&receipts), ScriptExecutionResult::Success);
+
+    let tx = script_tx(&mut test_context, script.clone(), script_gas_limit);
+    let expected_fee = TransactionFee::checked_from_tx(
+        test_context.get_gas_costs(),
+        test_context.get_fee_params(),
+        tx.transaction(),
+        0,
+    )
+    .unwrap();
+    assert_eq!(fee, expected_fee);
+
+    let tx = script_tx(&mut test_context, script, script_gas_limit - 1);
+    let receipts = client(&test_context).transact(tx).to_vec();
+    assert_eq!(script_result(&receipts), ScriptExecutionResult::Panic);
+}
+
+#[test]
+fn estimate_script_gas__searches_limit_when_outcome_depends_on_gas() {
+    let threshold = 10_000;
+    let mut test_context = TestBuilder::new(2322u64);
+    // Reverts if there is less than `threshold` gas left
+    let script = vec![
+        op::movi(0x10, threshold),
+        op::lt(0x11, RegId::CGAS, 0x10),
+        op::jnzf(0x11, RegId::ZERO, 1),
+        op::ret(RegId::ONE),
+        op::rvrt(RegId::ONE),
+    ];
+    let tx = script_tx(&mut test_context, script.clone(), GAS_LIMIT);
+
+    let estimation = client(&test_context)
+        .estimate_script_gas(tx, &ConsensusParameters::standard())
+        .expect("Estimation should succeed");
+
+    assert!(estimation.script_gas_limit > estimation.gas_used);
+    assert!(estimation.script_gas_limit > threshold as Word);
+    assert_eq!(
+        script_result(&estimation.receipts),
+        ScriptExecutionResult::Success
+    );
+
+    let tx = script_tx(&mut test_context, script, estimation.script_gas_limit - 1);
+    let receipts = client(&test_context).transact(tx).to_vec();
+    assert_eq!(script_result(&receipts), ScriptExecutionResult::Revert);
+}
+
+#[test]
+fn estimate_script_gas__does_not_modify_storage() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![op::sww(RegId::ZERO, 0x10, RegId::ONE), op::ret(RegId::ONE)],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(GAS_LIMIT)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut client = client(&test_context);
+    let estimation = client
+        .estimate_script_gas(tx, &ConsensusParameters::standard())
+        .expect("Estimation should succeed");
+
+    assert_eq!(
+        script_result(&estimation.receipts),
+        ScriptExecutionResult::Success
+    );
+    assert_eq!(client.as_ref().all_contract_state().count(), 0);
+}
+
+#[test]
+fn estimate_script_gas__does_not_modify_memory_of_last_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    );
+    let script = vec![
+        op::movi(0x10, 0xab),
+        op::cfei(8),
+        op::sw(RegId::SSP, 0x10, 0),
+        op::ret(RegId::ONE),
+    ];
+    transactor.transact(script_tx(&mut test_context, script, GAS_LIMIT));
+    let memory = transactor.interpreter().memory().clone();
+
+    let script = vec![
+        op::cfei(8),
+        op::sw(RegId::SSP, RegId::ONE, 0),
+        op::ret(RegId::ONE),
+    ];
+    let tx = script_tx(&mut test_context, script, GAS_LIMIT);
+    transactor
+        .estimate_script_gas(tx, &ConsensusParameters::standard())
+        .expect("Estimation should succeed");
+
+    assert_eq!(transactor.interpreter().memory(), &memory);
+}
+
+#[test]
+fn estimate_script_gas__fails_when_script_runs_out_of_gas() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let script = vec![op::noop(); 10]
+        .into_iter()
+        .chain([op::ret(RegId::ONE)])
+        .collect::<Vec<_>>();
+    let tx = script_tx(&mut test_context, script, 5);
+
+    let result =
+        client(&test_context).estimate_script_gas(tx, &ConsensusParameters::standard());
+
+    assert!(matches!(
+        result,
+        Err(InterpreterError::Panic(PanicReason::OutOfGas))
+    ));
+}
@@ -0,0 +1,141 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    checked_transaction::Checked,
+    interpreter::{
+        InstructionCache,
+        InterpreterParams,
+    },
+    prelude::*,
+};
+
+type Vm = Interpreter<MemoryInstance, MemoryStorage, Script>;
+
+fn interpreter(test_context: &TestBuilder) -> Vm {
+    Interpreter::with_storage(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+}
+
+fn transact(vm: &mut Vm, tx: Checked<Script>) -> Vec<Receipt> {
+    vm.transact(tx.test_into_ready())
+        .expect("Transaction should be executed")
+        .receipts()
+        .to_vec()
+}
+
+#[test]
+fn instruction_cache__script_loop_matches_uncached_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let script = vec![
+        op::movi(0x10, 100),
+        op::subi(0x10, 0x10, 1),
+        op::jnzb(0x10, RegId::ZERO, 0),
+        op::log(0x10, RegId::ONE, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let tx = test_context
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let expected = transact(&mut interpreter(&test_context), tx.clone());
+    let mut vm =
+        interpreter(&test_context).with_instruction_cache(InstructionCache::new());
+    let receipts = transact(&mut vm, tx);
+
+    assert_eq!(receipts, expected);
+    assert!(matches!(receipts[0], Receipt::Log { ra: 0, rb: 1, .. }));
+}
+
+#[test]
+fn instruction_cache__keeps_called_contracts_across_transactions() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, 10),
+                op::subi(0x10, 0x10, 1),
+                op::jnzb(0x10, RegId::ZERO, 0),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let expected = transact(&mut interpreter(&test_context), tx.clone());
+
+    let mut vm =
+        interpreter(&test_context).with_instruction_cache(InstructionCache::new());
+    assert_eq!(transact(&mut vm, tx.clone()), expected);
+    let cache = vm.take_instruction_cache().expect("The cache is enabled");
+    assert_eq!(cache.len(), 1);
+    assert!(cache.contains(&contract_id));
+
+    let mut vm = interpreter(&test_context).with_instruction_cache(cache);
+    assert_eq!(transact(&mut vm, tx), expected);
+}
+
+#[test]
+fn instruction_cache__is_bypassed_after_memory_is_modified() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![op::movi(0x10, 1), op::movi(0x11, 2), op::ret(RegId::ONE)],
+            vec![],
+        )
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let mut vm =
+        interpreter(&test_context).with_instruction_cache(InstructionCache::new());
+    vm.init_script(tx.test_into_ready())
+        .expect("Initialization should succeed");
+    vm.execute().expect("`movi` should succeed");
+    assert_eq!(vm.registers()[0x10], 1);
+
+    // Replace the next instruction after it was cached
+    let pc = vm.registers()[RegId::PC];
+    vm.memory_mut()
+        .write_noownerchecks(pc, Instruction::SIZE)
+        .expect("The code is in memory")
+        .copy_from_slice(&op::movi(0x11, 3).to_bytes());
+    vm.execute().expect("`movi` should succeed");
+
+    assert_eq!(vm.registers()[0x11], 3);
+}
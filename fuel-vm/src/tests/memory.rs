@@ -12,7 +12,10 @@ use fuel_asm::{
     op,
     RegId,
 };
-use fuel_tx::Receipt;
+use fuel_tx::{
+    PanicContext,
+    Receipt,
+};
 use fuel_vm::{
     consts::VM_MAX_RAM,
     interpreter::InterpreterParams,
@@ -503,3 +506,48 @@ fn test_heap_allocation_zeroes_memory() {
         panic!("Expected return receipt");
     }
 }
+
+#[test]
+fn test_memory_ownership_panic_sets_memory_range_context() {
+    let receipts = run_script(vec![
+        op::movi(0x10, 8),
+        op::mcl(RegId::ZERO, 0x10),
+        op::ret(RegId::ONE),
+    ]);
+
+    if let Some(Receipt::Panic {
+        reason, context, ..
+    }) = receipts.first()
+    {
+        assert_eq!(reason.reason(), &PanicReason::MemoryOwnership);
+        assert_eq!(context, &PanicContext::MemoryRange { start: 0, len: 8 });
+    } else {
+        panic!("Expected panic receipt");
+    }
+}
+
+#[test]
+fn test_memory_overflow_panic_sets_memory_range_context() {
+    let receipts = run_script(vec![
+        op::not(0x10, RegId::ZERO),
+        op::movi(0x11, 8),
+        op::mcp(RegId::ZERO, 0x10, 0x11),
+        op::ret(RegId::ONE),
+    ]);
+
+    if let Some(Receipt::Panic {
+        reason, context, ..
+    }) = receipts.first()
+    {
+        assert_eq!(reason.reason(), &PanicReason::MemoryOverflow);
+        assert_eq!(
+            context,
+            &PanicContext::MemoryRange {
+                start: Word::MAX,
+                len: 8
+            }
+        );
+    } else {
+        panic!("Expected panic receipt");
+    }
+}
@@ -0,0 +1,308 @@
+#![cfg(feature = "std")]
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    consts::VM_MAX_RAM,
+    interpreter::{
+        EcalRegistry,
+        InterpreterParams,
+        MemoryDiagnostic,
+        MemoryDiagnosticKind,
+    },
+    prelude::*,
+};
+
+fn run_script(script: Vec<Instruction>, sanitizer: bool) -> Vec<MemoryDiagnostic> {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    );
+    if sanitizer {
+        transactor = transactor.with_memory_sanitizer();
+    }
+    transactor.transact(tx);
+
+    let receipts = transactor.receipts().expect("Expected receipts");
+    assert!(
+        matches!(
+            receipts.last(),
+            Some(Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                ..
+            })
+        ),
+        "The sanitizer must not change the execution: {receipts:?}"
+    );
+    transactor
+        .memory_diagnostics()
+        .expect("The transaction was executed")
+}
+
+fn kinds(diagnostics: &[MemoryDiagnostic]) -> Vec<MemoryDiagnosticKind> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.kind)
+        .collect()
+}
+
+#[test]
+fn memory_sanitizer__reports_uninitialized_heap_read() {
+    let diagnostics = run_script(
+        vec![
+            op::movi(0x10, 16),
+            op::aloc(0x10),
+            op::sw(RegId::HP, RegId::ONE, 0),
+            // Initialized
+            op::lw(0x11, RegId::HP, 0),
+            // Never written
+            op::lw(0x11, RegId::HP, 1),
+            op::ret(RegId::ONE),
+        ],
+        true,
+    );
+
+    assert_eq!(
+        kinds(&diagnostics),
+        vec![MemoryDiagnosticKind::UninitializedRead]
+    );
+    let diagnostic = &diagnostics[0];
+    assert_eq!(
+        diagnostic.range,
+        VM_MAX_RAM - 8..VM_MAX_RAM,
+        "The second word of the allocation"
+    );
+    let location = diagnostic.location.expect("Read by an instruction");
+    assert_eq!(location.context, None);
+    assert_eq!(location.offset, 4 * Instruction::SIZE as Word);
+}
+
+#[test]
+fn memory_sanitizer__reports_read_past_allocation() {
+    let diagnostics = run_script(
+        vec![
+            op::cfei(16),
+            op::movi(0x10, 8),
+            op::aloc(0x10),
+            op::sw(RegId::HP, RegId::ONE, 0),
+            op::aloc(0x10),
+            op::sw(RegId::HP, RegId::ONE, 0),
+            // Reads both allocations at once
+            op::movi(0x11, 16),
+            op::mcp(RegId::SSP, RegId::HP, 0x11),
+            op::ret(RegId::ONE),
+        ],
+        true,
+    );
+
+    assert_eq!(
+        kinds(&diagnostics),
+        vec![MemoryDiagnosticKind::ReadPastAllocation]
+    );
+    assert_eq!(diagnostics[0].range, VM_MAX_RAM - 16..VM_MAX_RAM);
+}
+
+#[test]
+fn memory_sanitizer__reports_read_of_freed_stack() {
+    let diagnostics = run_script(
+        vec![
+            op::cfei(16),
+            op::sw(RegId::SSP, RegId::ONE, 0),
+            op::lw(0x10, RegId::SSP, 0),
+            op::cfsi(16),
+            // The stack was freed, but it's still accessible
+            op::lw(0x10, RegId::SSP, 0),
+            op::ret(RegId::ONE),
+        ],
+        true,
+    );
+
+    assert_eq!(
+        kinds(&diagnostics),
+        vec![MemoryDiagnosticKind::ReadPastAllocation]
+    );
+}
+
+#[test]
+fn memory_sanitizer__deduplicates_diagnostics() {
+    let diagnostics = run_script(
+        vec![
+            op::movi(0x10, 8),
+            op::aloc(0x10),
+            op::movi(0x11, 3),
+            // The same read in a loop
+            op::lw(0x12, RegId::HP, 0),
+            op::subi(0x11, 0x11, 1),
+            op::jnzb(0x11, RegId::ZERO, 1),
+            op::ret(RegId::ONE),
+        ],
+        true,
+    );
+
+    assert_eq!(
+        kinds(&diagnostics),
+        vec![MemoryDiagnosticKind::UninitializedRead]
+    );
+}
+
+#[test]
+fn memory_sanitizer__no_diagnostics_when_disabled() {
+    let diagnostics = run_script(
+        vec![
+            op::movi(0x10, 16),
+            op::aloc(0x10),
+            op::lw(0x11, RegId::HP, 1),
+            op::ret(RegId::ONE),
+        ],
+        false,
+    );
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn memory_sanitizer__no_diagnostics_for_contract_call() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x10, 32),
+                op::aloc(0x10),
+                op::mcp(RegId::HP, RegId::FP, 0x10),
+                op::lw(0x11, RegId::HP, 0),
+                op::cfei(8),
+                op::sw(RegId::SSP, RegId::ONE, 0),
+                op::lw(0x11, RegId::SSP, 0),
+                op::ret(0x11),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+    .with_memory_sanitizer();
+    transactor.transact(tx);
+
+    assert_eq!(transactor.memory_diagnostics(), Some(vec![]));
+}
+
+const WRITE_WORD: Word = 1;
+
+#[test]
+fn memory_sanitizer__reports_caller_stack_write() {
+    let registry =
+        EcalRegistry::new().with_function(WRITE_WORD, 1, |vm, (addr,): (Word,)| {
+            vm.memory_mut()
+                .write_noownerchecks(addr, 8usize)?
+                .copy_from_slice(&Word::MAX.to_be_bytes());
+            Ok(0)
+        });
+
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                op::movi(0x20, WRITE_WORD as u32),
+                // `$r0x12` points to the stack of the script
+                op::ecal(0x20, 0x12, RegId::ZERO, RegId::ZERO),
+                // The own stack of the contract is fine
+                op::cfei(8),
+                op::movi(0x20, WRITE_WORD as u32),
+                op::ecal(0x20, RegId::SSP, RegId::ZERO, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::move_(0x12, RegId::SSP),
+            op::cfei(8),
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+        .test_into_ready();
+
+    let mut vm = Interpreter::<_, _, Script, EcalRegistry>::with_storage_and_ecal(
+        MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        InterpreterParams::new(0, ConsensusParameters::standard()),
+        registry,
+    )
+    .with_memory_sanitizer();
+    let receipts = vm
+        .transact(tx)
+        .expect("Failed to transact")
+        .receipts()
+        .to_vec();
+    assert!(matches!(
+        receipts.last(),
+        Some(Receipt::ScriptResult {
+            result: ScriptExecutionResult::Success,
+            ..
+        })
+    ));
+
+    let diagnostics = vm.memory_diagnostics();
+    assert_eq!(
+        kinds(&diagnostics),
+        vec![MemoryDiagnosticKind::CallerStackWrite]
+    );
+    let location = diagnostics[0].location.expect("Written by the ECAL");
+    assert_eq!(location.context, Some(contract_id));
+    assert_eq!(location.offset, Instruction::SIZE as Word);
+}
@@ -0,0 +1,217 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+};
+use fuel_types::{
+    MessageId,
+    Nonce,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    block_executor::{
+        BlockExecutor,
+        BlockHeader,
+    },
+    crypto::ephemeral_merkle_root,
+    message_outbox::{
+        MessageOutbox,
+        MessageOutboxError,
+    },
+    prelude::*,
+};
+
+#[test]
+fn message_outbox__proves_every_message_sent_by_the_block() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![
+                op::movi(0x10, 1),
+                op::smo(RegId::ZERO, RegId::ZERO, 0x10, RegId::ZERO),
+                op::movi(0x10, 2),
+                op::smo(RegId::ZERO, RegId::ZERO, 0x10, RegId::ZERO),
+                op::movi(0x10, 3),
+                op::smo(RegId::ZERO, RegId::ZERO, 0x10, RegId::ZERO),
+                op::ret(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(100_000)
+        .fee_input()
+        .build();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let header = BlockHeader::new(Default::default(), 0, ContractId::zeroed(), 0);
+    let block = executor
+        .execute(&header, [tx])
+        .expect("Failed to execute the block");
+    let receipts: Vec<_> = block.included().flat_map(|tx| tx.receipts.iter()).collect();
+
+    let outbox = MessageOutbox::from_receipts(receipts.iter().copied())
+        .expect("The receipts contain the data");
+    assert_eq!(outbox.len(), 3);
+    assert_eq!(outbox.root(), block.message_outbox_root);
+    assert_eq!(block.message_outbox().message_ids(), outbox.message_ids());
+
+    for receipt in receipts {
+        let Receipt::MessageOut {
+            sender,
+            recipient,
+            amount,
+            nonce,
+            data: Some(data),
+            ..
+        } = receipt
+        else {
+            continue;
+        };
+        let message_id = receipt.message_id().expect("Message receipt");
+        let proof = outbox.prove(&message_id).expect("The message is sent");
+
+        assert!(proof.verify(&block.message_outbox_root));
+        assert!(proof.verify_message(
+            &block.message_outbox_root,
+            sender,
+            recipient,
+            nonce,
+            *amount,
+            data
+        ));
+    }
+}
+
+#[test]
+fn message_outbox__skips_messages_of_reverted_scripts() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(
+            vec![
+                op::movi(0x10, 1),
+                op::smo(RegId::ZERO, RegId::ZERO, 0x10, RegId::ZERO),
+                op::rvrt(RegId::ONE),
+            ],
+            vec![],
+        )
+        .script_gas_limit(100_000)
+        .fee_input()
+        .build();
+
+    let mut executor = BlockExecutor::new(
+        test_context.get_storage().clone(),
+        ConsensusParameters::standard(),
+    );
+    let header = BlockHeader::new(Default::default(), 0, ContractId::zeroed(), 0);
+    let block = executor
+        .execute(&header, [tx])
+        .expect("Failed to execute the block");
+
+    assert!(block
+        .included()
+        .flat_map(|tx| tx.receipts.iter())
+        .any(|receipt| receipt.message_id().is_some()));
+    assert!(block.message_outbox().is_empty());
+    assert_eq!(block.message_outbox_root, MessageOutbox::new().root());
+}
+
+#[test]
+fn message_proof__rejects_tampered_proofs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let (sender, recipient, amount): (Address, Address, Word) =
+        (rng.gen(), rng.gen(), rng.gen());
+    let data = vec![1, 2, 3];
+    let nonces: Vec<Nonce> = (0..5).map(|_| rng.gen()).collect();
+
+    let mut outbox = MessageOutbox::new();
+    for nonce in &nonces {
+        outbox.push(Input::compute_message_id(
+            &sender, &recipient, nonce, amount, &data,
+        ));
+    }
+    let root = outbox.root();
+    let proof = outbox.prove_index(2).expect("The message is in the outbox");
+    assert!(proof.verify_message(&root, &sender, &recipient, &nonces[2], amount, &data));
+
+    assert!(!proof.verify(&rng.gen()), "Other root");
+    assert!(
+        !proof.verify_message(&root, &sender, &recipient, &nonces[1], amount, &data),
+        "Other message"
+    );
+    assert!(
+        !proof.verify_message(
+            &root,
+            &sender,
+            &recipient,
+            &nonces[2],
+            amount.wrapping_add(1),
+            &data
+        ),
+        "Other amount"
+    );
+
+    let mut wrong_index = proof.clone();
+    wrong_index.proof_index = 3;
+    assert!(!wrong_index.verify(&root));
+
+    let mut wrong_proof_set = proof.clone();
+    wrong_proof_set.proof_set[0] = rng.gen();
+    assert!(!wrong_proof_set.verify(&root));
+
+    let mut wrong_leaves_count = proof;
+    wrong_leaves_count.leaves_count = 4;
+    assert!(!wrong_leaves_count.verify(&root));
+}
+
+#[test]
+fn message_outbox__from_receipts_requires_message_data() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let nonce: Nonce = rng.gen();
+    let receipts = [
+        Receipt::ret(rng.gen(), 1, 0, 0),
+        Receipt::message_out_with_len(rng.gen(), rng.gen(), 0, nonce, 3, rng.gen(), None),
+    ];
+
+    let result = MessageOutbox::from_receipts(receipts.iter());
+
+    assert_eq!(result.err(), Some(MessageOutboxError::MissingData(nonce)));
+}
+
+#[test]
+fn message_outbox__handles_small_outboxes() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let mut outbox = MessageOutbox::new();
+    assert!(outbox.is_empty());
+    assert_eq!(
+        outbox.root(),
+        ephemeral_merkle_root(core::iter::empty::<MessageId>())
+    );
+    assert_eq!(outbox.prove(&rng.gen()), None);
+
+    let message_id: MessageId = rng.gen();
+    outbox.push(message_id);
+    let proof = outbox
+        .prove(&message_id)
+        .expect("The message is in the outbox");
+
+    assert!(proof.proof_set.is_empty());
+    assert!(proof.verify(&outbox.root()));
+    assert_eq!(outbox.prove_index(1), None);
+}
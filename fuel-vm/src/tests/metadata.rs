@@ -368,6 +368,8 @@ fn get_transaction_fields() {
     let tip = 4321;
     let gas_limit = 10_000_000;
     let maturity = 50.into();
+    let expiration: BlockHeight = 200.into();
+    let owner_index = 0;
     let height = 122.into();
     let input = 10_000_000;
 
@@ -602,6 +604,18 @@ fn get_transaction_fields() {
         op::eq(0x10, 0x10, 0x11),
         op::and(0x20, 0x20, 0x10),
 
+        op::movi(0x19, 0x00),
+        op::movi(0x11, *expiration as Immediate18),
+        op::gtf_args(0x10, 0x19, GTFArgs::PolicyExpiration),
+        op::eq(0x10, 0x10, 0x11),
+        op::and(0x20, 0x20, 0x10),
+
+        op::movi(0x19, 0x00),
+        op::movi(0x11, owner_index as Immediate18),
+        op::gtf_args(0x10, 0x19, GTFArgs::PolicyOwner),
+        op::eq(0x10, 0x10, 0x11),
+        op::and(0x20, 0x20, 0x10),
+
         op::movi(0x19, 0x00),
         op::movi(0x11, max_fee_limit as Immediate18),
         op::gtf_args(0x10, 0x19, GTFArgs::PolicyMaxFee),
@@ -943,6 +957,8 @@ fn get_transaction_fields() {
     let tx = builder
         .tip(tip)
         .maturity(maturity)
+        .expiration(expiration)
+        .owner_index(owner_index)
         .script_gas_limit(gas_limit)
         .witness_limit(witness_limit)
         .max_fee_limit(max_fee_limit)
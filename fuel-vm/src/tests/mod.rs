@@ -9,29 +9,46 @@ mod test_helpers;
 
 mod alu;
 mod backtrace;
+mod block_executor;
 mod blockchain;
 mod cgas;
 mod code_coverage;
 mod coins;
 mod contract;
 mod crypto;
+#[cfg(feature = "debug-ecal")]
+mod debug_ecal;
+mod dry_run;
 mod encoding;
+mod execution_hook;
+mod execution_statistics;
 mod external;
 mod flow;
+mod funding;
+mod gas_estimation;
 mod gas_factor;
+mod instruction_cache;
 mod jump_absolute;
 mod jump_relative;
 mod limits;
 mod log;
 mod memory;
+mod memory_sanitizer;
+mod message_outbox;
 mod metadata;
 mod outputs;
+mod partially_signed;
 mod predicate;
 mod profile_gas;
 mod receipts;
 mod serde_profile;
+mod signer;
+#[cfg(feature = "source-map")]
+mod source_map;
 mod spec;
 mod upgrade;
 mod upload;
+mod utxo;
 mod validation;
+mod vm_params;
 mod wideint;
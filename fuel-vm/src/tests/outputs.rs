@@ -167,6 +167,7 @@ fn correct_change_is_provided_for_coin_outputs_create() {
         *context.get_base_asset_id(),
         context.get_block_gas_limit(),
         *context.get_privileged_address(),
+        *context.get_vm_params(),
     );
     let create = create
         .into_checked_basic(context.get_block_height(), &consensus_params)
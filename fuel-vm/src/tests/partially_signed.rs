@@ -0,0 +1,340 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_crypto::{
+    signer::TestSigner,
+    Signer,
+};
+use fuel_tx::{
+    field::Inputs,
+    ConsensusParameters,
+    Finalizable,
+    InputRequirement,
+    PartiallySignedError,
+    PartiallySignedTransaction,
+    Party,
+    PredicateEstimation,
+    TransactionBuilder,
+};
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Serialize,
+    },
+    ChainId,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    checked_transaction::{
+        CheckPredicateParams,
+        EstimatePredicates,
+    },
+    prelude::*,
+};
+
+struct Swap {
+    alice: TestSigner,
+    bob: TestSigner,
+    transaction: Transaction,
+}
+
+/// The swap of the coins of Alice and Bob, with the base asset coin of the predicate.
+fn swap(rng: &mut StdRng) -> Swap {
+    let (alice, bob) = (TestSigner::new(1), TestSigner::new(2));
+    let (alice_asset, bob_asset): (AssetId, AssetId) = (rng.gen(), rng.gen());
+    let predicate: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let predicate_owner = Input::predicate_owner(&predicate);
+
+    let transaction = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    )
+    .script_gas_limit(10_000)
+    .add_signer_coin_input(
+        &alice.public_key(),
+        rng.gen(),
+        100,
+        alice_asset,
+        Default::default(),
+    )
+    .add_signer_coin_input(
+        &bob.public_key(),
+        rng.gen(),
+        200,
+        bob_asset,
+        Default::default(),
+    )
+    .add_input(Input::coin_predicate(
+        rng.gen(),
+        predicate_owner,
+        1_000,
+        AssetId::BASE,
+        Default::default(),
+        0,
+        predicate,
+        vec![],
+    ))
+    .add_output(Output::coin(
+        Input::owner(&bob.public_key()),
+        100,
+        alice_asset,
+    ))
+    .add_output(Output::coin(
+        Input::owner(&alice.public_key()),
+        200,
+        bob_asset,
+    ))
+    .add_output(Output::change(predicate_owner, 0, AssetId::BASE))
+    .finalize_without_signature()
+    .into();
+
+    Swap {
+        alice,
+        bob,
+        transaction,
+    }
+}
+
+fn sign(
+    partially_signed: &PartiallySignedTransaction,
+    signer: &TestSigner,
+) -> PartiallySignedTransaction {
+    let mut signed = partially_signed.clone();
+    let signature = signer.sign(&signed.message()).unwrap();
+    signed
+        .add_signature(&signer.public_key(), signature)
+        .expect("The signer owns the input");
+    signed
+}
+
+fn estimate(partially_signed: &PartiallySignedTransaction) -> Transaction {
+    let mut estimated = partially_signed.transaction().clone();
+    estimated
+        .estimate_predicates(
+            &CheckPredicateParams::from(&ConsensusParameters::standard()),
+            MemoryInstance::new(),
+        )
+        .expect("The predicate is valid");
+    estimated
+}
+
+#[test]
+fn partially_signed_transaction__collects_contributions_of_the_parties() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let Swap {
+        alice,
+        bob,
+        transaction,
+    } = swap(rng);
+    let params = ConsensusParameters::standard();
+
+    let mut coordinator =
+        PartiallySignedTransaction::new(transaction, params.chain_id()).unwrap();
+    coordinator
+        .add_party(Input::owner(&alice.public_key()), b"maker".to_vec())
+        .add_party(Input::owner(&bob.public_key()), b"taker".to_vec());
+    assert_eq!(
+        coordinator.inputs()[2],
+        InputRequirement::Predicate(PredicateEstimation::Pending)
+    );
+    assert_eq!(coordinator.missing_signatures().count(), 2);
+
+    let from_alice = sign(&coordinator, &alice);
+    let from_bob = sign(&coordinator, &bob);
+    coordinator
+        .merge(&from_alice)
+        .unwrap()
+        .merge(&from_bob)
+        .unwrap();
+
+    assert_eq!(coordinator.missing_signatures().count(), 0);
+    assert_eq!(
+        coordinator.pending_predicates().collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert_eq!(
+        coordinator.clone().finalize(Default::default(), &params),
+        Err(PartiallySignedError::UnestimatedPredicate(2))
+    );
+
+    let estimated = estimate(&coordinator);
+    coordinator.apply_predicate_estimation(&estimated).unwrap();
+    assert!(coordinator.is_complete());
+
+    let transaction = coordinator
+        .finalize(Default::default(), &params)
+        .expect("Every input is unlocked");
+    transaction
+        .into_checked(Default::default(), &params)
+        .expect("The swap is valid");
+}
+
+#[test]
+fn partially_signed_transaction__round_trips_through_bytes_and_json() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let Swap {
+        alice, transaction, ..
+    } = swap(rng);
+    let mut partially_signed =
+        PartiallySignedTransaction::new(transaction, ChainId::default()).unwrap();
+    partially_signed.add_party(Input::owner(&alice.public_key()), b"maker".to_vec());
+    let partially_signed = sign(&partially_signed, &alice);
+
+    let bytes = partially_signed.to_bytes();
+    let decoded = PartiallySignedTransaction::from_bytes(&bytes).expect("Valid encoding");
+    assert_eq!(decoded, partially_signed);
+
+    let json = serde_json::to_string(&partially_signed).expect("Serializable");
+    let decoded: PartiallySignedTransaction =
+        serde_json::from_str(&json).expect("Valid JSON");
+    assert_eq!(decoded, partially_signed);
+    assert_eq!(decoded.missing_signatures().count(), 1);
+}
+
+#[test]
+fn partially_signed_transaction__rejects_invalid_contributions() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let Swap {
+        alice,
+        bob,
+        transaction,
+    } = swap(rng);
+    let mut partially_signed =
+        PartiallySignedTransaction::new(transaction, ChainId::default()).unwrap();
+    let alice_owner = Input::owner(&alice.public_key());
+
+    let stranger = TestSigner::new(3);
+    let signature = stranger.sign(&partially_signed.message()).unwrap();
+    assert_eq!(
+        partially_signed
+            .add_signature(&stranger.public_key(), signature)
+            .map(|_| ()),
+        Err(PartiallySignedError::UnknownOwner(Input::owner(
+            &stranger.public_key()
+        )))
+    );
+
+    let signature = bob.sign(&partially_signed.message()).unwrap();
+    assert_eq!(
+        partially_signed
+            .add_signature(&alice.public_key(), signature)
+            .map(|_| ()),
+        Err(PartiallySignedError::InvalidSignature(alice_owner))
+    );
+
+    let other =
+        PartiallySignedTransaction::new(swap(rng).transaction, ChainId::default())
+            .unwrap();
+    assert!(matches!(
+        partially_signed.merge(&other),
+        Err(PartiallySignedError::TransactionMismatch { .. })
+    ));
+
+    let mut other = partially_signed.clone();
+    other.add_party(alice_owner, b"taker".to_vec());
+    partially_signed.add_party(alice_owner, b"maker".to_vec());
+    assert_eq!(
+        partially_signed.merge(&other).map(|_| ()),
+        Err(PartiallySignedError::PartyConflict(alice_owner))
+    );
+
+    assert_eq!(
+        partially_signed.finalize(Default::default(), &ConsensusParameters::standard()),
+        Err(PartiallySignedError::MissingSignature(0))
+    );
+}
+
+#[test]
+fn partially_signed_transaction__rejects_conflicting_predicate_estimations() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let coordinator =
+        PartiallySignedTransaction::new(swap(rng).transaction, ChainId::default())
+            .unwrap();
+
+    let estimated = estimate(&coordinator);
+    let mut overestimated = estimated.clone();
+    if let Transaction::Script(script) = &mut overestimated {
+        if let Some(Input::CoinPredicate(coin)) = script.inputs_mut().get_mut(2) {
+            coin.predicate_gas_used = coin.predicate_gas_used.saturating_add(1);
+        }
+    }
+
+    let mut from_alice = coordinator.clone();
+    from_alice.apply_predicate_estimation(&estimated).unwrap();
+    let mut from_bob = coordinator.clone();
+    from_bob.apply_predicate_estimation(&overestimated).unwrap();
+
+    let mut merged = coordinator.clone();
+    merged.merge(&from_alice).unwrap();
+    assert_eq!(
+        merged.merge(&from_bob).map(|_| ()),
+        Err(PartiallySignedError::PredicateConflict(2))
+    );
+    assert_eq!(merged.transaction(), from_alice.transaction());
+    // The same estimation isn't a conflict
+    merged.merge(&from_alice).unwrap();
+}
+
+/// The fields of the [`PartiallySignedTransaction`] without the checks.
+#[derive(fuel_types::canonical::Serialize)]
+struct UncheckedPartiallySignedTransaction {
+    chain_id: ChainId,
+    transaction: Transaction,
+    inputs: Vec<InputRequirement>,
+    parties: Vec<Party>,
+}
+
+#[test]
+fn partially_signed_transaction__rejects_requirements_not_matching_inputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let partially_signed =
+        PartiallySignedTransaction::new(swap(rng).transaction, ChainId::default())
+            .unwrap();
+    let mut truncated = partially_signed.inputs().to_vec();
+    truncated.pop();
+    let mut forged = partially_signed.inputs().to_vec();
+    forged[2] = InputRequirement::None;
+
+    for inputs in [truncated, forged] {
+        let unchecked = UncheckedPartiallySignedTransaction {
+            chain_id: partially_signed.chain_id(),
+            transaction: partially_signed.transaction().clone(),
+            inputs: inputs.clone(),
+            parties: vec![],
+        };
+        assert!(PartiallySignedTransaction::from_bytes(&unchecked.to_bytes()).is_err());
+
+        let mut json = serde_json::to_value(&partially_signed).expect("Serializable");
+        json["inputs"] = serde_json::to_value(&inputs).expect("Serializable");
+        let error = serde_json::from_value::<PartiallySignedTransaction>(json)
+            .expect_err("The inputs don't match");
+        assert_eq!(
+            error.to_string(),
+            PartiallySignedError::InputRequirementsMismatch.to_string()
+        );
+    }
+}
+
+#[test]
+fn partially_signed_transaction__rejects_mint() {
+    let mint = Transaction::Mint(Default::default());
+
+    assert_eq!(
+        PartiallySignedTransaction::new(mint, ChainId::default()),
+        Err(PartiallySignedError::UnsupportedTransaction)
+    );
+}
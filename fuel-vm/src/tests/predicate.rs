@@ -17,7 +17,10 @@ use tokio_rayon::AsyncRayonHandle;
 
 use crate::{
     error::PredicateVerificationFailed,
-    pool::DummyPool,
+    pool::{
+        DummyPool,
+        MemoryPool,
+    },
     prelude::*,
 };
 
@@ -337,6 +340,52 @@ async fn predicate_gas_metering() {
     assert_eq!(gas_used_by[1] * 3, gas_used_by[3]);
 }
 
+#[tokio::test]
+#[allow(non_snake_case)]
+async fn check_predicates_async__recycles_memory_from_pool() {
+    const PREDICATES: usize = 3;
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let predicate: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+    let owner = Input::predicate_owner(&predicate);
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder.max_fee_limit(2_000);
+    for _ in 0..PREDICATES {
+        builder.add_input(Input::coin_predicate(
+            rng.gen(),
+            owner,
+            10_000_000,
+            AssetId::default(),
+            rng.gen(),
+            0,
+            predicate.clone(),
+            vec![],
+        ));
+    }
+
+    let params = CheckPredicateParams::default();
+    let pool = MemoryPool::new(PREDICATES, usize::MAX);
+    let mut transaction = builder.finalize();
+    transaction
+        .estimate_predicates_async::<TokioWithRayon>(&params, &pool)
+        .await
+        .expect("Should estimate predicates");
+    let tx = transaction
+        .into_checked_basic(Default::default(), &ConsensusParameters::standard())
+        .expect("Should successfully create checked tranaction with predicate");
+    Interpreter::check_predicates_async::<TokioWithRayon>(&tx, &params, &pool)
+        .await
+        .expect("Predicates should be valid");
+
+    // Each run requests an instance per predicate, the second run reuses the memory
+    // returned by the first one.
+    let metrics = pool.metrics();
+    assert_eq!(metrics.hits + metrics.misses, 2 * PREDICATES as u64);
+    assert!(metrics.hits >= PREDICATES as u64);
+    assert_eq!(metrics.pooled as u64, metrics.misses);
+    assert_eq!(metrics.discarded, 0);
+}
+
 #[tokio::test]
 async fn gas_used_by_predicates_not_causes_out_of_gas_during_script() {
     let rng = &mut StdRng::seed_from_u64(2322u64);
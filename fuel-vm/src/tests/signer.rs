@@ -0,0 +1,136 @@
+#![allow(non_snake_case)]
+
+use alloc::vec;
+
+use fuel_crypto::{
+    signer::TestSigner,
+    Signer,
+};
+use fuel_tx::{
+    field::Witnesses,
+    ConsensusParameters,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::prelude::*;
+
+use super::test_helpers::script_builder;
+
+#[test]
+fn finalize_with_signer__signs_coin_and_message_inputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let signer = TestSigner::new(1);
+    let secret = SecretKey::random(rng);
+
+    let mut builder = script_builder();
+    builder
+        .add_signer_coin_input(
+            &signer.public_key(),
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            Default::default(),
+        )
+        .add_signer_message_input(
+            &signer.public_key(),
+            rng.gen(),
+            rng.gen(),
+            1_000,
+            vec![],
+        )
+        .add_unsigned_coin_input(
+            secret,
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            Default::default(),
+        );
+    let tx = builder
+        .finalize_with_signer(&signer)
+        .expect("The test signer is infallible");
+
+    assert_eq!(
+        tx.witnesses().len(),
+        2,
+        "The inputs of the signer share the witness"
+    );
+    tx.into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("The inputs are signed by their owners");
+}
+
+#[test]
+fn finalize_with_signer__leaves_inputs_of_other_signers_unsigned() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let (signer, other) = (TestSigner::new(1), TestSigner::new(2));
+
+    let tx = script_builder()
+        .add_signer_coin_input(
+            &signer.public_key(),
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            Default::default(),
+        )
+        .add_signer_coin_input(
+            &other.public_key(),
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            Default::default(),
+        )
+        .finalize_with_signer(&signer)
+        .expect("The test signer is infallible");
+
+    assert!(tx.witnesses()[1].as_vec().is_empty());
+    assert!(tx
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .is_err());
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[tokio::test]
+async fn finalize_with_async_signer__signs_through_the_separate_signer() {
+    use fuel_crypto::signer::{
+        serve,
+        StdioSigner,
+    };
+    use std::{
+        io::BufReader,
+        os::unix::net::UnixStream,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let key_keeper = TestSigner::new(1);
+    let (client, server) = UnixStream::pair().expect("Failed to create the socket pair");
+    let server = std::thread::spawn(move || {
+        let reader = BufReader::new(server.try_clone().unwrap());
+        serve(&key_keeper, reader, server)
+    });
+    let signer =
+        StdioSigner::connect(BufReader::new(client.try_clone().unwrap()), client)
+            .expect("Failed to connect to the signer");
+
+    let tx = script_builder()
+        .add_signer_coin_input(
+            &signer.public_key(),
+            rng.gen(),
+            1_000,
+            AssetId::BASE,
+            Default::default(),
+        )
+        .finalize_with_async_signer(&signer)
+        .await
+        .expect("The signer is available");
+
+    tx.into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("The input is signed by the owner");
+    drop(signer);
+    server
+        .join()
+        .unwrap()
+        .expect("The signer stops at the end of the input");
+}
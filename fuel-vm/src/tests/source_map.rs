@@ -0,0 +1,333 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    format,
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::Receipt;
+use fuel_types::canonical::Serialize;
+
+use crate::{
+    backtrace::Backtrace,
+    constraints::InstructionLocation,
+    prelude::*,
+    source_map::{
+        SourceLocation,
+        SourceMap,
+        SourceMapError,
+        SourceMaps,
+        StackFrame,
+        TraceCause,
+    },
+};
+
+fn location(line: u32, function: &str) -> SourceLocation {
+    SourceLocation {
+        file: "src/main.sw".to_string(),
+        line,
+        column: Some(5),
+        function: Some(function.to_string()),
+    }
+}
+
+/// The debug info of the program, with a location for every instruction.
+fn debug_info_json(id: Bytes32, function: &str, len: usize) -> String {
+    let locations = (0..len)
+        .map(|i| {
+            format!(
+                r#"{{ "offset": {}, "file": "src/main.sw", "line": {}, "column": 5, "function": "{function}" }}"#,
+                i * Instruction::SIZE,
+                i + 1,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(r#"{{ "id": "{id}", "locations": [{locations}] }}"#)
+}
+
+fn bytes(program: &[Instruction]) -> Vec<u8> {
+    program.iter().copied().collect()
+}
+
+struct Execution {
+    source_maps: SourceMaps,
+    receipts: Vec<Receipt>,
+    backtrace: Backtrace,
+    callee: ContractId,
+    caller: ContractId,
+}
+
+/// The script calls the `caller` contract, which calls the `callee` contract, which
+/// panics.
+fn execute_nested_call() -> Execution {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let callee_code = vec![
+        op::noop(),
+        op::noop(),
+        op::div(0x20, RegId::ONE, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let callee = test_context
+        .setup_contract(callee_code.clone(), None, None)
+        .contract_id;
+
+    // `$r0x11` points to the call of the `callee` set by the script
+    let caller_code = vec![
+        op::noop(),
+        op::call(0x11, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let caller = test_context
+        .setup_contract(caller_code.clone(), None, None)
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::addi(0x11, 0x10, Call::LEN as u16),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let script_data = [
+        Call::new(caller, 0, 0).to_bytes(),
+        Call::new(callee, 0, 0).to_bytes(),
+    ]
+    .concat();
+
+    let json = format!(
+        r#"{{ "programs": [{}, {}, {}] }}"#,
+        debug_info_json(SourceMaps::script_id(&bytes(&script)), "main", script.len()),
+        debug_info_json(
+            Contract::root_from_code(bytes(&caller_code)),
+            "call_callee",
+            caller_code.len()
+        ),
+        debug_info_json(
+            Contract::root_from_code(bytes(&callee_code)),
+            "divide",
+            callee_code.len()
+        ),
+    );
+    let mut source_maps =
+        SourceMaps::from_json(json.as_bytes()).expect("Valid debug info");
+    source_maps.set_script(&bytes(&script));
+    source_maps.bind_contract_code(caller, &bytes(&caller_code));
+    source_maps.bind_contract_code(callee, &bytes(&callee_code));
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .script_gas_limit(1_000_000)
+        .contract_input(caller)
+        .contract_input(callee)
+        .fee_input()
+        .contract_output(&caller)
+        .contract_output(&callee)
+        .build();
+    let (state, backtrace) = test_context
+        .execute_tx_with_backtrace(tx, 0)
+        .expect("Failed to execute the transaction");
+
+    Execution {
+        source_maps,
+        receipts: state.receipts().to_vec(),
+        backtrace: backtrace.expect("The callee panics"),
+        callee,
+        caller,
+    }
+}
+
+#[test]
+fn source_map__lookup_returns_closest_preceding_location() {
+    let mut source_map = SourceMap::new();
+    assert!(source_map.is_empty());
+    source_map.insert(0, location(1, "main"));
+    source_map.insert(8, location(2, "main"));
+
+    assert_eq!(source_map.lookup(0), Some(&location(1, "main")));
+    assert_eq!(source_map.lookup(4), Some(&location(1, "main")));
+    assert_eq!(source_map.lookup(8), Some(&location(2, "main")));
+    assert_eq!(source_map.lookup(1024), Some(&location(2, "main")));
+
+    let mut source_map = SourceMap::new();
+    source_map.insert(8, location(2, "main"));
+    assert_eq!(source_map.lookup(4), None);
+}
+
+#[test]
+fn source_maps__resolve_unknown_programs_to_none() {
+    let mut source_maps = SourceMaps::new();
+    let mut source_map = SourceMap::new();
+    source_map.insert(0, location(1, "main"));
+    source_maps.insert(Bytes32::zeroed(), source_map);
+
+    // The script isn't set
+    let script_location = InstructionLocation {
+        context: None,
+        offset: 0,
+    };
+    assert_eq!(source_maps.resolve(&script_location), None);
+
+    // The contract isn't bound
+    let contract_location = InstructionLocation {
+        context: Some(ContractId::zeroed()),
+        offset: 0,
+    };
+    assert_eq!(source_maps.resolve(&contract_location), None);
+
+    source_maps.bind_contract(ContractId::zeroed(), Bytes32::zeroed());
+    assert_eq!(
+        source_maps.resolve(&contract_location),
+        Some(&location(1, "main"))
+    );
+}
+
+#[test]
+fn source_maps__from_json_rejects_invalid_debug_info() {
+    let result = SourceMaps::from_json(br#"{ "programs": [{ "id": "0x00" }] }"#);
+
+    assert!(matches!(result, Err(SourceMapError::Json(_))));
+}
+
+#[test]
+fn source_maps__load_reports_missing_file() {
+    let result = SourceMaps::load("/nonexistent/debug_info.json");
+
+    assert!(matches!(result, Err(SourceMapError::Io(_))));
+}
+
+#[test]
+fn symbolize_backtrace__resolves_every_frame() {
+    let execution = execute_nested_call();
+
+    let trace = execution
+        .source_maps
+        .symbolize_backtrace(&execution.backtrace);
+
+    assert_eq!(trace.cause, TraceCause::Panic(None));
+    let frames: Vec<_> = trace
+        .frames
+        .iter()
+        .map(|frame| (frame.location, frame.source.clone()))
+        .collect();
+    assert_eq!(
+        frames,
+        vec![
+            (
+                InstructionLocation {
+                    context: Some(execution.callee),
+                    offset: 8,
+                },
+                Some(location(3, "divide")),
+            ),
+            (
+                InstructionLocation {
+                    context: Some(execution.caller),
+                    offset: 4,
+                },
+                Some(location(2, "call_callee")),
+            ),
+            (
+                InstructionLocation {
+                    context: None,
+                    offset: 8,
+                },
+                Some(location(3, "main")),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn symbolize_receipt__resolves_panic_location() {
+    let execution = execute_nested_call();
+    let panic = execution
+        .receipts
+        .iter()
+        .find(|receipt| matches!(receipt, Receipt::Panic { .. }))
+        .expect("Expected a panic");
+
+    let trace = execution
+        .source_maps
+        .symbolize_receipt(panic)
+        .expect("Panic is symbolized");
+
+    assert_eq!(
+        trace.cause,
+        TraceCause::Panic(Some(PanicReason::ArithmeticError))
+    );
+    assert_eq!(
+        trace.frames,
+        vec![StackFrame {
+            location: InstructionLocation {
+                context: Some(execution.callee),
+                offset: 8,
+            },
+            source: Some(location(3, "divide")),
+        }]
+    );
+    assert_eq!(
+        trace.to_string(),
+        format!(
+            "panic: ArithmeticError\n    0: divide at src/main.sw:3:5 (contract {} +0x8)",
+            execution.callee
+        )
+    );
+}
+
+#[test]
+fn symbolize_receipt__resolves_script_revert() {
+    let script = vec![op::noop(), op::rvrt(RegId::ONE)];
+    let mut source_maps = SourceMaps::new();
+    let mut source_map = SourceMap::new();
+    source_map.insert(4, location(7, "main"));
+    source_maps.insert(SourceMaps::script_id(&bytes(&script)), source_map);
+    source_maps.set_script(&bytes(&script));
+
+    let mut test_context = TestBuilder::new(2322u64);
+    let tx = test_context
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+    let receipts = test_context
+        .execute_tx(tx)
+        .expect("Failed to execute the transaction")
+        .receipts()
+        .to_vec();
+
+    let traces: Vec<_> = receipts
+        .iter()
+        .filter_map(|receipt| source_maps.symbolize_receipt(receipt))
+        .collect();
+    assert_eq!(traces.len(), 1, "Only the revert is symbolized");
+    assert_eq!(
+        traces[0].to_string(),
+        "revert: 0x1\n    0: main at src/main.sw:7:5 (script +0x4)"
+    );
+}
+
+#[test]
+fn stack_frame__displays_unknown_location() {
+    let frame = StackFrame {
+        location: InstructionLocation {
+            context: None,
+            offset: 16,
+        },
+        source: None,
+    };
+
+    assert_eq!(frame.to_string(), "<unknown> (script +0x10)");
+}
@@ -87,3 +87,25 @@ pub fn assert_panics(receipts: &[Receipt], reason: PanicReason) {
         unreachable!("No script receipt for a paniced tx");
     }
 }
+
+/// Creates a client on top of the storage of the `test_context`, e.g. with the
+/// contracts deployed by it
+pub fn client(
+    test_context: &crate::prelude::TestBuilder,
+) -> crate::prelude::MemoryClient<crate::prelude::MemoryInstance> {
+    crate::prelude::MemoryClient::new(
+        crate::prelude::MemoryInstance::new(),
+        test_context.get_storage().clone(),
+        crate::interpreter::InterpreterParams::new(0, ConsensusParameters::standard()),
+    )
+}
+
+/// Creates a builder of a script that returns right away
+pub fn script_builder() -> TransactionBuilder<Script> {
+    let mut builder = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    );
+    builder.script_gas_limit(10_000);
+    builder
+}
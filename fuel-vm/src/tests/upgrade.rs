@@ -1,5 +1,6 @@
 use crate::{
     checked_transaction::{
+        CheckError,
         Checked,
         IntoChecked,
     },
@@ -24,6 +25,7 @@ use fuel_tx::{
     Output,
     Transaction,
     Upgrade,
+    VmParameters,
 };
 use fuel_types::AssetId;
 
@@ -387,6 +389,48 @@ mod consensus_parameters {
         assert_eq!(asset_id, AssetId::BASE);
     }
 
+    #[test]
+    fn check_fails_when_vm_parameters_are_out_of_safe_ranges() {
+        let predicate = vec![op::ret(1)].into_iter().collect::<Vec<u8>>();
+        let owner = Input::predicate_owner(&predicate);
+        let inputs = vec![Input::coin_predicate(
+            Default::default(),
+            owner,
+            AMOUNT,
+            AssetId::BASE,
+            Default::default(),
+            Default::default(),
+            predicate,
+            vec![],
+        )];
+        let outputs = vec![Output::change(owner, 0, AssetId::BASE)];
+
+        // Given
+        let mut new_consensus_params = ConsensusParameters::standard();
+        new_consensus_params.set_vm_params(VmParameters::DEFAULT.with_max_receipts(0));
+        let upgrade = Transaction::upgrade_consensus_parameters(
+            &new_consensus_params,
+            Policies::new().with_max_fee(AMOUNT),
+            inputs,
+            outputs,
+            vec![],
+        )
+        .expect("failed to generate upgrade tx");
+        let mut consensus_params = ConsensusParameters::standard();
+        consensus_params.set_privileged_address(owner);
+
+        // When
+        let result = upgrade.into_checked_basic(0.into(), &consensus_params);
+
+        // Then
+        assert_eq!(
+            Some(CheckError::Validity(
+                ValidityError::TransactionUpgradeInvalidVmParameters
+            )),
+            result.err()
+        );
+    }
+
     #[test]
     fn transact_fails_when_try_to_override_consensus_parameters() {
         let mut client = Interpreter::<_, _, Upgrade>::with_storage(
@@ -0,0 +1,462 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::Outputs,
+    ConsensusParameters,
+    Finalizable,
+    TransactionBuilder,
+};
+use fuel_types::ChainId;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    block_executor::{
+        BlockExecutor,
+        BlockHeader,
+    },
+    checked_transaction::Checked,
+    prelude::*,
+    utxo::{
+        Coin,
+        MemoryUtxoStore,
+        RelayedMessage,
+        UtxoError,
+        UtxoStore,
+        UtxoValidator,
+    },
+};
+
+const AMOUNT: Word = 1_000;
+
+struct Wallet {
+    secret: SecretKey,
+    owner: Address,
+}
+
+impl Wallet {
+    fn random(rng: &mut StdRng) -> Self {
+        let secret = SecretKey::random(rng);
+        let owner = Input::owner(&secret.public_key());
+        Self { secret, owner }
+    }
+}
+
+fn genesis(rng: &mut StdRng, owner: Address) -> (UtxoId, UtxoValidator<MemoryUtxoStore>) {
+    let utxo_id: UtxoId = rng.gen();
+    let mut store = MemoryUtxoStore::new();
+    store
+        .insert_coin(
+            utxo_id,
+            Coin {
+                owner,
+                amount: AMOUNT,
+                asset_id: AssetId::BASE,
+                tx_pointer: TxPointer::default(),
+            },
+        )
+        .unwrap();
+    (utxo_id, UtxoValidator::new(store))
+}
+
+/// Sends `amount` of the coin to the `recipient`, with the change to the `wallet`.
+fn transfer(
+    wallet: &Wallet,
+    utxo_id: UtxoId,
+    coin: Coin,
+    recipient: Address,
+    amount: Word,
+) -> Checked<Script> {
+    TransactionBuilder::script(vec![op::ret(RegId::ONE)].into_iter().collect(), vec![])
+        .script_gas_limit(10_000)
+        .add_unsigned_coin_input(
+            wallet.secret,
+            utxo_id,
+            coin.amount,
+            coin.asset_id,
+            coin.tx_pointer,
+        )
+        .add_output(Output::coin(recipient, amount, coin.asset_id))
+        .add_output(Output::change(wallet.owner, 0, coin.asset_id))
+        .finalize_checked(Default::default())
+}
+
+#[test]
+fn validate__accepts_unspent_coin() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let (utxo_id, validator) = genesis(rng, wallet.owner);
+    let coin = validator.as_ref().coin(&utxo_id).unwrap().unwrap();
+
+    let tx = transfer(&wallet, utxo_id, coin, rng.gen(), 10);
+
+    assert_eq!(validator.validate(tx.transaction()), Ok(()));
+}
+
+#[test]
+fn validate__rejects_inputs_not_matching_the_coin() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let (utxo_id, validator) = genesis(rng, wallet.owner);
+    let coin = validator.as_ref().coin(&utxo_id).unwrap().unwrap();
+
+    let missing = rng.gen();
+    let cases = [
+        (
+            transfer(&wallet, missing, coin, rng.gen(), 10),
+            UtxoError::CoinNotFound(missing),
+        ),
+        (
+            transfer(&Wallet::random(rng), utxo_id, coin, rng.gen(), 10),
+            UtxoError::OwnerMismatch(utxo_id),
+        ),
+        (
+            transfer(
+                &wallet,
+                utxo_id,
+                Coin {
+                    amount: AMOUNT + 1,
+                    ..coin
+                },
+                rng.gen(),
+                10,
+            ),
+            UtxoError::AmountMismatch(utxo_id),
+        ),
+        (
+            transfer(
+                &wallet,
+                utxo_id,
+                Coin {
+                    asset_id: rng.gen(),
+                    ..coin
+                },
+                rng.gen(),
+                10,
+            ),
+            UtxoError::AssetIdMismatch(utxo_id),
+        ),
+        (
+            transfer(
+                &wallet,
+                utxo_id,
+                Coin {
+                    tx_pointer: TxPointer::new(1.into(), 1),
+                    ..coin
+                },
+                rng.gen(),
+                10,
+            ),
+            UtxoError::TxPointerMismatch(utxo_id),
+        ),
+    ];
+
+    for (tx, expected) in cases {
+        assert_eq!(validator.validate(tx.transaction()), Err(expected));
+    }
+}
+
+#[test]
+fn apply__spends_inputs_and_creates_non_empty_outputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let recipient: Address = rng.gen();
+    let (utxo_id, mut validator) = genesis(rng, wallet.owner);
+    let coin = validator.as_ref().coin(&utxo_id).unwrap().unwrap();
+
+    let tx = transfer(&wallet, utxo_id, coin, recipient, 10);
+    let tx_id = tx.id();
+    let tx_pointer = TxPointer::new(1.into(), 0);
+    let created = validator
+        .apply(&tx_id, tx_pointer, tx.transaction(), false)
+        .expect("Valid transaction");
+
+    // The change wasn't set by the execution, so it's empty
+    assert_eq!(created, vec![UtxoId::new(tx_id, 0)]);
+    let store = validator.as_ref();
+    assert_eq!(store.coin(&utxo_id).unwrap(), None);
+    assert_eq!(
+        store.coin(&created[0]).unwrap(),
+        Some(Coin {
+            owner: recipient,
+            amount: 10,
+            asset_id: AssetId::BASE,
+            tx_pointer,
+        })
+    );
+
+    // The spent coin can't be used again
+    assert_eq!(
+        validator.validate(tx.transaction()),
+        Err(UtxoError::CoinNotFound(utxo_id))
+    );
+}
+
+#[test]
+fn apply_block__chains_transfers_through_created_coins() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let alice = Wallet::random(rng);
+    let bob = Wallet::random(rng);
+    let (utxo_id, mut validator) = genesis(rng, alice.owner);
+    let coin = validator.as_ref().coin(&utxo_id).unwrap().unwrap();
+
+    let header = BlockHeader::new(1.into(), 0, ContractId::zeroed(), 0);
+    let mut executor =
+        BlockExecutor::new(MemoryStorage::default(), ConsensusParameters::standard());
+
+    // Alice sends 100 to Bob
+    let tx = transfer(&alice, utxo_id, coin, bob.owner, 100);
+    validator.validate(tx.transaction()).expect("Valid inputs");
+    let block = executor.execute(&header, [tx]).expect("Valid block");
+    let created = validator.apply_block(&block).expect("Valid block");
+
+    let store = validator.as_ref();
+    assert_eq!(created.len(), 2);
+    assert_eq!(store.balance(&bob.owner, &AssetId::BASE), 100);
+    assert_eq!(store.balance(&alice.owner, &AssetId::BASE), 900);
+
+    // Bob sends 40 back to Alice from the received coin
+    let (bob_utxo_id, bob_coin) = store
+        .coins_of(&bob.owner)
+        .map(|(utxo_id, coin)| (*utxo_id, *coin))
+        .next()
+        .expect("Bob received the coin");
+    assert_eq!(bob_coin.tx_pointer, TxPointer::new(1.into(), 0));
+    let tx = transfer(&bob, bob_utxo_id, bob_coin, alice.owner, 40);
+    validator.validate(tx.transaction()).expect("Valid inputs");
+    let header = BlockHeader::new(2.into(), 0, ContractId::zeroed(), 0);
+    let block = executor.execute(&header, [tx]).expect("Valid block");
+    validator.apply_block(&block).expect("Valid block");
+
+    let store = validator.as_ref();
+    assert_eq!(store.balance(&bob.owner, &AssetId::BASE), 60);
+    assert_eq!(store.balance(&alice.owner, &AssetId::BASE), 940);
+    assert_eq!(store.coins().count(), 3);
+}
+
+fn relayed_message(rng: &mut StdRng, wallet: &Wallet, data: Vec<u8>) -> RelayedMessage {
+    RelayedMessage {
+        sender: rng.gen(),
+        recipient: wallet.owner,
+        nonce: rng.gen(),
+        amount: AMOUNT,
+        data,
+        da_height: 1,
+    }
+}
+
+/// Spends the `messages`, with the change to the `wallet`.
+fn message_tx(wallet: &Wallet, messages: &[&RelayedMessage]) -> Script {
+    let mut builder = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    );
+    builder.script_gas_limit(10_000);
+    for message in messages {
+        builder.add_unsigned_message_input(
+            wallet.secret,
+            message.sender,
+            message.nonce,
+            message.amount,
+            message.data.clone(),
+        );
+    }
+    builder
+        .add_output(Output::change(wallet.owner, 0, AssetId::BASE))
+        .finalize()
+}
+
+#[test]
+fn import_message__rejects_duplicated_nonce() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let mut validator = UtxoValidator::new(MemoryUtxoStore::new());
+    let message = relayed_message(rng, &wallet, vec![]);
+
+    assert_eq!(validator.import_message(message.clone()), Ok(()));
+    assert_eq!(
+        validator.import_message(RelayedMessage {
+            amount: 1,
+            ..message.clone()
+        }),
+        Err(UtxoError::MessageAlreadyImported(message.nonce))
+    );
+    assert_eq!(
+        validator.as_ref().message(&message.nonce),
+        Ok(Some(message))
+    );
+}
+
+#[test]
+fn validate__rejects_inputs_not_matching_the_message() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let mut validator = UtxoValidator::new(MemoryUtxoStore::new());
+    let message = relayed_message(rng, &wallet, vec![1, 2, 3]);
+    validator
+        .import_message(message.clone())
+        .expect("New message");
+    let nonce = message.nonce;
+
+    let tx = message_tx(&wallet, &[&message]);
+    assert_eq!(validator.validate(&tx), Ok(()));
+
+    let cases = [
+        (
+            RelayedMessage {
+                nonce: rng.gen(),
+                ..message.clone()
+            },
+            None,
+        ),
+        (
+            RelayedMessage {
+                sender: rng.gen(),
+                ..message.clone()
+            },
+            Some(UtxoError::MessageSenderMismatch(nonce)),
+        ),
+        (
+            RelayedMessage {
+                amount: AMOUNT + 1,
+                ..message.clone()
+            },
+            Some(UtxoError::MessageAmountMismatch(nonce)),
+        ),
+        (
+            RelayedMessage {
+                data: vec![1, 2],
+                ..message.clone()
+            },
+            Some(UtxoError::MessageDataMismatch(nonce)),
+        ),
+        // The message coin input can't spend the message with data
+        (
+            RelayedMessage {
+                data: vec![],
+                ..message.clone()
+            },
+            Some(UtxoError::MessageDataMismatch(nonce)),
+        ),
+    ];
+    for (input, expected) in cases {
+        let tx = message_tx(&wallet, &[&input]);
+        let expected = expected.unwrap_or(UtxoError::MessageNotFound(input.nonce));
+        assert_eq!(validator.validate(&tx), Err(expected));
+    }
+
+    // The input is signed by the recipient
+    let tx = message_tx(&Wallet::random(rng), &[&message]);
+    assert_eq!(
+        validator.validate(&tx),
+        Err(UtxoError::MessageRecipientMismatch(nonce))
+    );
+}
+
+#[test]
+fn apply__spends_messages() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let mut validator = UtxoValidator::new(MemoryUtxoStore::new());
+    let message = relayed_message(rng, &wallet, vec![]);
+    validator
+        .import_message(message.clone())
+        .expect("New message");
+
+    let tx = message_tx(&wallet, &[&message]);
+    validator
+        .apply(
+            &tx.id(&ChainId::default()),
+            TxPointer::default(),
+            &tx,
+            false,
+        )
+        .expect("Unspent message");
+
+    assert_eq!(
+        validator.validate(&tx),
+        Err(UtxoError::MessageSpent(message.nonce))
+    );
+    assert_eq!(validator.as_ref().unspent_messages().count(), 0);
+}
+
+#[test]
+fn apply__keeps_data_messages_of_reverted_transactions() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let mut validator = UtxoValidator::new(MemoryUtxoStore::new());
+    let coin = relayed_message(rng, &wallet, vec![]);
+    let data = relayed_message(rng, &wallet, vec![1, 2, 3]);
+    validator.import_message(coin.clone()).expect("New message");
+    validator.import_message(data.clone()).expect("New message");
+
+    let tx = message_tx(&wallet, &[&coin, &data]);
+    validator
+        .apply(&tx.id(&ChainId::default()), TxPointer::default(), &tx, true)
+        .expect("Unspent messages");
+
+    let store = validator.as_ref();
+    assert_eq!(store.is_message_spent(&coin.nonce), Ok(true));
+    assert_eq!(store.is_message_spent(&data.nonce), Ok(false));
+    assert_eq!(store.unspent_messages().collect::<Vec<_>>(), vec![&data]);
+}
+
+#[test]
+fn apply_block__deposits_relayed_message() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let mut validator = UtxoValidator::new(MemoryUtxoStore::new());
+    let message = relayed_message(rng, &wallet, vec![]);
+    validator
+        .import_message(message.clone())
+        .expect("New message");
+
+    let tx = message_tx(&wallet, &[&message]);
+    validator.validate(&tx).expect("Valid inputs");
+    let tx = tx
+        .into_checked(Default::default(), &ConsensusParameters::standard())
+        .expect("Valid transaction");
+    let header = BlockHeader::new(1.into(), 0, ContractId::zeroed(), 0);
+    let mut executor =
+        BlockExecutor::new(MemoryStorage::default(), ConsensusParameters::standard());
+    let block = executor.execute(&header, [tx]).expect("Valid block");
+    validator.apply_block(&block).expect("Valid block");
+
+    let store = validator.as_ref();
+    assert_eq!(store.is_message_spent(&message.nonce), Ok(true));
+    assert_eq!(store.balance(&wallet.owner, &AssetId::BASE), AMOUNT as u128);
+}
+
+#[test]
+fn apply__skips_contract_outputs() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let wallet = Wallet::random(rng);
+    let (utxo_id, mut validator) = genesis(rng, wallet.owner);
+    let coin = validator.as_ref().coin(&utxo_id).unwrap().unwrap();
+
+    let mut tx = transfer(&wallet, utxo_id, coin, rng.gen(), 10)
+        .transaction()
+        .clone();
+    tx.outputs_mut()
+        .insert(0, Output::contract_created(rng.gen(), rng.gen()));
+    let created: Vec<_> = validator
+        .apply(&rng.gen(), TxPointer::default(), &tx, false)
+        .expect("Valid transaction")
+        .into_iter()
+        .map(|utxo_id| utxo_id.output_index())
+        .collect();
+
+    assert_eq!(created, vec![1]);
+}
@@ -1,5 +1,6 @@
 use crate::{
     checked_transaction::{
+        CheckError,
         CheckPredicateParams,
         EstimatePredicates,
     },
@@ -66,6 +67,80 @@ fn transaction_can_be_executed_after_maturity() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn transaction_is_invalid_after_expiration() {
+    const EXPIRATION: BlockHeight = BlockHeight::new(2);
+
+    let arb_max_fee = 1;
+
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let tx = TransactionBuilder::script(
+        Some(op::ret(1)).into_iter().collect(),
+        Default::default(),
+    )
+    .max_fee_limit(arb_max_fee)
+    .add_unsigned_coin_input(
+        SecretKey::random(rng),
+        rng.gen(),
+        arb_max_fee,
+        Default::default(),
+        rng.gen(),
+    )
+    .script_gas_limit(100)
+    .expiration(EXPIRATION)
+    .finalize();
+    let params = ConsensusParameters::standard();
+
+    let valid = tx.clone().into_checked(EXPIRATION, &params);
+    let expired = tx.into_checked(EXPIRATION.succ().unwrap(), &params);
+
+    assert!(valid.is_ok());
+    assert_eq!(
+        expired.map(|_| ()),
+        Err(CheckError::Validity(ValidityError::TransactionExpiration))
+    );
+}
+
+#[test]
+fn transaction_owner_is_the_owner_of_the_coin_or_message_input() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let secret = SecretKey::random(rng);
+    let mut builder = TransactionBuilder::script(
+        Some(op::ret(1)).into_iter().collect(),
+        Default::default(),
+    );
+    builder
+        .max_fee_limit(0)
+        .script_gas_limit(100)
+        .add_input(Input::contract(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+        ))
+        .add_unsigned_coin_input(secret, rng.gen(), 0, Default::default(), rng.gen())
+        .add_output(Output::contract(0, rng.gen(), rng.gen()));
+    let params = ConsensusParameters::standard();
+
+    let tx: Transaction = builder.owner_index(1).finalize().into();
+    assert_eq!(tx.owner(), Some(&Input::owner(&secret.public_key())));
+    assert!(tx.into_checked(Default::default(), &params).is_ok());
+
+    for index in [0, 2] {
+        let tx: Transaction = builder.owner_index(index).finalize().into();
+        assert_eq!(tx.owner(), None);
+        assert_eq!(
+            tx.into_checked(Default::default(), &params).map(|_| ()),
+            Err(CheckError::Validity(
+                ValidityError::TransactionOwnerInputIndex {
+                    index: index as Word
+                }
+            ))
+        );
+    }
+}
+
 /// Malleable fields should not affect validity of the create transaction
 #[test]
 fn malleable_fields_do_not_affect_validity_of_create() {
@@ -0,0 +1,217 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    Receipt,
+    VmParameters,
+};
+use fuel_types::canonical::Serialize;
+
+use crate::prelude::*;
+
+use super::test_helpers::assert_panics;
+
+fn run_script(vm_params: VmParameters, script: Vec<Instruction>) -> Vec<Receipt> {
+    let mut test_context = TestBuilder::new(2322u64);
+    test_context.with_vm_params(vm_params);
+    let tx = test_context
+        .start_script(script, vec![])
+        .script_gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    test_context
+        .execute_tx(tx)
+        .expect("Failed to execute the transaction")
+        .receipts()
+        .to_vec()
+}
+
+#[test]
+fn max_receipts__script_panics_when_limit_is_reached() {
+    let vm_params = VmParameters::DEFAULT.with_max_receipts(10);
+    let receipts = run_script(
+        vm_params,
+        vec![
+            op::log(RegId::ZERO, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::jmpb(RegId::ZERO, 0),
+        ],
+    );
+
+    assert_eq!(receipts.len(), 10);
+    assert_panics(&receipts, PanicReason::TooManyReceipts);
+}
+
+#[test]
+fn max_receipts__script_succeeds_below_limit() {
+    let vm_params = VmParameters::DEFAULT.with_max_receipts(10);
+    let receipts = run_script(
+        vm_params,
+        vec![
+            // The last two slots are reserved for the panic and the script result
+            op::movi(0x10, 7),
+            op::log(RegId::ZERO, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::subi(0x10, 0x10, 1),
+            op::jnzb(0x10, RegId::ZERO, 1),
+            op::ret(RegId::ONE),
+        ],
+    );
+
+    assert_eq!(receipts.len(), 9);
+    assert!(matches!(
+        receipts.last(),
+        Some(Receipt::ScriptResult {
+            result: ScriptExecutionResult::Success,
+            ..
+        })
+    ));
+}
+
+/// Calls the contract with `max_call_depth`, where the contract calls itself until
+/// it runs out of gas.
+fn run_recursive_call(max_call_depth: Word) -> Vec<Receipt> {
+    let mut test_context = TestBuilder::new(2322u64);
+    test_context
+        .with_vm_params(VmParameters::DEFAULT.with_max_call_depth(max_call_depth));
+
+    let contract_id = test_context
+        .setup_contract(
+            vec![
+                // The id of the current contract is at the start of the call frame
+                op::movi(0x10, Call::LEN as u32),
+                op::aloc(0x10),
+                op::mcpi(RegId::HP, RegId::FP, ContractId::LEN as u16),
+                op::call(RegId::HP, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            None,
+            None,
+        )
+        .contract_id;
+
+    let (script, _) = crate::script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.get_tx_params().tx_offset()
+    );
+    let tx = test_context
+        .start_script(script, Call::new(contract_id, 0, 0).to_bytes())
+        .script_gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    test_context
+        .execute_tx(tx)
+        .expect("Failed to execute the transaction")
+        .receipts()
+        .to_vec()
+}
+
+#[test]
+fn max_call_depth__call_panics_when_limit_is_reached() {
+    let receipts = run_recursive_call(3);
+
+    let calls = receipts
+        .iter()
+        .filter(|receipt| matches!(receipt, Receipt::Call { .. }))
+        .count();
+    assert_eq!(calls, 3);
+    assert_panics(&receipts, PanicReason::CallDepthExceeded);
+}
+
+#[test]
+fn max_call_depth__default_is_bounded_by_gas() {
+    let receipts = run_recursive_call(VmParameters::DEFAULT.max_call_depth());
+
+    assert_panics(&receipts, PanicReason::OutOfGas);
+}
+
+const MEMORY_LIMIT: Word = 1024 * 1024;
+
+fn memory_limit_script(cfe: bool) -> Vec<Instruction> {
+    let grow = if cfe { op::cfe(0x10) } else { op::aloc(0x10) };
+
+    vec![
+        // Half of the limit
+        op::movi(0x10, 1),
+        op::slli(0x10, 0x10, 19),
+        op::aloc(0x10),
+        grow,
+        op::ret(RegId::ONE),
+    ]
+}
+
+#[test]
+fn max_memory__heap_growth_past_limit_panics() {
+    let vm_params = VmParameters::DEFAULT.with_max_memory(MEMORY_LIMIT);
+    let receipts = run_script(vm_params, memory_limit_script(false));
+
+    assert_panics(&receipts, PanicReason::MemoryGrowthOverlap);
+}
+
+#[test]
+fn max_memory__stack_growth_past_limit_panics() {
+    let vm_params = VmParameters::DEFAULT.with_max_memory(MEMORY_LIMIT);
+    let receipts = run_script(vm_params, memory_limit_script(true));
+
+    assert_panics(&receipts, PanicReason::MemoryGrowthOverlap);
+}
+
+#[test]
+fn max_memory__growth_within_default_limit_succeeds() {
+    for cfe in [false, true] {
+        let receipts = run_script(VmParameters::DEFAULT, memory_limit_script(cfe));
+
+        assert!(matches!(
+            receipts.last(),
+            Some(Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn validate__accepts_default_params() {
+    let params = ConsensusParameters::standard();
+
+    assert_eq!(params.vm_params().validate(params.tx_params()), Ok(()));
+}
+
+#[test]
+fn validate__rejects_params_out_of_safe_ranges() {
+    let params = ConsensusParameters::standard();
+    let tx_params = params.tx_params();
+    let max_tx_end = tx_params.tx_offset() as Word + tx_params.max_size();
+
+    for vm_params in [
+        VmParameters::DEFAULT.with_max_receipts(VmParameters::MIN_RECEIPTS - 1),
+        VmParameters::DEFAULT.with_max_receipts(VmParameters::MAX_RECEIPTS + 1),
+        VmParameters::DEFAULT.with_max_call_depth(0),
+        VmParameters::DEFAULT.with_max_memory(VmParameters::MAX_MEMORY + 1),
+        VmParameters::DEFAULT.with_max_memory(max_tx_end - 1),
+    ] {
+        assert_eq!(
+            vm_params.validate(tx_params),
+            Err(ValidityError::TransactionUpgradeInvalidVmParameters),
+            "{vm_params:?}"
+        );
+    }
+}
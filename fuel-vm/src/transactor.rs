@@ -3,7 +3,9 @@
 use crate::{
     backtrace::Backtrace,
     checked_transaction::{
+        CheckError,
         Checked,
+        Checks,
         IntoChecked,
         Ready,
     },
@@ -12,9 +14,13 @@ use crate::{
         CheckedMetadata,
         EcalHandler,
         ExecutableTransaction,
+        ExecutionHook,
+        ExecutionStatistics,
         Interpreter,
         InterpreterParams,
         Memory,
+        MemoryInstance,
+        NoopHook,
         NotSupportedEcal,
     },
     state::{
@@ -22,20 +28,31 @@ use crate::{
         StateTransition,
         StateTransitionRef,
     },
-    storage::InterpreterStorage,
+    storage::{
+        InterpreterStorage,
+        OverlayStorage,
+        StateOverrides,
+    },
 };
+use alloc::vec::Vec;
+use fuel_asm::PanicReason;
 use fuel_tx::{
+    field::ScriptGasLimit,
+    ConsensusParameters,
     Create,
     FeeParameters,
     GasCosts,
     Receipt,
     Script,
+    TransactionFee,
     Upgrade,
     Upload,
+    ValidityError,
 };
+use fuel_types::Word;
 
-#[cfg(any(test, feature = "test-helpers"))]
-use crate::interpreter::MemoryInstance;
+#[cfg(feature = "std")]
+use crate::interpreter::MemoryDiagnostic;
 
 #[derive(Debug)]
 /// State machine to execute transactions and provide runtime entities on
@@ -45,11 +62,11 @@ use crate::interpreter::MemoryInstance;
 /// builder`.
 ///
 /// Based on <https://doc.rust-lang.org/1.5.0/style/ownership/builders.html#non-consuming-builders-preferred>
-pub struct Transactor<M, S, Tx, Ecal = NotSupportedEcal>
+pub struct Transactor<M, S, Tx, Ecal = NotSupportedEcal, Hook = NoopHook>
 where
     S: InterpreterStorage,
 {
-    interpreter: Interpreter<M, S, Tx, Ecal>,
+    interpreter: Interpreter<M, S, Tx, Ecal, Hook>,
     program_state: Option<ProgramState>,
     error: Option<InterpreterError<S::DataError>>,
 }
@@ -73,7 +90,7 @@ where
         }
     }
 }
-impl<M, S, Tx, Ecal> Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Transactor<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
@@ -148,7 +165,7 @@ where
     }
 
     /// Gets the interpreter.
-    pub fn interpreter(&self) -> &Interpreter<M, S, Tx, Ecal> {
+    pub fn interpreter(&self) -> &Interpreter<M, S, Tx, Ecal, Hook> {
         &self.interpreter
     }
 
@@ -172,9 +189,66 @@ where
     pub fn tx_offset(&self) -> usize {
         self.interpreter.tx_offset()
     }
+
+    /// Read access to the execution hook of the interpreter.
+    pub fn execution_hook(&self) -> &Hook {
+        self.interpreter.execution_hook()
+    }
+
+    /// Replace the execution hook of the interpreter, e.g. with
+    /// [`ExecutionStatistics`] to collect the statistics of the execution.
+    pub fn with_execution_hook<H>(self, hook: H) -> Transactor<M, S, Tx, Ecal, H> {
+        Transactor {
+            interpreter: self.interpreter.with_execution_hook(hook),
+            program_state: self.program_state,
+            error: self.error,
+        }
+    }
+}
+
+impl<M, S, Tx, Ecal> Transactor<M, S, Tx, Ecal, ExecutionStatistics>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    Ecal: EcalHandler,
+{
+    /// Statistics of the execution of the last transaction.
+    ///
+    /// Follows the same criteria as [`Self::state_transition`] to return
+    /// `None`.
+    pub fn statistics(&self) -> Option<&ExecutionStatistics> {
+        self.program_state
+            .is_some()
+            .then(|| self.interpreter.execution_hook())
+    }
+
+    /// State transition representation after the execution of a transaction, next to
+    /// the statistics of the execution.
+    ///
+    /// Follows the same criteria as [`Self::state_transition`] to return
+    /// `None`.
+    pub fn state_transition_with_statistics(
+        &self,
+    ) -> Option<(StateTransitionRef<'_, Tx>, &ExecutionStatistics)> {
+        self.state_transition()
+            .map(|state_transition| (state_transition, self.interpreter.execution_hook()))
+    }
+
+    /// Owned state transition representation after the execution of a transaction,
+    /// next to the statistics of the execution.
+    ///
+    /// Follows the same criteria as [`Self::state_transition`] to return
+    /// `None`.
+    pub fn to_owned_state_transition_with_statistics(
+        &self,
+    ) -> Option<(StateTransition<Tx>, ExecutionStatistics)> {
+        self.to_owned_state_transition().map(|state_transition| {
+            (state_transition, self.interpreter.execution_hook().clone())
+        })
+    }
 }
 
-impl<M, S, Ecal> Transactor<M, S, Script, Ecal>
+impl<M, S, Ecal, Hook> Transactor<M, S, Script, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
@@ -189,6 +263,27 @@ where
             .then(|| self.interpreter.receipts())
     }
 
+    /// Suspicious memory accesses found by the memory sanitizer during the execution
+    /// of a transaction, see [`Self::with_memory_sanitizer`].
+    ///
+    /// Follows the same criteria as [`Self::state_transition`] to return
+    /// `None`.
+    #[cfg(feature = "std")]
+    pub fn memory_diagnostics(&self) -> Option<Vec<MemoryDiagnostic>> {
+        self.program_state
+            .is_some()
+            .then(|| self.interpreter.memory_diagnostics())
+    }
+
+    /// Enables the memory sanitizer, which reports the suspicious memory accesses
+    /// alongside the receipts without changing the execution, see
+    /// [`MemoryInstance::enable_sanitizer`].
+    #[cfg(feature = "std")]
+    pub fn with_memory_sanitizer(mut self) -> Self {
+        self.interpreter = self.interpreter.with_memory_sanitizer();
+        self
+    }
+
     /// Generate a backtrace when at least one receipt of `ScriptResult` was
     /// found.
     pub fn backtrace(&self) -> Option<Backtrace> {
@@ -199,7 +294,247 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Transactor<M, S, Tx, Ecal>
+impl<M, S, Ecal, Hook> Transactor<M, S, Script, Ecal, Hook>
+where
+    M: Memory,
+    S: InterpreterStorage,
+    Ecal: EcalHandler,
+    Hook: ExecutionHook,
+{
+    /// Finds the minimal `script_gas_limit` for the `checked` script.
+    ///
+    /// The script is executed with its own `script_gas_limit` first, which serves as
+    /// the upper bound. Then the gas used by that run is tried as the limit, and if
+    /// it changes the outcome, a smaller limit than the upper bound that doesn't is
+    /// searched with a binary search. The outcome is considered unchanged if the same
+    /// receipts are produced, ignoring the gas forwarded with calls and the gas used
+    /// by the script.
+    ///
+    /// Every returned limit was executed and produced the outcome of the upper bound.
+    /// The search assumes that the outcome doesn't change for the limits above the
+    /// one producing it. A script that inspects its gas, e.g. via `$cgas`, may break
+    /// this assumption, and then the limit is not necessarily the minimal one.
+    ///
+    /// The transaction is checked again with every tried limit against the
+    /// `consensus_params`, except for the signatures that don't sign the modified
+    /// transaction. Every run is executed on top of an [`OverlayStorage`] with the
+    /// memory of the transactor, so the storage remains untouched.
+    ///
+    /// If the script runs out of gas with its own `script_gas_limit`, the limit is not
+    /// an upper bound, and [`PanicReason::OutOfGas`] is returned. Other failures of the
+    /// script, like reverts, are the outcome preserved by the estimation.
+    pub fn estimate_script_gas(
+        &mut self,
+        checked: Checked<Script>,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<ScriptGasEstimation, InterpreterError<S::DataError>> {
+        let upper_bound = *checked.transaction().script_gas_limit();
+        let reference = self.dry_run_script(&checked, upper_bound, consensus_params)?;
+        let out_of_gas = reference.receipts.iter().any(|receipt| match receipt {
+            Receipt::Panic { reason, .. } => *reason.reason() == PanicReason::OutOfGas,
+            _ => false,
+        });
+        if out_of_gas {
+            return Err(InterpreterError::Panic(PanicReason::OutOfGas))
+        }
+
+        let mut low = reference.gas_used;
+        let mut best =
+            self.dry_run_script(&checked, reference.gas_used, consensus_params)?;
+
+        if !same_outcome(&reference.receipts, &best.receipts) {
+            best = reference.clone();
+            // Invariant: the outcome differs for `low` and matches for `best`.
+            while best.script_gas_limit.saturating_sub(low) > 1 {
+                let mid =
+                    low.saturating_add(best.script_gas_limit.saturating_sub(low) / 2);
+                let candidate = self.dry_run_script(&checked, mid, consensus_params)?;
+
+                if same_outcome(&reference.receipts, &candidate.receipts) {
+                    best = candidate;
+                } else {
+                    low = mid;
+                }
+            }
+        }
+
+        let (mut tx, _) = checked.into();
+        *tx.script_gas_limit_mut() = best.script_gas_limit;
+        let fee = TransactionFee::checked_from_tx(
+            self.gas_costs(),
+            self.fee_params(),
+            &tx,
+            self.interpreter.gas_price(),
+        )
+        .ok_or(InterpreterError::CheckError(CheckError::Validity(
+            ValidityError::BalanceOverflow,
+        )))?;
+
+        Ok(ScriptGasEstimation {
+            script_gas_limit: best.script_gas_limit,
+            gas_used: best.gas_used,
+            fee,
+            receipts: best.receipts,
+        })
+    }
+
+    /// Executes the `checked` script with the `script_gas_limit` in a separate memory
+    /// on top of an [`OverlayStorage`], so the transactor remains untouched.
+    fn dry_run_script(
+        &mut self,
+        checked: &Checked<Script>,
+        script_gas_limit: Word,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<DryRun, InterpreterError<S::DataError>> {
+        let ready = with_script_gas_limit(checked, script_gas_limit, consensus_params)
+            .and_then(|checked| {
+                checked.into_ready(
+                    self.interpreter.gas_price(),
+                    self.gas_costs(),
+                    self.fee_params(),
+                )
+            })
+            .map_err(InterpreterError::CheckError)?;
+
+        let interpreter_params = self.interpreter.interpreter_params().clone();
+        let ecal_state = self.interpreter.ecal_state().clone();
+        let storage: &S = self.interpreter.as_ref();
+        let mut interpreter = Interpreter::<_, _, Script, Ecal>::with_storage_and_ecal(
+            MemoryInstance::new(),
+            OverlayStorage::new(storage),
+            interpreter_params,
+            ecal_state,
+        );
+        let receipts = interpreter.transact(ready)?.receipts().to_vec();
+        let gas_used = receipts
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                _ => None,
+            })
+            .ok_or(InterpreterError::NoTransactionInitialized)?;
+
+        Ok(DryRun {
+            script_gas_limit,
+            gas_used,
+            receipts,
+        })
+    }
+}
+
+impl<M, S, Ecal, Hook> Transactor<M, S, Script, Ecal, Hook>
+where
+    M: Memory,
+    S: InterpreterStorage,
+    Ecal: EcalHandler,
+{
+    /// Executes the `checked` script against the storage of the transactor with the
+    /// `overrides` applied, e.g. replaced contract bytecode or forced balances.
+    ///
+    /// The script runs in a separate memory on top of an [`OverlayStorage`], so
+    /// neither the storage nor the state of the last transaction are modified. The
+    /// signatures of the transaction are verified unless
+    /// [`StateOverrides::with_skipped_signature_checks`] is set.
+    pub fn dry_run_with_overrides(
+        &mut self,
+        checked: Checked<Script>,
+        overrides: &StateOverrides,
+    ) -> Result<StateTransition<Script>, InterpreterError<S::DataError>> {
+        let checked = if overrides.skip_signature_checks() {
+            checked
+        } else {
+            checked
+                .check_signatures(&self.interpreter.chain_id())
+                .map_err(InterpreterError::CheckError)?
+        };
+        let ready = checked
+            .into_ready(
+                self.interpreter.gas_price(),
+                self.gas_costs(),
+                self.fee_params(),
+            )
+            .map_err(InterpreterError::CheckError)?;
+
+        let interpreter_params = self.interpreter.interpreter_params().clone();
+        let ecal_state = self.interpreter.ecal_state().clone();
+        let storage: &S = self.interpreter.as_ref();
+        let mut interpreter = Interpreter::with_storage_and_ecal(
+            MemoryInstance::new(),
+            OverlayStorage::with_overrides(storage, overrides),
+            interpreter_params,
+            ecal_state,
+        );
+        let state = interpreter.transact(ready)?;
+
+        Ok(state.into())
+    }
+}
+
+/// Result of [`Transactor::estimate_script_gas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptGasEstimation {
+    /// The minimal `script_gas_limit` that doesn't change the outcome of the script.
+    pub script_gas_limit: Word,
+    /// The gas used by the script with the estimated `script_gas_limit`.
+    pub gas_used: Word,
+    /// The fee of the transaction with the estimated `script_gas_limit`.
+    pub fee: TransactionFee,
+    /// The receipts of the script with the estimated `script_gas_limit`.
+    pub receipts: Vec<Receipt>,
+}
+
+/// A single run of [`Transactor::estimate_script_gas`].
+#[derive(Debug, Clone)]
+struct DryRun {
+    script_gas_limit: Word,
+    gas_used: Word,
+    receipts: Vec<Receipt>,
+}
+
+/// Rebuilds the `checked` script with a different `script_gas_limit` and checks it
+/// again at the same block height.
+///
+/// The signatures don't sign the modified transaction, and the predicates may depend
+/// on it, so [`Checks::Signatures`] and [`Checks::Predicates`] are cleared, and the
+/// result is suitable only for the dry runs.
+fn with_script_gas_limit(
+    checked: &Checked<Script>,
+    script_gas_limit: Word,
+    consensus_params: &ConsensusParameters,
+) -> Result<Checked<Script>, CheckError> {
+    let checks = checked
+        .checks()
+        .difference(Checks::Signatures | Checks::Predicates);
+    let block_height = checked.metadata().block_height;
+    let (mut tx, _) = checked.clone().into();
+    *tx.script_gas_limit_mut() = script_gas_limit;
+    let (tx, metadata) = tx
+        .into_checked_basic(block_height, consensus_params)?
+        .into();
+
+    Ok(Checked::new(tx, metadata, checks))
+}
+
+/// Compares the receipts of two runs, ignoring the gas related fields.
+fn same_outcome(reference: &[Receipt], candidate: &[Receipt]) -> bool {
+    reference.len() == candidate.len()
+        && reference
+            .iter()
+            .zip(candidate)
+            .all(|(a, b)| without_gas(a) == without_gas(b))
+}
+
+fn without_gas(receipt: &Receipt) -> Receipt {
+    let mut receipt = receipt.clone();
+    match &mut receipt {
+        Receipt::Call { gas, .. } => *gas = 0,
+        Receipt::ScriptResult { gas_used, .. } => *gas_used = 0,
+        _ => {}
+    }
+    receipt
+}
+
+impl<M, S, Tx, Ecal, Hook> Transactor<M, S, Tx, Ecal, Hook>
 where
     S: InterpreterStorage,
 {
@@ -276,13 +611,14 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> Transactor<M, S, Tx, Ecal, Hook>
 where
     M: Memory,
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
     <Tx as IntoChecked>::Metadata: CheckedMetadata,
     Ecal: EcalHandler,
+    Hook: ExecutionHook,
 {
     /// Execute a transaction, and return the new state of the transactor
     pub fn transact(&mut self, tx: Checked<Tx>) -> &mut Self {
@@ -319,12 +655,13 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> From<Interpreter<M, S, Tx, Ecal>> for Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> From<Interpreter<M, S, Tx, Ecal, Hook>>
+    for Transactor<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
 {
-    fn from(interpreter: Interpreter<M, S, Tx, Ecal>) -> Self {
+    fn from(interpreter: Interpreter<M, S, Tx, Ecal, Hook>) -> Self {
         let program_state = None;
         let error = None;
 
@@ -336,28 +673,30 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> From<Transactor<M, S, Tx, Ecal>> for Interpreter<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> From<Transactor<M, S, Tx, Ecal, Hook>>
+    for Interpreter<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
 {
-    fn from(transactor: Transactor<M, S, Tx, Ecal>) -> Self {
+    fn from(transactor: Transactor<M, S, Tx, Ecal, Hook>) -> Self {
         transactor.interpreter
     }
 }
 
-impl<M, S, Tx, Ecal> AsRef<Interpreter<M, S, Tx, Ecal>> for Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> AsRef<Interpreter<M, S, Tx, Ecal, Hook>>
+    for Transactor<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
     Ecal: EcalHandler,
 {
-    fn as_ref(&self) -> &Interpreter<M, S, Tx, Ecal> {
+    fn as_ref(&self) -> &Interpreter<M, S, Tx, Ecal, Hook> {
         &self.interpreter
     }
 }
 
-impl<M, S, Tx, Ecal> AsRef<S> for Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> AsRef<S> for Transactor<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
@@ -367,7 +706,7 @@ where
     }
 }
 
-impl<M, S, Tx, Ecal> AsMut<S> for Transactor<M, S, Tx, Ecal>
+impl<M, S, Tx, Ecal, Hook> AsMut<S> for Transactor<M, S, Tx, Ecal, Hook>
 where
     Tx: ExecutableTransaction,
     S: InterpreterStorage,
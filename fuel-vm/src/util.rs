@@ -146,6 +146,7 @@ pub mod test_helpers {
         Transaction,
         TransactionBuilder,
         TxParameters,
+        VmParameters,
         Witness,
     };
     use fuel_types::{
@@ -332,6 +333,11 @@ pub mod test_helpers {
             self
         }
 
+        pub fn with_vm_params(&mut self, vm_params: VmParameters) -> &mut TestBuilder {
+            self.consensus_params.set_vm_params(vm_params);
+            self
+        }
+
         pub fn build(&mut self) -> Checked<Script> {
             self.builder.max_fee_limit(self.max_fee_limit);
             self.builder.with_tx_params(*self.get_tx_params());
@@ -378,6 +384,10 @@ pub mod test_helpers {
             self.consensus_params.privileged_address()
         }
 
+        pub fn get_vm_params(&self) -> &VmParameters {
+            self.consensus_params.vm_params()
+        }
+
         pub fn get_chain_id(&self) -> ChainId {
             self.consensus_params.chain_id()
         }
@@ -0,0 +1,544 @@
+//! The set of unspent transaction outputs.
+//!
+//! [`Checked`](crate::checked_transaction::Checked) transactions are valid on their
+//! own, but their coin and message inputs still have to be checked against the state
+//! of the chain. [`UtxoValidator`] verifies them against a [`UtxoStore`] and, after
+//! the execution, spends the inputs and adds the coin outputs to the store.
+//!
+//! The messages relayed from the L1 are imported into the store with
+//! [`UtxoValidator::import_message`] before they can be spent by the message inputs.
+
+use alloc::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::convert::Infallible;
+
+use fuel_tx::{
+    field,
+    Input,
+    Output,
+    Transaction,
+    TxId,
+    TxPointer,
+    UtxoId,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    MessageId,
+    Nonce,
+    Word,
+};
+
+use crate::block_executor::ExecutedBlock;
+
+/// The unspent coin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coin {
+    /// The owner of the coin.
+    pub owner: Address,
+    /// The amount of the asset.
+    pub amount: Word,
+    /// The asset of the coin.
+    pub asset_id: AssetId,
+    /// The position of the transaction that created the coin.
+    pub tx_pointer: TxPointer,
+}
+
+/// The message relayed from the L1.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelayedMessage {
+    /// The sender of the message on the L1.
+    pub sender: Address,
+    /// The recipient of the message.
+    pub recipient: Address,
+    /// The unique nonce of the message.
+    pub nonce: Nonce,
+    /// The amount of the base asset.
+    pub amount: Word,
+    /// The data of the message, empty for the plain deposits.
+    pub data: Vec<u8>,
+    /// The height of the L1 block that emitted the message.
+    pub da_height: Word,
+}
+
+impl RelayedMessage {
+    /// The id of the message, as computed by the message inputs.
+    pub fn id(&self) -> MessageId {
+        Input::compute_message_id(
+            &self.sender,
+            &self.recipient,
+            &self.nonce,
+            self.amount,
+            &self.data,
+        )
+    }
+}
+
+/// The storage of the unspent coins and the relayed messages.
+pub trait UtxoStore {
+    /// Error implementation for reasons unspecified in the protocol.
+    type Error;
+
+    /// Returns the unspent coin.
+    fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error>;
+
+    /// Adds the unspent coin, returning the previous one with the same id.
+    fn insert_coin(
+        &mut self,
+        utxo_id: UtxoId,
+        coin: Coin,
+    ) -> Result<Option<Coin>, Self::Error>;
+
+    /// Removes the coin, returning it if it was unspent.
+    fn remove_coin(&mut self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error>;
+
+    /// Returns the imported message, spent or not.
+    fn message(&self, nonce: &Nonce) -> Result<Option<RelayedMessage>, Self::Error>;
+
+    /// Adds the relayed message, returning the previous one with the same nonce.
+    fn insert_message(
+        &mut self,
+        message: RelayedMessage,
+    ) -> Result<Option<RelayedMessage>, Self::Error>;
+
+    /// Returns `true` if the message with the `nonce` was spent.
+    fn is_message_spent(&self, nonce: &Nonce) -> Result<bool, Self::Error>;
+
+    /// Marks the message with the `nonce` as spent.
+    fn spend_message(&mut self, nonce: Nonce) -> Result<(), Self::Error>;
+}
+
+impl<S> UtxoStore for &mut S
+where
+    S: UtxoStore,
+{
+    type Error = S::Error;
+
+    fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error> {
+        <S as UtxoStore>::coin(self, utxo_id)
+    }
+
+    fn insert_coin(
+        &mut self,
+        utxo_id: UtxoId,
+        coin: Coin,
+    ) -> Result<Option<Coin>, Self::Error> {
+        <S as UtxoStore>::insert_coin(self, utxo_id, coin)
+    }
+
+    fn remove_coin(&mut self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error> {
+        <S as UtxoStore>::remove_coin(self, utxo_id)
+    }
+
+    fn message(&self, nonce: &Nonce) -> Result<Option<RelayedMessage>, Self::Error> {
+        <S as UtxoStore>::message(self, nonce)
+    }
+
+    fn insert_message(
+        &mut self,
+        message: RelayedMessage,
+    ) -> Result<Option<RelayedMessage>, Self::Error> {
+        <S as UtxoStore>::insert_message(self, message)
+    }
+
+    fn is_message_spent(&self, nonce: &Nonce) -> Result<bool, Self::Error> {
+        <S as UtxoStore>::is_message_spent(self, nonce)
+    }
+
+    fn spend_message(&mut self, nonce: Nonce) -> Result<(), Self::Error> {
+        <S as UtxoStore>::spend_message(self, nonce)
+    }
+}
+
+/// In-memory implementation of the [`UtxoStore`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryUtxoStore {
+    coins: BTreeMap<UtxoId, Coin>,
+    messages: BTreeMap<Nonce, RelayedMessage>,
+    spent_messages: BTreeSet<Nonce>,
+}
+
+impl MemoryUtxoStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the unspent coins.
+    pub fn coins(&self) -> impl Iterator<Item = (&UtxoId, &Coin)> {
+        self.coins.iter()
+    }
+
+    /// Iterate over the unspent coins of the `owner`.
+    pub fn coins_of<'a>(
+        &'a self,
+        owner: &'a Address,
+    ) -> impl Iterator<Item = (&'a UtxoId, &'a Coin)> {
+        self.coins().filter(move |(_, coin)| &coin.owner == owner)
+    }
+
+    /// Iterate over the imported messages that weren't spent.
+    pub fn unspent_messages(&self) -> impl Iterator<Item = &RelayedMessage> {
+        self.messages
+            .values()
+            .filter(|message| !self.spent_messages.contains(&message.nonce))
+    }
+
+    /// The total amount of the unspent coins of the `owner` for the `asset_id`.
+    pub fn balance(&self, owner: &Address, asset_id: &AssetId) -> u128 {
+        self.coins_of(owner)
+            .filter(|(_, coin)| &coin.asset_id == asset_id)
+            .fold(0u128, |balance, (_, coin)| {
+                balance.saturating_add(coin.amount as u128)
+            })
+    }
+}
+
+impl UtxoStore for MemoryUtxoStore {
+    type Error = Infallible;
+
+    fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error> {
+        Ok(self.coins.get(utxo_id).copied())
+    }
+
+    fn insert_coin(
+        &mut self,
+        utxo_id: UtxoId,
+        coin: Coin,
+    ) -> Result<Option<Coin>, Self::Error> {
+        Ok(self.coins.insert(utxo_id, coin))
+    }
+
+    fn remove_coin(&mut self, utxo_id: &UtxoId) -> Result<Option<Coin>, Self::Error> {
+        Ok(self.coins.remove(utxo_id))
+    }
+
+    fn message(&self, nonce: &Nonce) -> Result<Option<RelayedMessage>, Self::Error> {
+        Ok(self.messages.get(nonce).cloned())
+    }
+
+    fn insert_message(
+        &mut self,
+        message: RelayedMessage,
+    ) -> Result<Option<RelayedMessage>, Self::Error> {
+        Ok(self.messages.insert(message.nonce, message))
+    }
+
+    fn is_message_spent(&self, nonce: &Nonce) -> Result<bool, Self::Error> {
+        Ok(self.spent_messages.contains(nonce))
+    }
+
+    fn spend_message(&mut self, nonce: Nonce) -> Result<(), Self::Error> {
+        self.spent_messages.insert(nonce);
+        Ok(())
+    }
+}
+
+/// The input doesn't match the state of the [`UtxoStore`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum UtxoError<StoreError> {
+    /// Store I/O error.
+    #[display(fmt = "Store error: {:?}", _0)]
+    Store(StoreError),
+    /// The coin doesn't exist or was already spent.
+    #[display(fmt = "The coin {} doesn't exist or was spent", _0)]
+    CoinNotFound(UtxoId),
+    /// The owner of the input differs from the owner of the coin.
+    #[display(fmt = "The owner of the coin {} doesn't match", _0)]
+    OwnerMismatch(UtxoId),
+    /// The amount of the input differs from the amount of the coin.
+    #[display(fmt = "The amount of the coin {} doesn't match", _0)]
+    AmountMismatch(UtxoId),
+    /// The asset of the input differs from the asset of the coin.
+    #[display(fmt = "The asset of the coin {} doesn't match", _0)]
+    AssetIdMismatch(UtxoId),
+    /// The `TxPointer` of the input differs from the position of the transaction that
+    /// created the coin.
+    #[display(fmt = "The tx pointer of the coin {} doesn't match", _0)]
+    TxPointerMismatch(UtxoId),
+    /// The message wasn't imported.
+    #[display(fmt = "The message with the nonce {} wasn't relayed", _0)]
+    MessageNotFound(Nonce),
+    /// The message was already spent.
+    #[display(fmt = "The message with the nonce {} was spent", _0)]
+    MessageSpent(Nonce),
+    /// The sender of the input differs from the sender of the message.
+    #[display(fmt = "The sender of the message with the nonce {} doesn't match", _0)]
+    MessageSenderMismatch(Nonce),
+    /// The recipient of the input differs from the recipient of the message.
+    #[display(
+        fmt = "The recipient of the message with the nonce {} doesn't match",
+        _0
+    )]
+    MessageRecipientMismatch(Nonce),
+    /// The amount of the input differs from the amount of the message.
+    #[display(fmt = "The amount of the message with the nonce {} doesn't match", _0)]
+    MessageAmountMismatch(Nonce),
+    /// The data of the input differs from the data of the message.
+    #[display(fmt = "The data of the message with the nonce {} doesn't match", _0)]
+    MessageDataMismatch(Nonce),
+    /// The message with the same nonce was already imported.
+    #[display(fmt = "The message with the nonce {} was already relayed", _0)]
+    MessageAlreadyImported(Nonce),
+}
+
+/// Validates the inputs of the transactions against the [`UtxoStore`] and applies
+/// their outputs.
+#[derive(Debug, Default, Clone)]
+pub struct UtxoValidator<U> {
+    store: U,
+}
+
+impl<U> UtxoValidator<U> {
+    /// Create the validator on top of the `store`.
+    pub const fn new(store: U) -> Self {
+        Self { store }
+    }
+
+    /// Consumes the validator, returning the store.
+    pub fn into_store(self) -> U {
+        self.store
+    }
+}
+
+impl<U> UtxoValidator<U>
+where
+    U: UtxoStore,
+{
+    /// Imports the message relayed from the L1, so the message inputs can spend it.
+    pub fn import_message(
+        &mut self,
+        message: RelayedMessage,
+    ) -> Result<(), UtxoError<U::Error>> {
+        let nonce = message.nonce;
+        if self
+            .store
+            .message(&nonce)
+            .map_err(UtxoError::Store)?
+            .is_some()
+        {
+            return Err(UtxoError::MessageAlreadyImported(nonce));
+        }
+        self.store
+            .insert_message(message)
+            .map_err(UtxoError::Store)?;
+        Ok(())
+    }
+
+    /// Checks that the coin inputs of the transaction are unspent and match the
+    /// coins, and that the message inputs match the unspent relayed messages.
+    pub fn validate<Tx>(&self, tx: &Tx) -> Result<(), UtxoError<U::Error>>
+    where
+        Tx: field::Inputs,
+    {
+        self.validate_inputs(tx.inputs())
+    }
+
+    /// Applies the executed transaction: validates and spends its inputs, then adds
+    /// the coin outputs with a non-zero amount to the store.
+    ///
+    /// If the transaction was `reverted`, the messages with data stay unspent, so they
+    /// can be retried. Returns the ids of the created coins.
+    pub fn apply<Tx>(
+        &mut self,
+        tx_id: &TxId,
+        tx_pointer: TxPointer,
+        tx: &Tx,
+        reverted: bool,
+    ) -> Result<Vec<UtxoId>, UtxoError<U::Error>>
+    where
+        Tx: field::Inputs + field::Outputs,
+    {
+        self.apply_inner(tx_id, tx_pointer, tx.inputs(), tx.outputs(), reverted)
+    }
+
+    /// Applies the transactions included into the `block`, in order.
+    ///
+    /// Returns the ids of the created coins.
+    pub fn apply_block<E>(
+        &mut self,
+        block: &ExecutedBlock<E>,
+    ) -> Result<Vec<UtxoId>, UtxoError<U::Error>> {
+        let mut created = Vec::new();
+        for executed in block.included() {
+            let (inputs, outputs) = match &executed.transaction {
+                Transaction::Script(tx) => {
+                    (field::Inputs::inputs(tx), field::Outputs::outputs(tx))
+                }
+                Transaction::Create(tx) => {
+                    (field::Inputs::inputs(tx), field::Outputs::outputs(tx))
+                }
+                Transaction::Upgrade(tx) => {
+                    (field::Inputs::inputs(tx), field::Outputs::outputs(tx))
+                }
+                Transaction::Upload(tx) => {
+                    (field::Inputs::inputs(tx), field::Outputs::outputs(tx))
+                }
+                Transaction::Mint(_) => continue,
+            };
+            created.extend(self.apply_inner(
+                &executed.id,
+                executed.tx_pointer,
+                inputs,
+                outputs,
+                executed.is_reverted(),
+            )?);
+        }
+        Ok(created)
+    }
+
+    fn validate_inputs(&self, inputs: &[Input]) -> Result<(), UtxoError<U::Error>> {
+        inputs.iter().try_for_each(|input| match input {
+            Input::CoinSigned(_) | Input::CoinPredicate(_) => self.validate_coin(input),
+            Input::MessageCoinSigned(_)
+            | Input::MessageCoinPredicate(_)
+            | Input::MessageDataSigned(_)
+            | Input::MessageDataPredicate(_) => self.validate_message(input),
+            Input::Contract(_) => Ok(()),
+        })
+    }
+
+    fn validate_message(&self, input: &Input) -> Result<(), UtxoError<U::Error>> {
+        let nonce = *input.nonce().expect("The message has a nonce");
+        let message = self
+            .store
+            .message(&nonce)
+            .map_err(UtxoError::Store)?
+            .ok_or(UtxoError::MessageNotFound(nonce))?;
+
+        if self
+            .store
+            .is_message_spent(&nonce)
+            .map_err(UtxoError::Store)?
+        {
+            return Err(UtxoError::MessageSpent(nonce));
+        }
+        if input.sender() != Some(&message.sender) {
+            return Err(UtxoError::MessageSenderMismatch(nonce));
+        }
+        if input.recipient() != Some(&message.recipient) {
+            return Err(UtxoError::MessageRecipientMismatch(nonce));
+        }
+        if input.amount() != Some(message.amount) {
+            return Err(UtxoError::MessageAmountMismatch(nonce));
+        }
+        // The message coin inputs can only spend the messages without data
+        if input.input_data().unwrap_or_default() != message.data.as_slice() {
+            return Err(UtxoError::MessageDataMismatch(nonce));
+        }
+        Ok(())
+    }
+
+    fn validate_coin(&self, input: &Input) -> Result<(), UtxoError<U::Error>> {
+        let utxo_id = *input.utxo_id().expect("The coin has a UTXO id");
+        let coin = self
+            .store
+            .coin(&utxo_id)
+            .map_err(UtxoError::Store)?
+            .ok_or(UtxoError::CoinNotFound(utxo_id))?;
+
+        if input.input_owner() != Some(&coin.owner) {
+            return Err(UtxoError::OwnerMismatch(utxo_id));
+        }
+        if input.amount() != Some(coin.amount) {
+            return Err(UtxoError::AmountMismatch(utxo_id));
+        }
+        // The base asset is only used by the messages
+        if input.asset_id(&AssetId::BASE) != Some(&coin.asset_id) {
+            return Err(UtxoError::AssetIdMismatch(utxo_id));
+        }
+        if input.tx_pointer() != Some(&coin.tx_pointer) {
+            return Err(UtxoError::TxPointerMismatch(utxo_id));
+        }
+        Ok(())
+    }
+
+    fn apply_inner(
+        &mut self,
+        tx_id: &TxId,
+        tx_pointer: TxPointer,
+        inputs: &[Input],
+        outputs: &[Output],
+        reverted: bool,
+    ) -> Result<Vec<UtxoId>, UtxoError<U::Error>> {
+        self.validate_inputs(inputs)?;
+
+        for input in inputs {
+            match input {
+                Input::CoinSigned(_) | Input::CoinPredicate(_) => {
+                    let utxo_id = input.utxo_id().expect("The coin has a UTXO id");
+                    self.store.remove_coin(utxo_id).map_err(UtxoError::Store)?;
+                }
+                Input::MessageDataSigned(_) | Input::MessageDataPredicate(_)
+                    if reverted => {}
+                Input::MessageCoinSigned(_)
+                | Input::MessageCoinPredicate(_)
+                | Input::MessageDataSigned(_)
+                | Input::MessageDataPredicate(_) => {
+                    let nonce = input.nonce().expect("The message has a nonce");
+                    self.store.spend_message(*nonce).map_err(UtxoError::Store)?;
+                }
+                Input::Contract(_) => {}
+            }
+        }
+
+        let mut created = Vec::new();
+        for (output_index, output) in (0..=u16::MAX).zip(outputs) {
+            let (owner, amount, asset_id) = match output {
+                Output::Coin {
+                    to,
+                    amount,
+                    asset_id,
+                }
+                | Output::Change {
+                    to,
+                    amount,
+                    asset_id,
+                }
+                | Output::Variable {
+                    to,
+                    amount,
+                    asset_id,
+                } => (*to, *amount, *asset_id),
+                Output::Contract(_) | Output::ContractCreated { .. } => continue,
+            };
+            // The unused change and variable outputs are empty
+            if amount == 0 {
+                continue;
+            }
+
+            let utxo_id = UtxoId::new(*tx_id, output_index);
+            let coin = Coin {
+                owner,
+                amount,
+                asset_id,
+                tx_pointer,
+            };
+            self.store
+                .insert_coin(utxo_id, coin)
+                .map_err(UtxoError::Store)?;
+            created.push(utxo_id);
+        }
+
+        Ok(created)
+    }
+}
+
+impl<U> AsRef<U> for UtxoValidator<U> {
+    fn as_ref(&self) -> &U {
+        &self.store
+    }
+}
+
+impl<U> AsMut<U> for UtxoValidator<U> {
+    fn as_mut(&mut self) -> &mut U {
+        &mut self.store
+    }
+}